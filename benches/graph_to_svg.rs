@@ -1,6 +1,20 @@
 mod common;
 use common::build_2d_grid;
 use criterion::{criterion_group, criterion_main, Criterion};
+use petgraph::{graph::Graph, Undirected};
+
+fn build_complete_graph(node_count: usize) -> Graph<(), (), Undirected> {
+    let mut g = Graph::new_undirected();
+    let nodes: Vec<_> = (0..node_count).map(|_| g.add_node(())).collect();
+
+    for i in 0..node_count {
+        for j in (i + 1)..node_count {
+            g.add_edge(nodes[i], nodes[j], ());
+        }
+    }
+
+    g
+}
 
 fn graph_to_svg_grid_25(c: &mut Criterion) {
     let graph = build_2d_grid(25, 25);
@@ -56,10 +70,22 @@ fn graph_to_svg_grid_250(c: &mut Criterion) {
     });
 }
 
+fn graph_to_svg_complete_100(c: &mut Criterion) {
+    let graph = build_complete_graph(100);
+
+    c.bench_function("graph_to_svg_complete_100", |b| {
+        b.iter(|| {
+            let settings = visgraph::settings::Settings::default();
+            visgraph::graph_to_svg::graph_to_svg_string(&graph, &settings)
+        });
+    });
+}
+
 criterion_group!(
     benches,
     graph_to_svg_grid_25,
     graph_to_svg_grid_100,
-    graph_to_svg_grid_250
+    graph_to_svg_grid_250,
+    graph_to_svg_complete_100
 );
 criterion_main!(benches);
@@ -0,0 +1,37 @@
+mod common;
+use common::build_2d_grid;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Compares building the SVG of a 250x250 grid on a single thread with building it on all
+/// available threads. Requires the `rayon` feature.
+fn graph_to_svg_grid_250_serial_vs_parallel(c: &mut Criterion) {
+    let graph = build_2d_grid(250, 250);
+    let position_map = |node_id| {
+        let (x, y) = *graph.node_weight(node_id).unwrap();
+        (x as f32 / 249.0, y as f32 / 249.0)
+    };
+    let settings = visgraph::settings::SettingsBuilder::new()
+        .position_map(position_map)
+        .build()
+        .unwrap();
+
+    let single_thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("graph_to_svg_grid_250_parallel");
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            single_thread_pool
+                .install(|| visgraph::graph_to_svg::graph_to_svg_string(&graph, &settings))
+        });
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| visgraph::graph_to_svg::graph_to_svg_string(&graph, &settings));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, graph_to_svg_grid_250_serial_vs_parallel);
+criterion_main!(benches);
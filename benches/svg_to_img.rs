@@ -0,0 +1,41 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use petgraph::graph::UnGraph;
+use visgraph::svg_to_img::Renderer;
+
+/// Compares rendering the SVG of a small graph 100 times with a new renderer per render, which
+/// loads the font every time, with reusing a single renderer. Requires the `img` feature.
+fn svg_to_img_100_renders_new_vs_reused_renderer(c: &mut Criterion) {
+    let mut graph = UnGraph::<(), ()>::new_undirected();
+    let nodes: Vec<_> = (0..2).map(|_| graph.add_node(())).collect();
+    for i in 0..nodes.len() {
+        graph.add_edge(nodes[i], nodes[(i + 1) % nodes.len()], ());
+    }
+    let settings = visgraph::settings::SettingsBuilder::new()
+        .width(50.0)
+        .height(50.0)
+        .node_radius(5.0)
+        .build()
+        .unwrap();
+    let svg_data = visgraph::graph_to_svg::graph_to_svg_string(&graph, &settings);
+
+    let mut group = c.benchmark_group("svg_to_img_100_renders");
+    group.bench_function("new_renderer", |b| {
+        b.iter(|| {
+            for _ in 0..100 {
+                Renderer::new().render(&svg_data, 50.0, 50.0).unwrap();
+            }
+        });
+    });
+    group.bench_function("reused_renderer", |b| {
+        let renderer = Renderer::new();
+        b.iter(|| {
+            for _ in 0..100 {
+                renderer.render(&svg_data, 50.0, 50.0).unwrap();
+            }
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, svg_to_img_100_renders_new_vs_reused_renderer);
+criterion_main!(benches);
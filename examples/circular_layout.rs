@@ -1,9 +1,10 @@
 use petgraph::graph::{EdgeIndex, UnGraph};
-use visgraph::{graph_to_img, settings::SettingsBuilder, Layout};
-
-const RAINBOW_COLORS: [&str; 7] = [
-    "RED", "ORANGE", "YELLOW", "GREEN", "BLUE", "INDIGO", "VIOLET",
-];
+use visgraph::{
+    graph_to_img,
+    palette::{categorical, Palette},
+    settings::SettingsBuilder,
+    Layout,
+};
 
 fn main() {
     // Create a complete graph with 100 nodes.
@@ -19,10 +20,7 @@ fn main() {
         }
     }
 
-    let edge_coloring_fn = |edge_id: EdgeIndex| {
-        let index = edge_id.index() % RAINBOW_COLORS.len();
-        RAINBOW_COLORS[index].to_string()
-    };
+    let edge_coloring_fn = |edge_id: EdgeIndex| categorical(Palette::Rainbow, edge_id.index());
 
     // Customize settings using the SettingsBuilder. Values which are not set will use defaults.
     let settings = SettingsBuilder::new()
@@ -13,9 +13,8 @@ fn main() {
     graph.add_edge(node_c, node_d, ());
     graph.add_edge(node_d, node_a, ());
 
-    // Define custom node and edge label functions. They should impl Fn(NodeIndex) -> String and
-    // Fn(EdgeIndex) -> String, respectively.
-    let node_labels = |node_id| graph.node_weight(node_id).unwrap().to_owned();
+    // Define a custom edge label function. It should impl Fn(EdgeIndex) -> String. The nodes are
+    // labeled with their weights below.
     let edge_labels = |_| "An edge".to_string();
 
     // Customize settings using the SettingsBuilder. Values which are not set will use defaults.
@@ -25,7 +24,7 @@ fn main() {
         .node_radius(50.0)
         .margin_x(0.1)
         .margin_y(0.1)
-        .node_label_fn(node_labels)
+        .node_labels_from_weights(&graph)
         .edge_label_fn(edge_labels)
         .build()
         .expect("Values should be valid.");
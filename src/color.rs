@@ -0,0 +1,335 @@
+//! Helpers for specifying colors and recognition of SVG color values.
+//!
+//! The functions [`hsv`] and [`hsl`] create colors from hue, saturation and value or lightness,
+//! which is convenient inside coloring functions, e.g. to cycle smoothly through the hues. The
+//! recognition of SVG color values is used to validate the colors returned by the coloring
+//! functions, see
+//! [`SettingsBuilder::validate_colors`](crate::settings::SettingsBuilder::validate_colors).
+
+use crate::settings::Color;
+
+/// Color keywords recognized by SVG, see <https://www.w3.org/TR/SVG11/types.html#ColorKeywords>,
+/// along with the special values `none`, `transparent` and `currentColor`. Sorted alphabetically.
+const SVG_COLOR_NAMES: [&str; 150] = [
+    "aliceblue",
+    "antiquewhite",
+    "aqua",
+    "aquamarine",
+    "azure",
+    "beige",
+    "bisque",
+    "black",
+    "blanchedalmond",
+    "blue",
+    "blueviolet",
+    "brown",
+    "burlywood",
+    "cadetblue",
+    "chartreuse",
+    "chocolate",
+    "coral",
+    "cornflowerblue",
+    "cornsilk",
+    "crimson",
+    "currentcolor",
+    "cyan",
+    "darkblue",
+    "darkcyan",
+    "darkgoldenrod",
+    "darkgray",
+    "darkgreen",
+    "darkgrey",
+    "darkkhaki",
+    "darkmagenta",
+    "darkolivegreen",
+    "darkorange",
+    "darkorchid",
+    "darkred",
+    "darksalmon",
+    "darkseagreen",
+    "darkslateblue",
+    "darkslategray",
+    "darkslategrey",
+    "darkturquoise",
+    "darkviolet",
+    "deeppink",
+    "deepskyblue",
+    "dimgray",
+    "dimgrey",
+    "dodgerblue",
+    "firebrick",
+    "floralwhite",
+    "forestgreen",
+    "fuchsia",
+    "gainsboro",
+    "ghostwhite",
+    "gold",
+    "goldenrod",
+    "gray",
+    "green",
+    "greenyellow",
+    "grey",
+    "honeydew",
+    "hotpink",
+    "indianred",
+    "indigo",
+    "ivory",
+    "khaki",
+    "lavender",
+    "lavenderblush",
+    "lawngreen",
+    "lemonchiffon",
+    "lightblue",
+    "lightcoral",
+    "lightcyan",
+    "lightgoldenrodyellow",
+    "lightgray",
+    "lightgreen",
+    "lightgrey",
+    "lightpink",
+    "lightsalmon",
+    "lightseagreen",
+    "lightskyblue",
+    "lightslategray",
+    "lightslategrey",
+    "lightsteelblue",
+    "lightyellow",
+    "lime",
+    "limegreen",
+    "linen",
+    "magenta",
+    "maroon",
+    "mediumaquamarine",
+    "mediumblue",
+    "mediumorchid",
+    "mediumpurple",
+    "mediumseagreen",
+    "mediumslateblue",
+    "mediumspringgreen",
+    "mediumturquoise",
+    "mediumvioletred",
+    "midnightblue",
+    "mintcream",
+    "mistyrose",
+    "moccasin",
+    "navajowhite",
+    "navy",
+    "none",
+    "oldlace",
+    "olive",
+    "olivedrab",
+    "orange",
+    "orangered",
+    "orchid",
+    "palegoldenrod",
+    "palegreen",
+    "paleturquoise",
+    "palevioletred",
+    "papayawhip",
+    "peachpuff",
+    "peru",
+    "pink",
+    "plum",
+    "powderblue",
+    "purple",
+    "red",
+    "rosybrown",
+    "royalblue",
+    "saddlebrown",
+    "salmon",
+    "sandybrown",
+    "seagreen",
+    "seashell",
+    "sienna",
+    "silver",
+    "skyblue",
+    "slateblue",
+    "slategray",
+    "slategrey",
+    "snow",
+    "springgreen",
+    "steelblue",
+    "tan",
+    "teal",
+    "thistle",
+    "tomato",
+    "transparent",
+    "turquoise",
+    "violet",
+    "wheat",
+    "white",
+    "whitesmoke",
+    "yellow",
+    "yellowgreen",
+];
+
+/// Functional notations whose arguments are checked by [`is_valid_svg_color`] along with the
+/// allowed numbers of arguments.
+const COLOR_FUNCTIONS: [(&str, &[usize]); 4] =
+    [("rgb", &[3]), ("rgba", &[4]), ("hsl", &[3]), ("hsla", &[4])];
+
+/// Creates a color from its `hue` in degrees, `saturation` and `value` in the HSV color model.
+///
+/// The hue wraps around, such that e.g. 360.0 is the same as 0.0, red. Saturation and value are
+/// clamped to [0.0, 1.0].
+pub fn hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let value = value.clamp(0.0, 1.0);
+    let chroma = value * saturation;
+    rgb_from_chroma(hue, chroma, value - chroma)
+}
+
+/// Creates a color from its `hue` in degrees, `saturation` and `lightness` in the HSL color model,
+/// which is also used by the `hsl()` notation of SVG.
+///
+/// The hue wraps around, such that e.g. 360.0 is the same as 0.0, red. Saturation and lightness are
+/// clamped to [0.0, 1.0].
+pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Color {
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    rgb_from_chroma(hue, chroma, lightness - chroma / 2.0)
+}
+
+/// Same as [`hsv`], but returns the color as an SVG color value, e.g. `#ff0000`.
+pub fn hsv_to_svg(hue: f32, saturation: f32, value: f32) -> String {
+    hsv(hue, saturation, value).to_svg().into_owned()
+}
+
+/// Same as [`hsl`], but returns the color as an SVG color value, e.g. `#ff0000`.
+pub fn hsl_to_svg(hue: f32, saturation: f32, lightness: f32) -> String {
+    hsl(hue, saturation, lightness).to_svg().into_owned()
+}
+
+/// Converts a color given by its `hue` in degrees, its `chroma` and the amount `offset` added to
+/// all components, all but the hue in [0.0, 1.0], to RGB. This is shared by the HSV and HSL color
+/// models, which only differ in how chroma and offset are computed.
+fn rgb_from_chroma(hue: f32, chroma: f32, offset: f32) -> Color {
+    let hue = if hue.is_finite() {
+        hue.rem_euclid(360.0) / 60.0
+    } else {
+        0.0
+    };
+    let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (red, green, blue) = match hue as u8 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let component = |value: f32| ((value + offset) * 255.0).round().clamp(0.0, 255.0) as u8;
+
+    Color::Rgb(component(red), component(green), component(blue))
+}
+
+/// Returns whether `color` is a color value recognized by SVG renderers.
+///
+/// Recognized are the SVG color keywords (case-insensitive), hex colors with 3, 4, 6 or 8 digits
+/// (e.g. `#F00` or `#FF000080`), the functional notations `rgb()`, `rgba()`, `hsl()` and
+/// `hsla()` with numeric or percentage arguments and references to paint servers like gradients or
+/// patterns, e.g. `url(#my-pattern)`.
+pub(crate) fn is_valid_svg_color(color: &str) -> bool {
+    let color = color.trim();
+
+    if let Some(id) = color
+        .strip_prefix("url(#")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        return !id.is_empty() && !id.contains(|c: char| c.is_whitespace() || c == ')');
+    }
+
+    if let Some(digits) = color.strip_prefix('#') {
+        return matches!(digits.len(), 3 | 4 | 6 | 8)
+            && digits.chars().all(|c| c.is_ascii_hexdigit());
+    }
+
+    if let Some((name, rest)) = color.split_once('(') {
+        let name = name.trim().to_ascii_lowercase();
+        let Some(arguments) = rest.strip_suffix(')') else {
+            return false;
+        };
+        let Some((_, argument_counts)) = COLOR_FUNCTIONS.iter().find(|(f, _)| *f == name) else {
+            return false;
+        };
+        let arguments: Vec<_> = arguments.split(',').map(str::trim).collect();
+        return argument_counts.contains(&arguments.len())
+            && arguments.iter().all(|argument| {
+                let number = argument.strip_suffix('%').unwrap_or(argument);
+                matches!(number.parse::<f32>(), Ok(value) if value.is_finite())
+            });
+    }
+
+    SVG_COLOR_NAMES
+        .binary_search(&color.to_ascii_lowercase().as_str())
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hsl, hsl_to_svg, hsv, hsv_to_svg, is_valid_svg_color, SVG_COLOR_NAMES};
+    use crate::settings::Color;
+
+    #[test]
+    fn test_hsv() {
+        assert_eq!(hsv_to_svg(0.0, 1.0, 1.0), "#ff0000");
+        assert_eq!(hsv_to_svg(120.0, 1.0, 1.0), "#00ff00");
+        assert_eq!(hsv_to_svg(240.0, 1.0, 1.0), "#0000ff");
+        assert_eq!(hsv_to_svg(60.0, 1.0, 1.0), "#ffff00");
+        assert_eq!(hsv_to_svg(30.0, 1.0, 1.0), "#ff8000");
+        assert_eq!(hsv_to_svg(0.0, 0.0, 0.5), "#808080");
+        assert_eq!(hsv_to_svg(200.0, 0.5, 0.0), "#000000");
+        assert_eq!(hsv(210.0, 0.5, 0.8), Color::rgb(102, 153, 204));
+        // The hue wraps around
+        assert_eq!(hsv(360.0, 1.0, 1.0), hsv(0.0, 1.0, 1.0));
+        assert_eq!(hsv(-120.0, 1.0, 1.0), hsv(240.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_hsl() {
+        assert_eq!(hsl_to_svg(0.0, 1.0, 0.5), "#ff0000");
+        assert_eq!(hsl_to_svg(120.0, 1.0, 0.25), "#008000");
+        assert_eq!(hsl_to_svg(240.0, 1.0, 0.75), "#8080ff");
+        assert_eq!(hsl_to_svg(0.0, 0.0, 1.0), "#ffffff");
+        assert_eq!(hsl(300.0, 1.0, 0.5), Color::rgb(255, 0, 255));
+        assert_eq!(hsl(720.0, 2.0, 0.5), hsl(0.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn test_color_names_are_sorted() {
+        assert!(SVG_COLOR_NAMES.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_is_valid_svg_color() {
+        for color in [
+            "red",
+            "DarkSlateGray",
+            "currentColor",
+            "#F00",
+            "#ff000080",
+            "rgb(255, 0, 0)",
+            "rgba(100%, 0%, 0%, 0.5)",
+            "hsl(120, 100%, 50%)",
+            "url(#stripes)",
+        ] {
+            assert!(is_valid_svg_color(color), "{color} should be valid");
+        }
+
+        for color in [
+            "reed",
+            "",
+            "#GG0000",
+            "#12345",
+            "rgb(255, 0)",
+            "rgb(255, 0, 0",
+            "rgb(a, b, c)",
+            "foo(1, 2, 3)",
+            "url(#)",
+            "url(stripes)",
+        ] {
+            assert!(!is_valid_svg_color(color), "{color} should be invalid");
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Ready-made coloring functions for common use cases.
+//!
+//! The functions of this module return closures which can be passed to
+//! [`SettingsBuilder::node_coloring_fn`](crate::settings::SettingsBuilder::node_coloring_fn), e.g.
+//! [`by_degree`], which colors nodes along a [`Colormap`] according to their degree.
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef};
+
+use crate::settings::Color;
+
+/// Stops of the viridis colormap, from low to high values.
+const VIRIDIS: [(u8, u8, u8); 10] = [
+    (0x44, 0x01, 0x54),
+    (0x48, 0x28, 0x78),
+    (0x3e, 0x49, 0x89),
+    (0x31, 0x68, 0x8e),
+    (0x26, 0x82, 0x8e),
+    (0x1f, 0x9e, 0x89),
+    (0x35, 0xb7, 0x79),
+    (0x6e, 0xce, 0x58),
+    (0xb5, 0xde, 0x2b),
+    (0xfd, 0xe7, 0x25),
+];
+
+/// Stops of the plasma colormap, from low to high values.
+const PLASMA: [(u8, u8, u8); 10] = [
+    (0x0d, 0x08, 0x87),
+    (0x46, 0x03, 0x9f),
+    (0x72, 0x01, 0xa8),
+    (0x9c, 0x17, 0x9e),
+    (0xbd, 0x37, 0x86),
+    (0xd8, 0x57, 0x6b),
+    (0xed, 0x79, 0x53),
+    (0xfb, 0x9f, 0x3a),
+    (0xfd, 0xca, 0x26),
+    (0xf0, 0xf9, 0x21),
+];
+
+/// Stops of the grayscale colormap, from low to high values.
+const GRAYSCALE: [(u8, u8, u8); 2] = [(0x00, 0x00, 0x00), (0xff, 0xff, 0xff)];
+
+/// Gradients mapping values in [0.0, 1.0] to colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Colormap {
+    /// The perceptually uniform viridis colormap, from dark purple to yellow. This is the default.
+    #[default]
+    Viridis,
+    /// The perceptually uniform plasma colormap, from dark blue to yellow.
+    Plasma,
+    /// Linear gradient from black to white.
+    Grayscale,
+}
+
+impl Colormap {
+    fn stops(self) -> &'static [(u8, u8, u8)] {
+        match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Plasma => &PLASMA,
+            Colormap::Grayscale => &GRAYSCALE,
+        }
+    }
+
+    /// Returns the color at position `t` of the gradient, where 0.0 is the start and 1.0 the end.
+    ///
+    /// Values outside of [0.0, 1.0] are clamped, NaN is treated as 0.0.
+    pub fn sample(self, t: f32) -> Color {
+        let stops = self.stops();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        let position = t * (stops.len() - 1) as f32;
+        let lower = (position.floor() as usize).min(stops.len() - 2);
+        let fraction = position - lower as f32;
+        let (start, end) = (stops[lower], stops[lower + 1]);
+        let interpolate =
+            |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * fraction).round() as u8;
+
+        Color::Rgb(
+            interpolate(start.0, end.0),
+            interpolate(start.1, end.1),
+            interpolate(start.2, end.2),
+        )
+    }
+}
+
+/// Returns a node coloring function which colors each node according to its degree along the
+/// given `colormap`.
+///
+/// The degree of a node is the number of edges incident to it, with self-loops counted twice and
+/// incoming and outgoing edges counted alike for directed graphs. The nodes with the lowest degree
+/// get the color at the start of the gradient and the nodes with the highest degree the color at
+/// its end. If all nodes have the same degree, all of them get the color at the start.
+///
+/// The degrees are computed once when calling this function, so later changes to the graph are not
+/// reflected.
+pub fn by_degree<G>(graph: G, colormap: Colormap) -> impl Fn(G::NodeId) -> Color
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+{
+    let mut degrees = vec![0usize; graph.node_bound()];
+    for edge in graph.edge_references() {
+        degrees[graph.to_index(edge.source())] += 1;
+        degrees[graph.to_index(edge.target())] += 1;
+    }
+
+    let (mut min_degree, mut max_degree) = (usize::MAX, 0);
+    for node_ref in graph.node_references() {
+        let degree = degrees[graph.to_index(node_ref.id())];
+        min_degree = min_degree.min(degree);
+        max_degree = max_degree.max(degree);
+    }
+
+    let colors: Vec<Color> = degrees
+        .iter()
+        .map(|&degree| {
+            if max_degree > min_degree {
+                colormap.sample(
+                    degree.saturating_sub(min_degree) as f32 / (max_degree - min_degree) as f32,
+                )
+            } else {
+                colormap.sample(0.0)
+            }
+        })
+        .collect();
+
+    move |node_id| colors[graph.to_index(node_id)].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use super::{by_degree, Colormap};
+    use crate::{
+        graph_to_svg::graph_to_svg_string,
+        settings::{Color, SettingsBuilder},
+    };
+
+    #[test]
+    fn test_colormap_sample() {
+        assert_eq!(Colormap::Viridis.sample(0.0), Color::Rgb(0x44, 0x01, 0x54));
+        assert_eq!(Colormap::Viridis.sample(1.0), Color::Rgb(0xfd, 0xe7, 0x25));
+        assert_eq!(
+            Colormap::Grayscale.sample(0.5),
+            Color::Rgb(0x80, 0x80, 0x80)
+        );
+        assert_eq!(Colormap::Plasma.sample(-1.0), Colormap::Plasma.sample(0.0));
+        assert_eq!(Colormap::Plasma.sample(2.0), Colormap::Plasma.sample(1.0));
+    }
+
+    #[test]
+    fn test_by_degree() {
+        // Star with four leaves, one of which has an additional neighbor
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let center = graph.add_node(());
+        let leaves: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        for &leaf in &leaves {
+            graph.add_edge(center, leaf, ());
+        }
+        let outer = graph.add_node(());
+        graph.add_edge(leaves[0], outer, ());
+
+        for colormap in [Colormap::Viridis, Colormap::Plasma, Colormap::Grayscale] {
+            let coloring = by_degree(&graph, colormap);
+            assert_eq!(coloring(center), colormap.sample(1.0));
+            assert_eq!(coloring(leaves[1]), colormap.sample(0.0));
+            assert_eq!(coloring(leaves[0]), colormap.sample(1.0 / 3.0));
+        }
+
+        let settings = SettingsBuilder::new()
+            .node_coloring_fn(by_degree(&graph, Colormap::Viridis))
+            .build()
+            .expect("Values should be valid.");
+        let svg = graph_to_svg_string(&graph, &settings);
+        assert!(svg.contains(&*Colormap::Viridis.sample(1.0).to_svg()));
+    }
+}
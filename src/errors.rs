@@ -1,5 +1,8 @@
+use petgraph::graph::{EdgeIndex, NodeIndex};
 #[cfg(feature = "img")]
 use resvg::usvg::Error as UsvgError;
+#[cfg(feature = "pdf")]
+use svg2pdf::usvg::Error as PdfUsvgError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -16,9 +19,40 @@ pub enum VisGraphError {
     #[cfg(feature = "img")]
     #[error("SVG to Image conversion error: {0}")]
     SvgToImage(#[from] SvgToImageError),
+    /// Error while converting SVG to PDF.
+    #[cfg(feature = "pdf")]
+    #[error("SVG to PDF conversion error: {0}")]
+    SvgToPdf(#[from] SvgToPdfError),
     /// IO error occurred during file operations.
     #[error("IO error: {0}")]
     IO(#[from] std::io::Error),
+    /// Error while computing a bipartite layout.
+    #[error("Bipartite layout error: {0}")]
+    NotBipartite(#[from] NotBipartiteError),
+    /// Error while highlighting a path.
+    #[error("Path error: {0}")]
+    NotConnected(#[from] NotConnectedError),
+    /// Error while constructing a graph from external data.
+    #[error("Interop error: {0}")]
+    Interop(#[from] InteropError),
+    /// A coloring function returned a value which is not a recognized SVG color. Only reported if
+    /// color validation is enabled.
+    #[error("Invalid color {value:?} for {node_or_edge:?}.")]
+    InvalidColor {
+        /// The node or edge which was assigned the invalid color.
+        node_or_edge: GraphElement,
+        /// The invalid color value.
+        value: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A node or an edge of a graph, identified by its index.
+pub enum GraphElement {
+    /// The node with the given index.
+    Node(NodeIndex),
+    /// The edge with the given index.
+    Edge(EdgeIndex),
 }
 
 #[derive(Debug, Error)]
@@ -31,23 +65,147 @@ pub enum SvgToImageError {
     /// IO error occurred during image saving.
     #[error("IO error: {0}")]
     IOError(#[from] std::io::Error),
+    /// The width or height of the image is not strictly positive or too large to allocate the
+    /// image.
+    #[error("Invalid image dimensions: ({0}, {1}).")]
+    InvalidDimensions(f32, f32),
+    /// The dimensions of the SVG data do not match those of the pixmap to render into.
+    #[error("SVG dimensions ({0}, {1}) do not match pixmap dimensions ({2}, {3}).")]
+    DimensionMismatch(f32, f32, u32, u32),
+    /// Error while encoding the image, e.g. as WebP, using the `image` crate.
+    #[cfg(any(feature = "gif", feature = "webp", feature = "png-options"))]
+    #[error("Image encoding error: {0}")]
+    Encoding(#[from] image::ImageError),
+}
+
+#[derive(Debug, Error)]
+#[cfg(feature = "pdf")]
+/// Errors that can occur when converting SVG data to a PDF document.
+pub enum SvgToPdfError {
+    /// Error while parsing SVG data.
+    #[error("SVG parsing error: {0}")]
+    SVGParserError(#[from] PdfUsvgError),
+    /// IO error occurred during document saving.
+    #[error("IO error: {0}")]
+    IOError(#[from] std::io::Error),
+    /// The width or height of the document is not a strictly positive, finite value.
+    #[error("Invalid document dimensions: ({0}, {1}).")]
+    InvalidDimensions(f32, f32),
 }
 
 #[derive(Clone, Copy, Debug, Error, PartialEq)]
 pub enum InvalidSettingsError {
-    /// Invalid dimensions: width or height are not strictly positive values.
-    #[error("Invalid dimensions: ({0}, {1}) must be positive values.")]
+    /// Invalid dimensions: width or height are not strictly positive, finite values.
+    #[error("Invalid dimensions: ({0}, {1}) must be positive, finite values.")]
     Dimensions(f32, f32),
-    /// Invalid radius: radius is not a strictly positive value.
-    #[error("Invalid radius: {0} must be a positive value.")]
+    /// Invalid radius: radius is not a strictly positive, finite value.
+    #[error("Invalid radius: {0} must be a positive, finite value.")]
     Radius(f32),
-    /// Invalid font size: font size is not a strictly positive value.
-    #[error("Invalid font size: {0} must be a positive value.")]
+    /// Invalid font size: font size is not a strictly positive, finite value.
+    #[error("Invalid font size: {0} must be a positive, finite value.")]
     FontSize(f32),
-    /// Invalid stroke width: stroke width is not a strictly positive value.
-    #[error("Invalid stroke width: {0} must be a positive value.")]
+    /// Invalid stroke width: stroke width is not a strictly positive, finite value.
+    #[error("Invalid stroke width: {0} must be a positive, finite value.")]
     StrokeWidth(f32),
     /// Invalid margins: margins are not in the range [0.0, 0.5).
     #[error("Invalid margins: ({0}, {1}) must lie in the range [0.0, 0.5).")]
     Margin(f32, f32),
+    /// Invalid edge label position: position is not in the range [0.0, 1.0].
+    #[error("Invalid edge label position: {0} must lie in the range [0.0, 1.0].")]
+    EdgeLabelPosition(f32),
+    /// Invalid minimum edge length: minimum edge length is not a strictly positive, finite value.
+    #[error("Invalid minimum edge length: {0} must be a positive, finite value.")]
+    MinEdgeLength(f32),
+    /// Invalid hierarchical spacing: a level or sibling spacing is not a strictly positive, finite
+    /// value.
+    #[error("Invalid hierarchical spacing: {0} must be a positive, finite value.")]
+    HierarchicalSpacing(f32),
+    /// Invalid circular layout parameters: the radius is not a strictly positive, finite value or
+    /// a coordinate of the center is not finite.
+    #[error("Invalid circular layout parameters: radius {0} must be a positive, finite value and center ({1}, {2}) finite.")]
+    CircularParams(f32, f32, f32),
+    /// Invalid pinned position: a coordinate of the position a node is pinned to is not in the
+    /// range [0.0, 1.0].
+    #[error("Invalid pinned position of node {0:?}: ({1}, {2}) must lie in the range [0.0, 1.0].")]
+    PinnedPosition(NodeIndex, f32, f32),
+    /// Invalid number of grid divisions: the number of divisions to snap positions to is zero.
+    #[error("Invalid number of grid divisions: {0} must be at least 1.")]
+    GridDivisions(usize),
+    /// Invalid maximum label length: the maximum number of characters of labels is zero.
+    #[error("Invalid maximum label length: {0} must be at least 1.")]
+    LabelMaxChars(usize),
+    /// Invalid title height: the space reserved for the title, which depends on the font size, is
+    /// negative or not smaller than the height of the canvas.
+    #[error("Invalid title height: {0} must be non-negative and smaller than the height {1}.")]
+    TitleHeight(f32, f32),
+}
+
+#[derive(Clone, Copy, Debug, Error, PartialEq)]
+/// Error returned when a graph that is expected to be bipartite contains a cycle of odd length.
+///
+/// Contains the endpoints of an edge which connects two nodes of the same partition.
+#[error("Graph is not bipartite: edge ({0:?}, {1:?}) connects two nodes of the same partition.")]
+pub struct NotBipartiteError(pub NodeIndex, pub NodeIndex);
+
+#[derive(Clone, Copy, Debug, Error, PartialEq)]
+/// Error returned when two consecutive nodes of a path are not connected by an edge.
+///
+/// Contains the two consecutive nodes.
+#[error("Nodes {0:?} and {1:?} are not connected by an edge.")]
+pub struct NotConnectedError(pub NodeIndex, pub NodeIndex);
+
+#[derive(Debug, Error)]
+/// Errors that can occur when constructing a graph or positions from external data, see the
+/// `interop` module.
+pub enum InteropError {
+    /// The adjacency matrix is not square: the given row does not have as many entries as the
+    /// matrix has rows.
+    #[error("Adjacency matrix is not square: row {row} has {len} entries, expected {expected}.")]
+    NotSquare {
+        /// Index of the offending row.
+        row: usize,
+        /// Number of entries of the offending row.
+        len: usize,
+        /// Number of rows of the matrix.
+        expected: usize,
+    },
+    /// The adjacency matrix of an undirected graph is not symmetric: the entries at (i, j) and
+    /// (j, i) differ.
+    #[error("Adjacency matrix is not symmetric: entries ({0}, {1}) and ({1}, {0}) differ.")]
+    NotSymmetric(usize, usize),
+    /// Error while reading CSV data.
+    #[cfg(feature = "csv")]
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    /// Error while reading or writing JSON data.
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A position to be written as JSON is not finite, which JSON cannot represent.
+    #[cfg(feature = "json")]
+    #[error("Position ({x}, {y}) of node {node:?} is not finite.")]
+    NonFinitePosition {
+        /// The node with the non-finite position.
+        node: NodeIndex,
+        /// The x-coordinate of the position.
+        x: f32,
+        /// The y-coordinate of the position.
+        y: f32,
+    },
+    /// A row of an edge list does not consist of a source, a target and an optional weight.
+    #[error("Malformed row on line {line}: expected 2 or 3 fields, found {fields}.")]
+    MalformedRow {
+        /// Line of the offending row, starting at 1.
+        line: u64,
+        /// Number of fields of the offending row.
+        fields: usize,
+    },
+    /// The weight of a row of an edge list is not a number.
+    #[error("Invalid weight on line {line}: {value:?} is not a number.")]
+    InvalidWeight {
+        /// Line of the offending row, starting at 1.
+        line: u64,
+        /// The value which could not be parsed as a weight.
+        value: String,
+    },
 }
@@ -0,0 +1,174 @@
+//! Functionality to embed graphs into standalone HTML documents.
+//!
+//! The main function is [`graph_to_html`], which wraps the SVG data generated by
+//! [`graph_to_svg_string`] into an HTML document with basic pan and zoom support. This is useful to
+//! quickly share an interactive view of a graph, which can be opened in any browser.
+
+use std::hash::Hash;
+
+use petgraph::visit::{
+    EdgeIndexable, GraphProp, IntoEdgeReferences, IntoNeighborsDirected, IntoNodeReferences,
+    NodeIndexable,
+};
+
+use crate::{
+    graph_to_svg::{graph_to_svg_string, MaybeSync, XML_DECLARATION},
+    settings::{Color, NodeFill, Settings},
+};
+
+/// Start of the HTML document, up to the point where the SVG data is inserted.
+const HTML_HEADER: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>visgraph</title>
+<style>
+    html, body { margin: 0; height: 100%; overflow: hidden; }
+    body > svg { display: block; width: 100%; height: 100%; cursor: grab; }
+</style>
+</head>
+<body>
+";
+
+/// End of the HTML document, containing the script for panning (dragging) and zooming (mouse
+/// wheel) by modifying the `viewBox` of the SVG.
+const HTML_FOOTER: &str = "
+<script>
+    const svg = document.querySelector(\"body > svg\");
+    if (!svg.hasAttribute(\"viewBox\")) {
+        svg.setAttribute(\"viewBox\", `0 0 ${svg.getAttribute(\"width\")} ${svg.getAttribute(\"height\")}`);
+    }
+    svg.setAttribute(\"width\", \"100%\");
+    svg.setAttribute(\"height\", \"100%\");
+    let [x, y, width, height] = svg.getAttribute(\"viewBox\").split(/[\\s,]+/).map(Number);
+    const update = () => svg.setAttribute(\"viewBox\", `${x} ${y} ${width} ${height}`);
+
+    svg.addEventListener(\"wheel\", (event) => {
+        event.preventDefault();
+        const factor = event.deltaY > 0 ? 1.1 : 1 / 1.1;
+        const rect = svg.getBoundingClientRect();
+        const pointerX = x + (event.clientX - rect.left) / rect.width * width;
+        const pointerY = y + (event.clientY - rect.top) / rect.height * height;
+        x = pointerX - (pointerX - x) * factor;
+        y = pointerY - (pointerY - y) * factor;
+        width *= factor;
+        height *= factor;
+        update();
+    }, { passive: false });
+
+    let dragStart = null;
+    svg.addEventListener(\"pointerdown\", (event) => {
+        dragStart = { clientX: event.clientX, clientY: event.clientY, x, y };
+        svg.setPointerCapture(event.pointerId);
+    });
+    svg.addEventListener(\"pointermove\", (event) => {
+        if (dragStart === null) {
+            return;
+        }
+        const rect = svg.getBoundingClientRect();
+        x = dragStart.x - (event.clientX - dragStart.clientX) / rect.width * width;
+        y = dragStart.y - (event.clientY - dragStart.clientY) / rect.height * height;
+        update();
+    });
+    svg.addEventListener(\"pointerup\", () => dragStart = null);
+</script>
+</body>
+</html>
+";
+
+/// Generates a standalone HTML document which displays the graph with basic pan and zoom support.
+///
+/// The document embeds the SVG data generated by [`graph_to_svg_string`] with the same settings
+/// as is. The graph can be panned by dragging and zoomed using the mouse wheel.
+///
+/// # Settings
+///
+/// To configure the graph rendering, use the [`SettingsBuilder`](crate::settings::SettingsBuilder)
+/// struct.
+pub fn graph_to_html<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
+    graph: G,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+) -> String
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    let svg_data = graph_to_svg_string(graph, settings);
+    // The XML declaration is only valid at the start of a document
+    let svg_data = svg_data.strip_prefix(XML_DECLARATION).unwrap_or(&svg_data);
+
+    let mut html = String::with_capacity(HTML_HEADER.len() + svg_data.len() + HTML_FOOTER.len());
+    html.push_str(HTML_HEADER);
+    html.push_str(svg_data);
+    html.push_str(HTML_FOOTER);
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::graph_to_html;
+    use crate::{graph_to_svg::graph_to_svg_string, tests::custom_labels_test_case};
+
+    #[test]
+    fn test_graph_to_html() {
+        let (graph, settings) = custom_labels_test_case();
+
+        let html = graph_to_html(&graph, &settings);
+        let svg_data = graph_to_svg_string(&graph, &settings);
+
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(html.contains(&svg_data));
+        assert!(html.contains("<script>"));
+    }
+}
@@ -10,15 +10,26 @@
 
 use std::hash::Hash;
 
-use petgraph::visit::{
-    EdgeIndexable, IntoEdgeReferences, IntoNeighborsDirected, IntoNodeReferences, NodeIndexable,
+use petgraph::{
+    graph::{EdgeIndex, NodeIndex},
+    visit::{
+        EdgeIndexable, GraphBase, GraphProp, IntoEdgeReferences, IntoNeighborsDirected,
+        IntoNodeReferences, NodeIndexable,
+    },
 };
 
 use crate::{
-    errors::VisGraphError, graph_to_svg::graph_to_svg_string, settings::Settings,
+    errors::VisGraphError,
+    graph_to_svg::{try_graph_to_svg_string, MaybeSync},
+    settings::{Color, NodeFill, Settings, SettingsBuilder},
     svg_to_img::svg_to_img,
+    Layout,
 };
 
+/// Resolution in dots per inch with which the rendering resolves canvases in
+/// [`Units`](crate::settings::Units) other than pixels.
+const CSS_DPI: f32 = 96.0;
+
 /// Generate and save an image of a graph to the specified path.
 ///
 /// # Settings
@@ -37,9 +48,38 @@ use crate::{
 /// More examples can be found in the [`examples`](https://github.com/RaoulLuque/visgraph/tree/main/examples)
 /// directory.
 #[cfg(feature = "img")]
-pub fn graph_to_img<G, PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>(
+pub fn graph_to_img<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
     graph: G,
-    settings: &Settings<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
     path: impl AsRef<std::path::Path>,
 ) -> Result<(), VisGraphError>
 where
@@ -47,15 +87,67 @@ where
         + IntoEdgeReferences
         + NodeIndexable
         + EdgeIndexable
-        + IntoNeighborsDirected,
-    G::NodeId: Hash + Eq,
-    PositionMapFn: Fn(G::NodeId) -> (f32, f32),
-    NodeLabelFn: Fn(G::NodeId) -> String,
-    EdgeLabelFn: Fn(G::EdgeId) -> String,
-    NodeColoringFn: Fn(G::NodeId) -> String,
-    EdgeColoringFn: Fn(G::EdgeId) -> String,
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
 {
-    let svg_data = graph_to_svg_string(graph, settings);
-    svg_to_img(&svg_data, settings.width, settings.height, path)?;
+    let svg_data = try_graph_to_svg_string(graph, settings)?;
+    svg_to_img(
+        &svg_data,
+        settings.units.to_pixels(settings.width, CSS_DPI),
+        settings.units.to_pixels(settings.height, CSS_DPI),
+        path,
+    )?;
     Ok(())
 }
+
+/// Generate and save an image of a graph to the specified path using default settings and the
+/// given layout.
+///
+/// This is a shorthand for calling [`graph_to_img`] with settings built by
+/// [`SettingsBuilder`] where only the layout is set. To configure the rendering further, use
+/// [`graph_to_img`] directly.
+///
+/// # Usage
+///
+/// ```
+/// # use petgraph::graph::UnGraph;
+/// # use visgraph::{visualize, Layout};
+/// let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 0)]);
+/// visualize(&graph, Layout::Circular, std::env::temp_dir().join("triangle.png")).unwrap();
+/// ```
+#[cfg(feature = "img")]
+pub fn visualize<G>(
+    graph: G,
+    layout: Layout,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(), VisGraphError>
+where
+    G: GraphBase<NodeId = NodeIndex, EdgeId = EdgeIndex>
+        + IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+{
+    let settings = SettingsBuilder::new().layout(layout).build()?;
+    graph_to_img(graph, &settings, path)
+}
@@ -4,34 +4,181 @@
 //! custom position map or a predefined layout algorithm, respectively.
 //!
 //! Note that if a position map is used, it should return normalized coordinates between 0.0 and
-//! 1.0.
+//! 1.0. Otherwise, consider setting a [`NormalizeMode`] using
+//! [`SettingsBuilder::normalize_positions`](crate::settings::SettingsBuilder::normalize_positions).
 //!
 //! For examples, see the `examples/` directory.
 
-use std::{fmt::Write, hash::Hash};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    hash::Hash,
+};
 
-use petgraph::visit::{
-    EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoNeighborsDirected, IntoNodeReferences,
-    NodeIndexable, NodeRef,
+use fixedbitset::FixedBitSet;
+use petgraph::{
+    graph::{EdgeIndex, NodeIndex},
+    visit::{
+        EdgeIndexable, EdgeRef, GraphProp, IntoEdgeReferences, IntoNeighborsDirected,
+        IntoNodeReferences, NodeIndexable, NodeRef,
+    },
 };
 
 use crate::{
-    errors::VisGraphError,
+    color::is_valid_svg_color,
+    errors::{GraphElement, VisGraphError},
     layout::{
+        auto::resolve_layout,
         bipartite::bipartite_layout,
-        circular::circular_layout,
-        force_directed::{force_directed_layout, DEFAULT_INITIAL_TEMPERATURE, DEFAULT_ITERATIONS},
-        hierarchical::hierarchical_layout,
+        cache::LayoutCache,
+        circular::circular_layout_with_params,
+        clustered::clustered_layout,
+        force_directed::{force_directed_layout_with_params, ForceDirectedParams},
+        hex_grid::hex_grid_layout,
+        hierarchical::{hierarchical_layout_with_params, Orientation},
         random::random_layout,
-        Layout, LayoutOrPositionMap,
+        resolve_overlaps,
+        spiral::spiral_layout_with_params,
+        Layout, LayoutOrPositionMap, NormalizeMode, YAxis,
     },
-    settings::Settings,
+    settings::{ArrowMode, ArrowStyle, Color, Corner, EdgeRouting, NodeFill, Settings, Units},
 };
 
-const EDGE_CLOSENESS_THRESHOLD: f32 = 0.001;
+/// Marker trait which is equivalent to [`Sync`], since the `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "rayon")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// Marker trait which is equivalent to [`Sync`] if the `rayon` feature is enabled and implemented
+/// for all types otherwise.
+///
+/// With the `rayon` feature, the SVG elements of nodes and edges are built in parallel. Therefore,
+/// the graph, its node and edge ids as well as the position map and the label and coloring
+/// functions need to be shareable between threads.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "rayon"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// XML declaration written before the opening <svg> tag if enabled in the settings.
+pub(crate) const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+/// Font size of the title relative to the font size of the labels.
+const TITLE_FONT_SIZE_FACTOR: f32 = 1.5;
+/// Number of cells of the debug grid along each axis, see [`draw_debug_grid`].
+const DEBUG_GRID_DIVISIONS: usize = 10;
+/// Font size of the debug grid labels relative to the font size of the labels.
+const DEBUG_GRID_FONT_SIZE_FACTOR: f32 = 0.6;
+/// Estimated width of a character relative to the font size, used to size the legend box.
+const ESTIMATED_CHAR_WIDTH_FACTOR: f32 = 0.6;
+/// Height of a row of the legend relative to the font size.
+const LEGEND_ROW_HEIGHT_FACTOR: f32 = 1.5;
+/// Font size of the index badges relative to the font size of the labels.
+const INDEX_BADGE_FONT_SIZE_FACTOR: f32 = 0.6;
+/// Radius of the nodes relative to the smallest distance between two nodes if the radius is chosen
+/// automatically. Below 0.5, such that nodes do not touch.
+const AUTO_NODE_RADIUS_FACTOR: f32 = 0.4;
+/// Distance between the two lines of reciprocal edges relative to the node radius, see
+/// [`EdgeShape::ReciprocalLine`].
+const RECIPROCAL_EDGE_GAP_FACTOR: f32 = 0.5;
+/// Length and width of arrowheads relative to the stroke width of the edge.
+const ARROWHEAD_SIZE_FACTOR: f32 = 4.0;
+/// Offset of the node shadows to the bottom right relative to the node radius.
+const NODE_SHADOW_OFFSET_FACTOR: f32 = 0.1;
+/// Standard deviation of the blur of the node shadows relative to the node radius.
+const NODE_SHADOW_BLUR_FACTOR: f32 = 0.1;
+/// Opacity of the node shadows.
+const NODE_SHADOW_OPACITY: f32 = 0.4;
+/// Number of fragments written by [`graph_to_svg_stream`] between two flushes of the writer.
+const STREAM_FLUSH_INTERVAL: usize = 1024;
 const ESTIMATED_SVG_NODE_ENTRY_SIZE: usize = 120;
 const ESTIMATED_SVG_EDGE_ENTRY_SIZE: usize = 200;
 
+/// Shapes in which [`draw_edge`] draws an edge.
+#[derive(Debug, Clone, Copy)]
+enum EdgeShape {
+    /// A straight line.
+    Line,
+    /// A curve following the hierarchy with the given orientation, see [`tree_edge_curve`].
+    TreeCurve(Orientation),
+    /// An orthogonal route, see [`orthogonal_route`].
+    Orthogonal(Option<Orientation>),
+    /// A straight line shifted sideways by the given distance to the right of its direction, which
+    /// ends in an arrowhead. Used for reciprocal edges, such that both directions are visible.
+    ReciprocalLine(f32),
+    /// A quadratic Bézier curve bending to the right of its direction for positive and to the left
+    /// for negative curvatures, see [`quadratic_control_point`].
+    QuadraticCurve(f32),
+}
+
+/// Output produced by [`internal_graph_to_svg`].
+enum Output<'a> {
+    /// The SVG elements.
+    Elements,
+    /// The SVG data is written to the writer one fragment at a time.
+    Stream(&'a mut dyn std::io::Write),
+    /// The assembled SVG data, returned as the header of otherwise empty SVG elements.
+    Assembled {
+        /// Whether the colors are validated, if
+        /// [`validate_colors`](crate::settings::SettingsBuilder::validate_colors) is enabled. Streamed
+        /// output is always validated in that case, the SVG elements never.
+        validated: bool,
+    },
+    /// Same as `Assembled` without validation, but with the canvas cropped to the estimated
+    /// bounding box of all drawn elements plus `padding` pixels on each side.
+    Cropped {
+        /// Space in pixels between the drawn elements and the borders of the cropped canvas.
+        padding: f32,
+    },
+}
+
+/// SVG elements of a drawn graph, as returned by [`graph_to_svg_elements`].
+///
+/// The fragments of the nodes and edges can be inspected, modified or reordered before assembling
+/// them into the SVG data using [`SvgElements::assemble`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SvgElements {
+    /// The opening <svg> tag followed by shared definitions, such as gradients, and the title.
+    pub header: String,
+    /// One fragment per edge in the order of the edge references of the graph, each consisting of
+    /// a <line> (or a <path> for
+    /// [`smooth_tree_edges`](crate::settings::SettingsBuilder::smooth_tree_edges) and
+    /// [`EdgeRouting::Orthogonal`]) and optionally a <text> tag for the label. If
+    /// [`bundle_parallel_edges`](crate::settings::SettingsBuilder::bundle_parallel_edges) is
+    /// enabled, there is one fragment per bundle of parallel edges instead.
+    pub edges: Vec<String>,
+    /// One fragment per node in drawing order, each consisting of a <circle> and optionally a
+    /// <text> tag for the label. Unless a
+    /// [`node_draw_order`](crate::settings::SettingsBuilder::node_draw_order) is set, this is the
+    /// order of the node references of the graph.
+    pub nodes: Vec<String>,
+    /// The legend, if any, followed by the closing </svg> tag.
+    pub footer: String,
+    /// Whether the drawn graph is directed, as reported by [`GraphProp::is_directed`].
+    pub directed: bool,
+}
+
+impl SvgElements {
+    /// Assembles the elements into SVG data.
+    ///
+    /// Edges are placed before nodes, such that nodes are painted on top of the edges.
+    pub fn assemble(&self) -> String {
+        let length = self.header.len()
+            + self.edges.iter().map(String::len).sum::<usize>()
+            + self.nodes.iter().map(String::len).sum::<usize>()
+            + self.footer.len();
+        let mut svg_buffer = String::with_capacity(length);
+
+        svg_buffer.push_str(&self.header);
+        for fragment in self.edges.iter().chain(&self.nodes) {
+            svg_buffer.push_str(fragment);
+        }
+        svg_buffer.push_str(&self.footer);
+        svg_buffer
+    }
+}
+
 /// Generates an SVG representation of the graph using the provided settings and
 /// saves it to the specified path.
 ///
@@ -40,6 +187,12 @@ const ESTIMATED_SVG_EDGE_ENTRY_SIZE: usize = 200;
 /// To configure the graph rendering, use the [`SettingsBuilder`](crate::settings::SettingsBuilder)
 /// struct.
 ///
+/// # Drawing order
+///
+/// Edges (including their labels) are drawn before nodes, so that nodes are painted on top of any
+/// edges passing through or ending at them. Where elements overlap, this means node circles and
+/// labels hide the edges below them.
+///
 /// # Usage
 ///
 /// The following is an example taken from
@@ -48,9 +201,38 @@ const ESTIMATED_SVG_EDGE_ENTRY_SIZE: usize = 200;
 #[allow(clippy::needless_doctest_main)]
 #[doc = include_str!("../examples/graph_to_svg.rs")]
 /// ```
-pub fn graph_to_svg<G, PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>(
+pub fn graph_to_svg<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
     graph: G,
-    settings: &Settings<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
     path: impl AsRef<std::path::Path>,
 ) -> Result<(), VisGraphError>
 where
@@ -58,15 +240,27 @@ where
         + IntoEdgeReferences
         + NodeIndexable
         + EdgeIndexable
-        + IntoNeighborsDirected,
-    G::NodeId: Hash + Eq,
-    PositionMapFn: Fn(G::NodeId) -> (f32, f32),
-    NodeLabelFn: Fn(G::NodeId) -> String,
-    EdgeLabelFn: Fn(G::EdgeId) -> String,
-    NodeColoringFn: Fn(G::NodeId) -> String,
-    EdgeColoringFn: Fn(G::EdgeId) -> String,
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
 {
-    let output = graph_to_svg_string(graph, settings);
+    let output = try_graph_to_svg_string(graph, settings)?;
 
     // Create target directory if it doesn't exist
     if let Some(parent) = path.as_ref().parent() {
@@ -78,7 +272,83 @@ where
     Ok(())
 }
 
+/// Same as [`graph_to_svg`] but writes the SVG data to the provided `writer` instead of saving it
+/// to a file.
+///
+/// The SVG data is never held in memory as a whole: The fragments of the nodes and edges are built
+/// and written one at a time, and the writer is flushed periodically. This bounds the memory usage
+/// for huge graphs, at the cost of not building the fragments in parallel with the `rayon`
+/// feature. Since the writer receives many small writes, consider wrapping it into a
+/// [`BufWriter`](std::io::BufWriter).
+pub fn graph_to_svg_stream<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
+    graph: G,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+    mut writer: impl std::io::Write,
+) -> Result<(), VisGraphError>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    internal_graph_to_svg(graph, settings, Output::Stream(&mut writer))?;
+
+    Ok(())
+}
+
 /// Same as [`graph_to_svg`] but returns the SVG data as a `String` instead of saving it to a file.
+///
+/// Since this cannot fail, colors are not validated, even if
+/// [`validate_colors`](crate::settings::SettingsBuilder::validate_colors) is enabled. Use
+/// [`try_graph_to_svg_string`] to validate them.
 pub fn graph_to_svg_string<
     G,
     PositionMapFn,
@@ -86,160 +356,1347 @@ pub fn graph_to_svg_string<
     EdgeLabelFn,
     NodeColoringFn,
     EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
 >(
     graph: G,
-    settings: &Settings<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
 ) -> String
 where
     G: IntoNodeReferences
         + IntoEdgeReferences
         + NodeIndexable
         + EdgeIndexable
-        + IntoNeighborsDirected,
-    G::NodeId: Hash + Eq,
-    PositionMapFn: Fn(G::NodeId) -> (f32, f32),
-    NodeLabelFn: Fn(G::NodeId) -> String,
-    EdgeLabelFn: Fn(G::EdgeId) -> String,
-    NodeColoringFn: Fn(G::NodeId) -> String,
-    EdgeColoringFn: Fn(G::EdgeId) -> String,
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    internal_graph_to_svg(graph, settings, Output::Assembled { validated: false })
+        .expect("Only streamed or validated output can fail")
+        .header
+}
+
+/// Same as [`graph_to_svg_string`], but validates the colors if
+/// [`validate_colors`](crate::settings::SettingsBuilder::validate_colors) is enabled.
+///
+/// Returns a [`VisGraphError::InvalidColor`] for the first color that is not a recognized SVG
+/// color.
+pub fn try_graph_to_svg_string<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
+    graph: G,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+) -> Result<String, VisGraphError>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    Ok(internal_graph_to_svg(graph, settings, Output::Assembled { validated: true })?.header)
+}
+
+/// Same as [`graph_to_svg_string`], but crops the canvas to the drawn elements plus `padding`
+/// pixels on each side, e.g. to embed tight figures.
+///
+/// The width, height and `viewBox` of the <svg> tag are set to the bounding box of all nodes,
+/// labels, the title and the legend. Since the exact extent of text depends on the font, the
+/// extent of labels is estimated from their number of characters and rotated edge labels are
+/// treated as if they were horizontal.
+pub fn graph_to_svg_string_cropped<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
+    graph: G,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+    padding: f32,
+) -> String
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    internal_graph_to_svg(graph, settings, Output::Cropped { padding })
+        .expect("Only streamed or validated output can fail")
+        .header
+}
+
+/// Same as [`graph_to_svg`] but returns the individual SVG elements as [`SvgElements`] instead of
+/// saving the assembled SVG data to a file.
+///
+/// This allows inspecting or post-processing the elements of single nodes and edges, e.g. to inject
+/// custom markup between them, before assembling them using [`SvgElements::assemble`].
+///
+/// Like for [`graph_to_svg_string`], colors are not validated. Call [`validate_colors`] beforehand
+/// to do so.
+pub fn graph_to_svg_elements<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
+    graph: G,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+) -> SvgElements
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    internal_graph_to_svg(graph, settings, Output::Elements)
+        .expect("Only streamed or validated output can fail")
+}
+
+/// Lays out the graph according to the settings and produces the `output`, see [`Output`].
+///
+/// Returns the SVG elements, which are empty except for [`SvgElements::directed`] if the output is
+/// streamed and hold the assembled SVG data as their header if it is assembled or cropped. Errors
+/// only occur while writing streamed output or for invalid colors, if validated.
+fn internal_graph_to_svg<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+>(
+    graph: G,
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+    output: Output<'_>,
+) -> Result<SvgElements, VisGraphError>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: Hash + Eq + MaybeSync,
+    G::EdgeId: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
 {
-    match &settings.layout_or_pos_map {
-        LayoutOrPositionMap::Layout(Layout::Circular) => {
-            let position_map = circular_layout(&graph);
-            internal_graph_to_svg_with_positions_and_labels(graph, position_map, settings)
+    // Resolve the layout once, since it also affects how the graph is drawn
+    let layout = match &settings.layout_or_pos_map {
+        LayoutOrPositionMap::Layout(layout) => resolve_layout(graph, layout),
+        LayoutOrPositionMap::PositionMap(position_map) => {
+            return internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                None,
+                output,
+            );
+        }
+    };
+    let layout = &*layout;
+
+    // Reuse the cached positions of the layout, if any, instead of computing it
+    let layout_cache = match (&settings.layout_or_pos_map, &settings.layout_cache) {
+        (LayoutOrPositionMap::Layout(_), Some((cache, key))) => Some((&**cache, key.as_str())),
+        _ => None,
+    };
+    if let Some(positions) = layout_cache.and_then(|(cache, key)| cache.get(key)) {
+        let position_map = |node_id| {
+            positions
+                .get(NodeIndexable::to_index(&graph, node_id))
+                .copied()
+                .unwrap_or((0.5, 0.5))
+        };
+        return internal_graph_to_svg_with_positions_and_labels(
+            graph,
+            position_map,
+            settings,
+            Some(layout),
+            output,
+        );
+    }
+
+    match layout {
+        Layout::Circular => {
+            let position_map = circular_layout_with_params(&graph, settings.circular_params);
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
         }
-        LayoutOrPositionMap::Layout(Layout::Hierarchical(orientation)) => {
-            let position_map = hierarchical_layout(&graph, *orientation);
-            internal_graph_to_svg_with_positions_and_labels(graph, position_map, settings)
+        Layout::Hierarchical(orientation) => {
+            let position_map =
+                hierarchical_layout_with_params(&graph, *orientation, settings.hierarchical_params);
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
         }
-        LayoutOrPositionMap::Layout(Layout::ForceDirected) => {
+        Layout::ForceDirected => {
+            let params = ForceDirectedParams {
+                pinned_positions: settings.pinned_positions.clone(),
+                edge_weight_mode: settings.edge_weight_mode,
+                seed: settings.seed,
+                time_budget: settings.layout_time_budget,
+                ..ForceDirectedParams::default()
+            };
             let position_map =
-                force_directed_layout(&graph, DEFAULT_ITERATIONS, DEFAULT_INITIAL_TEMPERATURE);
-            internal_graph_to_svg_with_positions_and_labels(graph, position_map, settings)
+                force_directed_layout_with_params(&graph, &params, &settings.edge_weight_fn);
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
         }
-        LayoutOrPositionMap::Layout(Layout::Bipartite(left_partition)) => {
+        Layout::Bipartite(left_partition) => {
             let position_map = bipartite_layout(&graph, left_partition.as_ref());
-            internal_graph_to_svg_with_positions_and_labels(graph, position_map, settings)
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
         }
-        LayoutOrPositionMap::Layout(Layout::Random) => {
+        Layout::Random => {
             let position_map = random_layout(&graph);
-            internal_graph_to_svg_with_positions_and_labels(graph, position_map, settings)
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
         }
-        LayoutOrPositionMap::PositionMap(position_map) => {
-            internal_graph_to_svg_with_positions_and_labels(graph, position_map, settings)
+        Layout::Spiral => {
+            let position_map = spiral_layout_with_params(&graph, settings.spiral_params);
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
+        }
+        Layout::Clustered(communities) => {
+            let position_map = clustered_layout(&graph, communities);
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
         }
+        Layout::HexGrid => {
+            let position_map = hex_grid_layout(&graph);
+            cache_positions(graph, &position_map, layout_cache);
+            internal_graph_to_svg_with_positions_and_labels(
+                graph,
+                position_map,
+                settings,
+                Some(layout),
+                output,
+            )
+        }
+        Layout::Auto => unreachable!("Layout::Auto is resolved to another layout"),
     }
 }
 
-fn internal_graph_to_svg_with_positions_and_labels<
+/// Caches the positions of all nodes of the graph given by the `position_map` under the key in the
+/// layout cache, if any. The positions are indexed by the node index.
+fn cache_positions<G>(
+    graph: G,
+    position_map: impl Fn(G::NodeId) -> (f32, f32),
+    layout_cache: Option<(&LayoutCache, &str)>,
+) where
+    G: IntoNodeReferences + NodeIndexable,
+{
+    let Some((cache, key)) = layout_cache else {
+        return;
+    };
+    let mut positions = vec![(0.0, 0.0); graph.node_bound()];
+    for node in graph.node_references() {
+        positions[NodeIndexable::to_index(&graph, node.id())] = position_map(node.id());
+    }
+    cache.insert(key, positions);
+}
+
+/// Checks that the node and edge coloring functions of the settings return recognized SVG colors
+/// for all nodes and edges of the graph.
+///
+/// Recognized are the SVG color keywords (case-insensitive), hex colors with 3, 4, 6 or 8 digits,
+/// the functional notations `rgb()`, `rgba()`, `hsl()` and `hsla()` and references like
+/// `url(#my-pattern)` to [`extra_defs`](crate::settings::SettingsBuilder::extra_defs). For gradient
+/// fills, both colors are checked. Returns a [`VisGraphError::InvalidColor`] for the first invalid
+/// color.
+///
+/// If [`validate_colors`](crate::settings::SettingsBuilder::validate_colors) is enabled,
+/// [`graph_to_svg`], [`try_graph_to_svg_string`] and [`graph_to_svg_stream`] validate the colors
+/// while drawing. Call this before the functions which cannot fail, such as
+/// [`graph_to_svg_elements`], to validate the colors there.
+pub fn validate_colors<
     G,
     PositionMapFn,
     NodeLabelFn,
     EdgeLabelFn,
     NodeColoringFn,
     EdgeColoringFn,
-    S,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
 >(
     graph: G,
-    position_map: PositionMapFn,
-    settings: &Settings<S, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>,
-) -> String
+    settings: &Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+) -> Result<(), VisGraphError>
 where
     G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + EdgeIndexable,
-    PositionMapFn: Fn(G::NodeId) -> (f32, f32),
-    NodeLabelFn: Fn(G::NodeId) -> String,
-    EdgeLabelFn: Fn(G::EdgeId) -> String,
-    NodeColoringFn: Fn(G::NodeId) -> String,
-    EdgeColoringFn: Fn(G::EdgeId) -> String,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor,
+    EdgeColor: Into<Color>,
 {
-    let mut svg_buffer = String::with_capacity(
-        graph.node_bound() * ESTIMATED_SVG_NODE_ENTRY_SIZE
-            + graph.edge_bound() * ESTIMATED_SVG_EDGE_ENTRY_SIZE,
-    );
-    let mut width_buffer = ryu::Buffer::new();
-    let width_str = width_buffer.format(settings.width);
+    for node in graph.node_references() {
+        let node_or_edge =
+            GraphElement::Node(NodeIndex::new(NodeIndexable::to_index(&graph, node.id())));
+        match (settings.node_coloring_fn)(node.id()).into() {
+            NodeFill::Solid(color) => check_color(node_or_edge, &color)?,
+            NodeFill::RadialGradient { inner, outer } => {
+                check_color(node_or_edge, &inner)?;
+                check_color(node_or_edge, &outer)?;
+            }
+        }
+    }
+    for edge in graph.edge_references() {
+        let node_or_edge =
+            GraphElement::Edge(EdgeIndex::new(EdgeIndexable::to_index(&graph, edge.id())));
+        check_color(
+            node_or_edge,
+            &(settings.edge_coloring_fn)(edge.id()).into().to_svg(),
+        )?;
+    }
 
-    let mut height_buffer = ryu::Buffer::new();
-    let height_str = height_buffer.format(settings.height);
+    Ok(())
+}
 
-    svg_buffer.push_str(&format!(
-        "<svg width=\"{width_str}\" height=\"{height_str}\" xmlns=\"http://www.w3.org/2000/svg\">\n",
-    ));
+/// Returns a [`VisGraphError::InvalidColor`] for the node or edge if `value` is not a recognized SVG
+/// color.
+fn check_color(node_or_edge: GraphElement, value: &str) -> Result<(), VisGraphError> {
+    if is_valid_svg_color(value) {
+        Ok(())
+    } else {
+        Err(VisGraphError::InvalidColor {
+            node_or_edge,
+            value: value.to_owned(),
+        })
+    }
+}
 
-    let node_label_map = &settings.node_label_fn;
-    let edge_label_map = &settings.edge_label_fn;
-    let node_coloring_map = &settings.node_coloring_fn;
-    let edge_coloring_map = &settings.edge_coloring_fn;
+fn internal_graph_to_svg_with_positions_and_labels<
+    G,
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+    NodeColor,
+    EdgeColor,
+    S,
+>(
+    graph: G,
+    position_map: PositionMapFn,
+    settings: &Settings<
+        S,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >,
+    layout: Option<&Layout>,
+    output: Output<'_>,
+) -> Result<SvgElements, VisGraphError>
+where
+    G: IntoNodeReferences
+        + IntoEdgeReferences
+        + NodeIndexable
+        + EdgeIndexable
+        + IntoNeighborsDirected
+        + GraphProp
+        + MaybeSync,
+    G::NodeId: MaybeSync,
+    G::EdgeId: MaybeSync,
+    S: MaybeSync,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32) + MaybeSync,
+    NodeLabelFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeLabelFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeColoringFn: Fn(G::NodeId) -> NodeColor + MaybeSync,
+    NodeColor: Into<NodeFill>,
+    EdgeColoringFn: Fn(G::EdgeId) -> EdgeColor + MaybeSync,
+    EdgeColor: Into<Color>,
+    EdgeWeightFn: MaybeSync,
+    NodeOpacityFn: Fn(G::NodeId) -> f32 + MaybeSync,
+    NodeClassFn: Fn(G::NodeId) -> String + MaybeSync,
+    EdgeClassFn: Fn(G::EdgeId) -> String + MaybeSync,
+    NodeImageFn: Fn(G::NodeId) -> Option<String> + MaybeSync,
+    EdgeCurvatureFn: Fn(G::EdgeId) -> f32 + MaybeSync,
+    NodeZFn: Fn(G::NodeId) -> i32 + MaybeSync,
+{
+    // The opening <svg> tag and the background depend on the extent of the canvas, which is only
+    // known after all elements have been placed if it is cropped. Hence, they are written once the
+    // remaining header is complete.
+    let mut svg_buffer = String::new();
 
-    for node in graph.node_references() {
-        let id = node.id();
+    let bounding_box = match settings.normalize_positions {
+        NormalizeMode::Fit => Some(bounding_box(&graph, &position_map)),
+        NormalizeMode::None | NormalizeMode::Clamp => None,
+    };
+    let position_map = |node_id| {
+        let position = normalize_position(
+            position_map(node_id),
+            settings.normalize_positions,
+            bounding_box,
+        );
+        match settings.snap_to_grid {
+            Some(divisions) => snap_to_grid(position, divisions),
+            None => position,
+        }
+    };
+
+    // Reserve space for the title at the top and draw the graph below it
+    let title_height = if let Some(title) = &settings.title {
+        draw_title(&mut svg_buffer, title, settings.width, settings.font_size)
+    } else {
+        0.0
+    };
+    let scale_position = |position| {
         let (scaled_x, scaled_y) = scale(
-            position_map(id),
+            position,
             settings.margin_x,
             settings.margin_y,
             settings.width,
-            settings.height,
+            settings.height - title_height,
+            settings.y_axis,
         );
-        let node_label = node_label_map(id);
-        let node_color = node_coloring_map(id);
+        (scaled_x, scaled_y + title_height)
+    };
+    if settings.draw_debug_grid {
+        draw_debug_grid(&mut svg_buffer, scale_position, settings.font_size);
+    }
+
+    let drawing_area = (
+        settings.width * (1.0 - 2.0 * settings.margin_x),
+        (settings.height - title_height) * (1.0 - 2.0 * settings.margin_y),
+    );
+    // Radial layouts and the hexagonal grid are corrected for the aspect ratio of the drawing area,
+    // such that circles stay circular and grid cells regular on non-square canvases
+    let preserve_aspect_ratio = matches!(
+        layout,
+        Some(Layout::Circular | Layout::Spiral | Layout::HexGrid)
+    );
+
+    // Compute the scaled position of each node once, since nodes are usually incident to several
+    // edges. The normalized positions are collected first, such that overlaps can be resolved
+    // before scaling. Also resolve the fill of each node, such that each distinct gradient is
+    // defined once. The colors are validated along the way, if requested.
+    let validate_colors = settings.validate_colors
+        && match output {
+            Output::Stream(_) => true,
+            Output::Assembled { validated } => validated,
+            Output::Elements | Output::Cropped { .. } => false,
+        };
+    let mut scaled_positions = vec![(0.0, 0.0); graph.node_bound()];
+    let mut node_fills = vec![String::new(); graph.node_bound()];
+    let mut gradient_ids = HashMap::new();
+    for node in graph.node_references() {
+        let id = node.id();
+        let index = NodeIndexable::to_index(&graph, id);
+        scaled_positions[index] = if preserve_aspect_ratio {
+            correct_aspect_ratio(position_map(id), drawing_area)
+        } else {
+            position_map(id)
+        };
+        let node_fill = (settings.node_coloring_fn)(id).into();
+        if validate_colors {
+            let node_or_edge = GraphElement::Node(NodeIndex::new(index));
+            match &node_fill {
+                NodeFill::Solid(color) => check_color(node_or_edge, color)?,
+                NodeFill::RadialGradient { inner, outer } => {
+                    check_color(node_or_edge, inner)?;
+                    check_color(node_or_edge, outer)?;
+                }
+            }
+        }
+        node_fills[index] = match node_fill {
+            NodeFill::Solid(color) => color,
+            NodeFill::RadialGradient { inner, outer } => {
+                let next_id = gradient_ids.len();
+                let gradient_id = *gradient_ids.entry((inner, outer)).or_insert(next_id);
+                format!("url(#node-gradient-{gradient_id})")
+            }
+        };
+    }
+    if !gradient_ids.is_empty() {
+        draw_gradient_defs(&mut svg_buffer, gradient_ids);
+    }
+
+    if settings.avoid_overlaps {
+        let node_indices: Vec<_> = graph
+            .node_references()
+            .map(|node| NodeIndexable::to_index(&graph, node.id()))
+            .collect();
+        let mut positions: Vec<_> = node_indices
+            .iter()
+            .map(|&index| scaled_positions[index])
+            .collect();
+        resolve_overlaps(&mut positions, settings.radius, drawing_area);
+        for (index, position) in node_indices.into_iter().zip(positions) {
+            scaled_positions[index] = position;
+        }
+    }
+    for position in &mut scaled_positions {
+        *position = scale_position(*position);
+    }
+
+    let radius = if settings.auto_node_radius {
+        let positions: Vec<_> = graph
+            .node_references()
+            .map(|node| scaled_positions[NodeIndexable::to_index(&graph, node.id())])
+            .collect();
+        min_distance(positions).map_or(settings.radius, |min_distance| {
+            (AUTO_NODE_RADIUS_FACTOR * min_distance).min(settings.radius)
+        })
+    } else {
+        settings.radius
+    };
+    if settings.node_shadow {
+        draw_shadow_defs(&mut svg_buffer, radius);
+    }
+
+    let node_label_map =
+        |id: G::NodeId| truncate_label((settings.node_label_fn)(id), settings.label_max_chars);
+    let edge_label_map =
+        |id: G::EdgeId| truncate_label((settings.edge_label_fn)(id), settings.label_max_chars);
+    let edge_coloring_map = &settings.edge_coloring_fn;
+    let node_opacity_map = &settings.node_opacity_fn;
+    let node_class_map = &settings.node_class_fn;
+    let edge_class_map = &settings.edge_class_fn;
+    let node_image_map = &settings.node_image_fn;
+    // The outlines of the nodes are drawn with the default stroke width of 1 pixel
+    let node_dash_array = settings.node_stroke_style.dash_array(1.0);
+
+    let write_node = |svg_buffer: &mut String, id: G::NodeId| {
+        let (scaled_x, scaled_y) = scaled_positions[NodeIndexable::to_index(&graph, id)];
+        let node_label = settings.draw_node_labels.then(|| node_label_map(id));
+        let index = NodeIndexable::to_index(&graph, id);
+        let node_color = if settings.highlighted_nodes.contains(&NodeIndex::new(index)) {
+            &settings.highlight_color
+        } else {
+            &node_fills[index]
+        };
+        let node_opacity = node_opacity_map(id);
+        let node_class = settings
+            .emit_css_classes
+            .then(|| css_class("node", &node_class_map(id)));
+        let node_image = node_image_map(id);
         draw_node(
-            &mut svg_buffer,
+            svg_buffer,
+            settings.emit_element_ids.then_some(index),
+            node_class.as_deref(),
             scaled_x,
             scaled_y,
-            &node_label,
-            &node_color,
-            settings.radius,
+            node_label.as_deref(),
+            node_color,
+            node_opacity,
+            radius,
             settings.font_size,
+            settings.rich_labels,
+            node_dash_array.as_deref(),
+            settings.node_shadow,
+            node_image.as_deref(),
+            settings.show_index_badge.then_some(index),
         );
+    };
+
+    let hierarchy_orientation = match layout {
+        Some(Layout::Hierarchical(orientation)) => Some(*orientation),
+        _ => None,
+    };
+    let edge_shape = match (settings.edge_routing, hierarchy_orientation) {
+        (EdgeRouting::Orthogonal, orientation) => EdgeShape::Orthogonal(orientation),
+        (EdgeRouting::Straight, Some(orientation)) if settings.smooth_tree_edges => {
+            EdgeShape::TreeCurve(orientation)
+        }
+        (EdgeRouting::Straight, _) => EdgeShape::Line,
+    };
+    // Directed edges whose reverse edge is contained in the graph, given by their node indices
+    let reciprocal_edges: HashSet<(usize, usize)> = if settings.separate_reciprocal_edges
+        && graph.is_directed()
+        && matches!(edge_shape, EdgeShape::Line)
+    {
+        let edges: HashSet<_> = graph
+            .edge_references()
+            .map(|edge| {
+                (
+                    NodeIndexable::to_index(&graph, edge.source()),
+                    NodeIndexable::to_index(&graph, edge.target()),
+                )
+            })
+            .collect();
+        edges
+            .iter()
+            .filter(|&&(source, target)| source != target && edges.contains(&(target, source)))
+            .copied()
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    let edges: Vec<_> = if settings.bundle_parallel_edges {
+        bundle_parallel_edges(&graph)
+    } else {
+        graph
+            .edge_references()
+            .map(|edge| (edge.id(), edge.source(), edge.target(), 1))
+            .collect()
+    };
+    // Resolve the color of each drawn edge once, such that each distinct color gets its own
+    // arrowhead marker
+    let is_highlighted = |index: usize| settings.highlighted_edges.contains(&EdgeIndex::new(index));
+    let mut edge_colors = vec![Color::from(""); graph.edge_bound()];
+    for &(id, ..) in &edges {
+        let index = EdgeIndexable::to_index(&graph, id);
+        if !is_highlighted(index) {
+            edge_colors[index] = edge_coloring_map(id).into();
+            if validate_colors {
+                check_color(
+                    GraphElement::Edge(EdgeIndex::new(index)),
+                    &edge_colors[index].to_svg(),
+                )?;
+            }
+        }
     }
+    let edge_color_of = |index: usize| {
+        if is_highlighted(index) {
+            Cow::Borrowed(settings.highlight_color.as_str())
+        } else {
+            edge_colors[index].to_svg()
+        }
+    };
+    let mut arrowhead_ids = HashMap::new();
+    if settings.edge_arrow_mode != ArrowMode::None || !reciprocal_edges.is_empty() {
+        for &(id, ..) in &edges {
+            let next_id = arrowhead_ids.len();
+            arrowhead_ids
+                .entry(edge_color_of(EdgeIndexable::to_index(&graph, id)))
+                .or_insert(next_id);
+        }
+        draw_arrowhead_defs(&mut svg_buffer, settings.arrow_style, &arrowhead_ids);
+    }
+    let edge_curvature_map = &settings.edge_curvature_fn;
+    let edge_shape_of = |id: G::EdgeId, source: usize, target: usize| {
+        let curvature = edge_curvature_map(id);
+        if !matches!(edge_shape, EdgeShape::Line) {
+            edge_shape
+        } else if curvature != 0.0 && curvature.is_finite() {
+            EdgeShape::QuadraticCurve(curvature)
+        } else if reciprocal_edges.contains(&(source, target)) {
+            EdgeShape::ReciprocalLine(radius * RECIPROCAL_EDGE_GAP_FACTOR / 2.0)
+        } else {
+            edge_shape
+        }
+    };
 
-    for edge in graph.edge_references() {
-        let source = edge.source();
-        let target = edge.target();
-        let (scaled_x_source, scaled_y_source) = scale(
-            position_map(source),
-            settings.margin_x,
-            settings.margin_y,
+    let write_edge =
+        |svg_buffer: &mut String,
+         (id, source, target, count): (G::EdgeId, G::NodeId, G::NodeId, usize)| {
+            // Bundles of parallel edges are labeled with the number of edges
+            let edge_label = if count > 1 {
+                Some(count.to_string())
+            } else {
+                settings.draw_edge_labels.then(|| edge_label_map(id))
+            };
+            let index = EdgeIndexable::to_index(&graph, id);
+            let edge_color = edge_color_of(index);
+            let stroke_width = if is_highlighted(index) {
+                settings.highlight_stroke_width
+            } else {
+                settings.stroke_width
+            };
+            let stroke_width = stroke_width * (count as f32).sqrt();
+
+            let edge_class = settings
+                .emit_css_classes
+                .then(|| css_class("edge", &edge_class_map(id)));
+
+            let source = NodeIndexable::to_index(&graph, source);
+            let target = NodeIndexable::to_index(&graph, target);
+            let shape = edge_shape_of(id, source, target);
+            // Reciprocal edges always end in an arrowhead to tell their directions apart
+            let arrows = match (shape, settings.edge_arrow_mode) {
+                (EdgeShape::ReciprocalLine(_), ArrowMode::None) => ArrowMode::End,
+                (_, arrow_mode) => arrow_mode,
+            };
+            draw_edge(
+                svg_buffer,
+                settings.emit_element_ids.then_some(index),
+                edge_class.as_deref(),
+                scaled_positions[source],
+                scaled_positions[target],
+                edge_label.as_deref(),
+                settings.edge_label_position,
+                settings.rotate_edge_labels,
+                &edge_color,
+                radius,
+                settings.min_edge_length,
+                shape,
+                arrows,
+                arrowhead_ids.get(&edge_color).copied(),
+                stroke_width,
+                settings.font_size,
+            );
+        };
+
+    let mut nodes = draw_order(&graph, settings.node_draw_order.as_deref());
+    // The sort is stable, such that nodes of the same z-order keep their draw order. The z-order
+    // function is called once per node.
+    nodes.sort_by_cached_key(|&id| (settings.node_z_fn)(id));
+    let mut footer = String::new();
+    if !settings.legend.is_empty() {
+        draw_legend(
+            &mut footer,
+            &settings.legend,
+            settings.legend_corner,
             settings.width,
             settings.height,
+            title_height,
+            settings.font_size,
         );
-        let (scaled_x_target, scaled_y_target) = scale(
-            position_map(target),
-            settings.margin_x,
-            settings.margin_y,
-            settings.width,
-            settings.height,
+    }
+    footer.push_str("</svg>");
+
+    // Estimate the extent of all drawn elements to crop the canvas to it, if requested
+    let content_bounds = match output {
+        Output::Cropped { padding } => Some(padding),
+        _ => None,
+    }
+    .map(|padding| {
+        let mut bounds = (
+            (f32::INFINITY, f32::INFINITY),
+            (f32::NEG_INFINITY, f32::NEG_INFINITY),
         );
-        let edge_label = edge_label_map(edge.id());
-        let edge_color = edge_coloring_map(edge.id());
-
-        draw_edge(
-            &mut svg_buffer,
-            (scaled_x_source, scaled_y_source),
-            (scaled_x_target, scaled_y_target),
-            &edge_label,
-            &edge_color,
-            settings.radius,
-            settings.stroke_width,
-            settings.font_size,
+        let mut include = |(x, y): (f32, f32), (half_width, half_height): (f32, f32)| {
+            let ((min_x, min_y), (max_x, max_y)) = &mut bounds;
+            *min_x = min_x.min(x - half_width);
+            *min_y = min_y.min(y - half_height);
+            *max_x = max_x.max(x + half_width);
+            *max_y = max_y.max(y + half_height);
+        };
+        let label_extent = |label: &str, font_size: f32| {
+            let width = label.chars().count() as f32 * font_size * ESTIMATED_CHAR_WIDTH_FACTOR;
+            (width / 2.0, font_size / 2.0)
+        };
+
+        for &id in &nodes {
+            let position = scaled_positions[NodeIndexable::to_index(&graph, id)];
+            include(position, (radius, radius));
+            if settings.draw_node_labels {
+                include(
+                    position,
+                    label_extent(&node_label_map(id), settings.font_size),
+                );
+            }
+        }
+        for &(id, source, target, count) in &edges {
+            let edge_label = if count > 1 {
+                count.to_string()
+            } else if settings.draw_edge_labels {
+                edge_label_map(id)
+            } else {
+                continue;
+            };
+            let source = NodeIndexable::to_index(&graph, source);
+            let target = NodeIndexable::to_index(&graph, target);
+            let anchor = edge_label_anchor(
+                scaled_positions[source],
+                scaled_positions[target],
+                settings.edge_label_position,
+                radius,
+                settings.min_edge_length,
+                edge_shape_of(id, source, target),
+            );
+            if let Some(anchor) = anchor.filter(|_| !edge_label.is_empty()) {
+                include(anchor, label_extent(&edge_label, settings.font_size));
+            }
+        }
+        if let Some(title) = &settings.title {
+            include(
+                (settings.width / 2.0, title_height / 2.0),
+                label_extent(title, settings.font_size * TITLE_FONT_SIZE_FACTOR),
+            );
+        }
+        if !settings.legend.is_empty() {
+            let ((box_x, box_y), (box_width, box_height)) = legend_box(
+                &settings.legend,
+                settings.legend_corner,
+                settings.width,
+                settings.height,
+                title_height,
+                settings.font_size,
+            );
+            include(
+                (box_x + box_width / 2.0, box_y + box_height / 2.0),
+                (box_width / 2.0, box_height / 2.0),
+            );
+        }
+
+        let ((min_x, min_y), (max_x, max_y)) = bounds;
+        (
+            (min_x - padding, min_y - padding),
+            (max_x + padding, max_y + padding),
+        )
+    })
+    .filter(|((min_x, _), (max_x, _))| min_x <= max_x);
+
+    let (origin, width, height) = match content_bounds {
+        Some(((min_x, min_y), (max_x, max_y))) => {
+            (Some((min_x, min_y)), max_x - min_x, max_y - min_y)
+        }
+        None => (None, settings.width, settings.height),
+    };
+    let mut header = String::new();
+    if settings.xml_declaration {
+        header.push_str(XML_DECLARATION);
+    }
+    write_svg_open_tag(
+        &mut header,
+        width,
+        height,
+        origin,
+        settings.responsive,
+        settings.units,
+    );
+    if settings.embed_metadata {
+        write_metadata_comment(
+            &mut header,
+            layout,
+            graph.node_references().count(),
+            graph.edge_references().count(),
+        );
+    }
+    if !settings.extra_defs.is_empty() {
+        writeln!(header, "    <defs>{}</defs>", settings.extra_defs)
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    if let Some(background_color) = &settings.background_color {
+        draw_background(&mut header, background_color, origin, width, height);
+    }
+    header.push_str(&svg_buffer);
+    svg_buffer = header;
+
+    // Write the fragments one at a time, such that the SVG data is never held in memory as a whole
+    if let Output::Stream(writer) = output {
+        writer.write_all(svg_buffer.as_bytes())?;
+        let mut fragment = String::with_capacity(ESTIMATED_SVG_EDGE_ENTRY_SIZE);
+        for (i, &edge) in edges.iter().enumerate() {
+            fragment.clear();
+            write_edge(&mut fragment, edge);
+            writer.write_all(fragment.as_bytes())?;
+            if (i + 1) % STREAM_FLUSH_INTERVAL == 0 {
+                writer.flush()?;
+            }
+        }
+        for (i, &id) in nodes.iter().enumerate() {
+            fragment.clear();
+            write_node(&mut fragment, id);
+            writer.write_all(fragment.as_bytes())?;
+            if (i + 1) % STREAM_FLUSH_INTERVAL == 0 {
+                writer.flush()?;
+            }
+        }
+        writer.write_all(footer.as_bytes())?;
+        writer.flush()?;
+
+        let elements = SvgElements {
+            directed: graph.is_directed(),
+            ..SvgElements::default()
+        };
+        return Ok(elements);
+    }
+
+    // Without parallelism, the fragments are written straight into the assembled SVG data
+    #[cfg(not(feature = "rayon"))]
+    if let Output::Assembled { .. } | Output::Cropped { .. } = output {
+        svg_buffer.reserve(
+            edges.len() * ESTIMATED_SVG_EDGE_ENTRY_SIZE
+                + nodes.len() * ESTIMATED_SVG_NODE_ENTRY_SIZE
+                + footer.len(),
         );
+        for &edge in &edges {
+            write_edge(&mut svg_buffer, edge);
+        }
+        for &id in &nodes {
+            write_node(&mut svg_buffer, id);
+        }
+        svg_buffer.push_str(&footer);
+
+        let elements = SvgElements {
+            header: svg_buffer,
+            directed: graph.is_directed(),
+            ..SvgElements::default()
+        };
+        return Ok(elements);
     }
 
-    svg_buffer.push_str("</svg>");
-    svg_buffer
+    let node_fragment = |&id: &G::NodeId| {
+        let mut fragment = String::with_capacity(ESTIMATED_SVG_NODE_ENTRY_SIZE);
+        write_node(&mut fragment, id);
+        fragment
+    };
+    let edge_fragment = |&edge: &(G::EdgeId, G::NodeId, G::NodeId, usize)| {
+        let mut fragment = String::with_capacity(ESTIMATED_SVG_EDGE_ENTRY_SIZE);
+        write_edge(&mut fragment, edge);
+        fragment
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    let (node_fragments, edge_fragments) = (
+        nodes.iter().map(node_fragment).collect(),
+        edges.iter().map(edge_fragment).collect(),
+    );
+
+    // Build the fragments of the individual nodes and edges in parallel, the order of the
+    // fragments is the same as in the serial case.
+    #[cfg(feature = "rayon")]
+    let (node_fragments, edge_fragments) = {
+        use rayon::prelude::*;
+
+        (
+            nodes.par_iter().map(node_fragment).collect(),
+            edges.par_iter().map(edge_fragment).collect(),
+        )
+    };
+
+    let elements = SvgElements {
+        header: svg_buffer,
+        edges: edge_fragments,
+        nodes: node_fragments,
+        footer,
+        directed: graph.is_directed(),
+    };
+    if let Output::Assembled { .. } | Output::Cropped { .. } = output {
+        let elements = SvgElements {
+            header: elements.assemble(),
+            directed: elements.directed,
+            ..SvgElements::default()
+        };
+        return Ok(elements);
+    }
+    Ok(elements)
 }
 
-/// Draws a node as a circle with a text label by writing appropriate <circle> and <text> tags to
-/// the provided `svg_buffer`.
-#[allow(clippy::too_many_arguments)]
-fn draw_node(
+/// Writes an XML comment with the name and version of this crate, the name of the resolved
+/// `layout` or `PositionMap` if there is none, the number of nodes and edges and the current time
+/// in seconds since the Unix epoch.
+fn write_metadata_comment(
     svg_buffer: &mut String,
-    coord_x: f32,
+    layout: Option<&Layout>,
+    node_count: usize,
+    edge_count: usize,
+) {
+    let layout = match layout {
+        Some(Layout::Circular) => "Circular",
+        Some(Layout::Hierarchical(_)) => "Hierarchical",
+        Some(Layout::ForceDirected) => "ForceDirected",
+        Some(Layout::Bipartite(_)) => "Bipartite",
+        Some(Layout::Random) => "Random",
+        Some(Layout::Spiral) => "Spiral",
+        Some(Layout::Clustered(_)) => "Clustered",
+        Some(Layout::HexGrid) => "HexGrid",
+        Some(Layout::Auto) => "Auto",
+        None => "PositionMap",
+    };
+    // A system clock set before the Unix epoch is reported as the epoch itself
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    writeln!(
+        svg_buffer,
+        "<!-- Generated by {} {} | layout: {layout} | nodes: {node_count} | edges: {edge_count} | timestamp: {timestamp} -->",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+    )
+    .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+}
+
+/// Writes the opening <svg> tag for a canvas of the given `width` and `height` to the provided
+/// `svg_buffer`.
+///
+/// If `origin` is `Some`, the `viewBox` starts at the given coordinates instead of (0, 0), which
+/// is used to crop the canvas. If `responsive` is `true`, the SVG spans `100%` of its container.
+/// Otherwise, the width and height carry the suffix of the `units`, which, other than pixels, also
+/// adds a `viewBox`, such that all coordinates are given in the units.
+fn write_svg_open_tag(
+    svg_buffer: &mut String,
+    width: f32,
+    height: f32,
+    origin: Option<(f32, f32)>,
+    responsive: bool,
+    units: Units,
+) {
+    let mut width_buffer = ryu::Buffer::new();
+    let width_str = width_buffer.format(width);
+    let mut height_buffer = ryu::Buffer::new();
+    let height_str = height_buffer.format(height);
+
+    let (size_width_str, size_height_str, suffix) = if responsive {
+        ("100%", "100%", "")
+    } else {
+        (width_str, height_str, units.suffix())
+    };
+    write!(
+        svg_buffer,
+        "<svg width=\"{size_width_str}{suffix}\" height=\"{size_height_str}{suffix}\" "
+    )
+    .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    if responsive || origin.is_some() || !suffix.is_empty() {
+        let (origin_x, origin_y) = origin.unwrap_or((0.0, 0.0));
+        let mut origin_x_buffer = ryu::Buffer::new();
+        let mut origin_y_buffer = ryu::Buffer::new();
+        let (origin_x_str, origin_y_str) = if origin.is_some() {
+            (
+                origin_x_buffer.format(origin_x),
+                origin_y_buffer.format(origin_y),
+            )
+        } else {
+            ("0", "0")
+        };
+        write!(
+            svg_buffer,
+            "viewBox=\"{origin_x_str} {origin_y_str} {width_str} {height_str}\" "
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    writeln!(svg_buffer, "xmlns=\"http://www.w3.org/2000/svg\">")
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+}
+
+/// Draws the background of a canvas of the given `width` and `height` by writing a <rect> tag
+/// filled with `color` to the provided `svg_buffer`. If `origin` is `Some`, the canvas starts at
+/// this point instead of (0, 0), e.g. for a cropped `viewBox`.
+fn draw_background(
+    svg_buffer: &mut String,
+    color: &str,
+    origin: Option<(f32, f32)>,
+    width: f32,
+    height: f32,
+) {
+    let mut width_buffer = ryu::Buffer::new();
+    let width_str = width_buffer.format(width);
+    let mut height_buffer = ryu::Buffer::new();
+    let height_str = height_buffer.format(height);
+
+    svg_buffer.push_str("    <rect ");
+    if let Some((origin_x, origin_y)) = origin {
+        let mut origin_x_buffer = ryu::Buffer::new();
+        let origin_x_str = origin_x_buffer.format(origin_x);
+        let mut origin_y_buffer = ryu::Buffer::new();
+        let origin_y_str = origin_y_buffer.format(origin_y);
+        write!(svg_buffer, "x=\"{origin_x_str}\" y=\"{origin_y_str}\" ")
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    writeln!(
+        svg_buffer,
+        "width=\"{width_str}\" height=\"{height_str}\" fill=\"{color}\"/>"
+    )
+    .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+}
+
+/// Draws a node as a circle with a text label by writing appropriate <circle> and <text> tags to
+/// the provided `svg_buffer`. If `node_label` is `None`, the <text> tag is omitted. If `index` is
+/// `Some`, the <circle> tag is given the id `node-<index>`. Similarly, if `class` is `Some`, it is
+/// emitted as `class` attribute.
+///
+/// If `rich_label` is `true`, subscripts and superscripts in the label are converted to <tspan>
+/// elements, see [`rich_label_markup`].
+///
+/// If `badge` is `Some`, it is drawn as a small <text> tag with the class `index-badge` at the top
+/// right of the node, using [`INDEX_BADGE_FONT_SIZE_FACTOR`] times the `font_size`.
+///
+/// The `node_opacity` is clamped to [0.0, 1.0] and only emitted as `fill-opacity` attribute if it
+/// is below 1.0. If `dash_array` is `Some`, it is emitted as `stroke-dasharray` attribute of the
+/// outline. If `shadow` is `true`, the circle references the drop shadow filter defined by
+/// [`draw_shadow_defs`].
+///
+/// If `image` is `Some`, the node is drawn as an <image> tag with the image as `href` instead of the
+/// <circle> tag. The image is centered at the node and sized to the diameter of the node, while the
+/// `node_color` is ignored and the `node_opacity` is emitted as `opacity` attribute.
+#[allow(clippy::too_many_arguments)]
+fn draw_node(
+    svg_buffer: &mut String,
+    index: Option<usize>,
+    class: Option<&str>,
+    coord_x: f32,
     coord_y: f32,
-    node_label: &str,
+    node_label: Option<&str>,
     node_color: &str,
+    node_opacity: f32,
     radius: f32,
     font_size: f32,
+    rich_label: bool,
+    dash_array: Option<&str>,
+    shadow: bool,
+    image: Option<&str>,
+    badge: Option<usize>,
 ) {
     let mut x_buffer = ryu::Buffer::new();
     let coord_x_str = x_buffer.format(coord_x);
@@ -247,32 +1704,137 @@ fn draw_node(
     let coord_y_str = y_buffer.format(coord_y);
     let mut radius_buffer = ryu::Buffer::new();
     let radius_str = radius_buffer.format(radius);
+
+    svg_buffer.push_str(if image.is_some() {
+        "\n    <image "
+    } else {
+        "\n    <circle "
+    });
+    if let Some(index) = index {
+        write!(svg_buffer, "id=\"node-{index}\" ")
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    if let Some(class) = class {
+        write!(svg_buffer, "class=\"{class}\" ")
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    let node_opacity = node_opacity.clamp(0.0, 1.0);
+    let mut opacity_buffer = ryu::Buffer::new();
+    let opacity_str = opacity_buffer.format(node_opacity);
+    if let Some(image) = image {
+        let mut corner_x_buffer = ryu::Buffer::new();
+        let corner_x_str = corner_x_buffer.format(coord_x - radius);
+        let mut corner_y_buffer = ryu::Buffer::new();
+        let corner_y_str = corner_y_buffer.format(coord_y - radius);
+        let mut diameter_buffer = ryu::Buffer::new();
+        let diameter_str = diameter_buffer.format(2.0 * radius);
+        write!(
+            svg_buffer,
+            "x=\"{corner_x_str}\" y=\"{corner_y_str}\" width=\"{diameter_str}\" \
+             height=\"{diameter_str}\" href=\"{}\" ",
+            escape_attribute(image),
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        if node_opacity < 1.0 {
+            write!(svg_buffer, "opacity=\"{opacity_str}\" ")
+                .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+        svg_buffer.push_str("/>\n");
+    } else {
+        write!(
+            svg_buffer,
+            "cx=\"{coord_x_str}\" cy=\"{coord_y_str}\" r=\"{radius_str}\" fill=\"{node_color}\" "
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        if node_opacity < 1.0 {
+            write!(svg_buffer, "fill-opacity=\"{opacity_str}\" ")
+                .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+        if let Some(dash_array) = dash_array {
+            write!(svg_buffer, "stroke-dasharray=\"{dash_array}\" ")
+                .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+        if shadow {
+            svg_buffer.push_str("filter=\"url(#node-shadow)\" ");
+        }
+        svg_buffer.push_str("stroke=\"black\"/>\n");
+    }
+
+    if let Some(badge) = badge {
+        // Place the badge at the point of the circle at 45 degrees to the top right
+        let offset = radius * std::f32::consts::FRAC_1_SQRT_2;
+        let mut badge_x_buffer = ryu::Buffer::new();
+        let badge_x_str = badge_x_buffer.format(coord_x + offset);
+        let mut badge_y_buffer = ryu::Buffer::new();
+        let badge_y_str = badge_y_buffer.format(coord_y - offset);
+        let mut badge_font_size_buffer = ryu::Buffer::new();
+        let badge_font_size_str =
+            badge_font_size_buffer.format(INDEX_BADGE_FONT_SIZE_FACTOR * font_size);
+        writeln!(
+            svg_buffer,
+            "    <text class=\"index-badge\" x=\"{badge_x_str}\" y=\"{badge_y_str}\" \
+             font-size=\"{badge_font_size_str}px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"black\" text-anchor=\"start\">{badge}</text>",
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+
+    let Some(node_label) = node_label else {
+        return;
+    };
+    let node_label = if rich_label {
+        rich_label_markup(node_label)
+    } else {
+        Cow::Borrowed(node_label)
+    };
     let mut font_size_buffer = ryu::Buffer::new();
     let font_size_str = font_size_buffer.format(font_size);
 
-    write!(
+    writeln!(
         svg_buffer,
-        "
-    <circle cx=\"{coord_x_str}\" cy=\"{coord_y_str}\" r=\"{radius_str}\" fill=\"{node_color}\" \
-         stroke=\"black\"/>
-    <text x=\"{coord_x_str}\" y=\"{coord_y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, \
+        "    <text x=\"{coord_x_str}\" y=\"{coord_y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, \
          sans-serif\" fill=\"black\" text-anchor=\"middle\" \
-         dominant-baseline=\"central\">{node_label}</text>\n",
+         dominant-baseline=\"central\">{node_label}</text>",
     )
     .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
 }
 
 /// Draws an edge as a line between two nodes by writing an appropriate <line> tag to the provided
 /// `svg_buffer`. Adjusting for the radius of the nodes so that the line starts and ends at the
-/// edge of the nodes rather than their centers.
+/// edge of the nodes rather than their centers. The label is placed at the fraction
+/// `edge_label_position` along the line. If `edge_label` is `None`, the <text> tag for the label is
+/// omitted.
+///
+/// If `index` is `Some`, the <line> tag is given the id `edge-<index>`. Similarly, if `class` is
+/// `Some`, it is emitted as `class` attribute.
+///
+/// If `rotate_edge_label` is `true`, the label is rotated around its anchor to follow the direction
+/// of the line, flipped by 180 degrees where necessary such that it is never upside down.
+///
+/// Unless the `shape` is [`EdgeShape::Line`] or [`EdgeShape::ReciprocalLine`], the edge is drawn as
+/// a <path> tag instead, either containing a cubic Bézier curve, see [`tree_edge_curve`], a
+/// quadratic Bézier curve, see [`quadratic_edge_curve`], or the segments of an orthogonal route,
+/// see [`orthogonal_route`]. The label is then placed on the path.
+///
+/// Depending on `arrows`, the edge starts and/or ends in the arrowhead marker with the id
+/// `arrowhead-<arrowhead_id>`, see [`draw_arrowhead_defs`]. No arrowheads are drawn if
+/// `arrowhead_id` is `None`.
 #[allow(clippy::too_many_arguments)]
 fn draw_edge(
     svg_buffer: &mut String,
+    index: Option<usize>,
+    class: Option<&str>,
     coord_source: (f32, f32),
     coord_target: (f32, f32),
-    edge_label: &str,
+    edge_label: Option<&str>,
+    edge_label_position: f32,
+    rotate_edge_label: bool,
     edge_color: &str,
     radius: f32,
+    min_edge_length: f32,
+    shape: EdgeShape,
+    arrows: ArrowMode,
+    arrowhead_id: Option<usize>,
     stroke_width: f32,
     font_size: f32,
 ) {
@@ -287,7 +1849,7 @@ fn draw_edge(
     let distance = (dir_vec_x * dir_vec_x + dir_vec_y * dir_vec_y).sqrt();
 
     // For very close nodes, we skip drawing the edge to avoid division by zero.
-    if distance < EDGE_CLOSENESS_THRESHOLD {
+    if distance < min_edge_length {
         return;
     }
 
@@ -295,128 +1857,2861 @@ fn draw_edge(
     let unit_dir_vec_x = dir_vec_x / distance;
     let unit_dir_vec_y = dir_vec_y / distance;
 
-    // Calculate the start and end point point (on the boundary of the circles)
-    let start_x = coord_x_source + radius * unit_dir_vec_x;
-    let start_y = coord_y_source + radius * unit_dir_vec_y;
-    let end_x = coord_x_target - radius * unit_dir_vec_x;
-    let end_y = coord_y_target - radius * unit_dir_vec_y;
+    // Tree edges and orthogonal edges leave and enter the nodes along the same axis and bend
+    // halfway between the nodes. Otherwise, edges are straight lines between the boundaries of the
+    // circles.
+    let path = match shape {
+        EdgeShape::Line | EdgeShape::ReciprocalLine(_) => None,
+        EdgeShape::TreeCurve(orientation) => Some(tree_edge_curve(
+            coord_source,
+            coord_target,
+            radius,
+            orientation,
+        )),
+        EdgeShape::Orthogonal(orientation) => Some(orthogonal_route(
+            coord_source,
+            coord_target,
+            radius,
+            orientation,
+        )),
+        EdgeShape::QuadraticCurve(curvature) => Some(quadratic_edge_curve(
+            coord_source,
+            coord_target,
+            radius,
+            curvature,
+        )),
+    };
+    let ((start_x, start_y), (end_x, end_y)) = match (path, shape) {
+        (Some([start, _, _, end]), _) => (start, end),
+        (None, EdgeShape::ReciprocalLine(offset)) => {
+            reciprocal_line_ends(coord_source, coord_target, radius, offset)
+        }
+        (None, _) => (
+            (
+                coord_x_source + radius * unit_dir_vec_x,
+                coord_y_source + radius * unit_dir_vec_y,
+            ),
+            (
+                coord_x_target - radius * unit_dir_vec_x,
+                coord_y_target - radius * unit_dir_vec_y,
+            ),
+        ),
+    };
+    // Ends with an arrowhead stop at its base, moving towards the adjacent point of the edge
+    let (draw_start_arrow, draw_end_arrow) = match arrows {
+        _ if arrowhead_id.is_none() => (false, false),
+        ArrowMode::None => (false, false),
+        ArrowMode::End => (false, true),
+        ArrowMode::Start => (true, false),
+        ArrowMode::Both => (true, true),
+    };
+    let line_length = ((end_x - start_x).powi(2) + (end_y - start_y).powi(2)).sqrt();
+    let arrowhead_length = (ARROWHEAD_SIZE_FACTOR * stroke_width).min(line_length / 2.0);
+    let (after_start, before_end) = match path {
+        Some([_, control_1, control_2, _]) => (control_1, control_2),
+        None => ((end_x, end_y), (start_x, start_y)),
+    };
+    let (line_start_x, line_start_y) = if draw_start_arrow {
+        move_towards((start_x, start_y), after_start, arrowhead_length)
+    } else {
+        (start_x, start_y)
+    };
+    let (line_end_x, line_end_y) = if draw_end_arrow {
+        move_towards((end_x, end_y), before_end, arrowhead_length)
+    } else {
+        (end_x, end_y)
+    };
 
     let mut start_x_buffer = ryu::Buffer::new();
-    let start_x_str = start_x_buffer.format(start_x);
+    let start_x_str = start_x_buffer.format(line_start_x);
     let mut start_y_buffer = ryu::Buffer::new();
-    let start_y_str = start_y_buffer.format(start_y);
+    let start_y_str = start_y_buffer.format(line_start_y);
     let mut end_x_buffer = ryu::Buffer::new();
-    let end_x_str = end_x_buffer.format(end_x);
+    let end_x_str = end_x_buffer.format(line_end_x);
     let mut end_y_buffer = ryu::Buffer::new();
-    let end_y_str = end_y_buffer.format(end_y);
+    let end_y_str = end_y_buffer.format(line_end_y);
+
+    let mut stroke_width_buffer = ryu::Buffer::new();
+    let stroke_width_str = stroke_width_buffer.format(stroke_width);
+
+    svg_buffer.push_str(if path.is_some() {
+        "\n    <path "
+    } else {
+        "\n    <line "
+    });
+    if let Some(index) = index {
+        write!(svg_buffer, "id=\"edge-{index}\" ")
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    if let Some(class) = class {
+        write!(svg_buffer, "class=\"{class}\" ")
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    if let Some([_, (control_1_x, control_1_y), (control_2_x, control_2_y), _]) = path {
+        let mut control_1_x_buffer = ryu::Buffer::new();
+        let control_1_x_str = control_1_x_buffer.format(control_1_x);
+        let mut control_1_y_buffer = ryu::Buffer::new();
+        let control_1_y_str = control_1_y_buffer.format(control_1_y);
+        let mut control_2_x_buffer = ryu::Buffer::new();
+        let control_2_x_str = control_2_x_buffer.format(control_2_x);
+        let mut control_2_y_buffer = ryu::Buffer::new();
+        let control_2_y_str = control_2_y_buffer.format(control_2_y);
+        // The inner points are the control points of the curve or the corners of the route
+        let d = if matches!(shape, EdgeShape::Orthogonal(_)) {
+            format!(
+                "M {start_x_str} {start_y_str} L {control_1_x_str} {control_1_y_str} L \
+                 {control_2_x_str} {control_2_y_str} L {end_x_str} {end_y_str}"
+            )
+        } else if let EdgeShape::QuadraticCurve(curvature) = shape {
+            let (control_x, control_y) =
+                quadratic_control_point(coord_source, coord_target, curvature);
+            let mut control_x_buffer = ryu::Buffer::new();
+            let control_x_str = control_x_buffer.format(control_x);
+            let mut control_y_buffer = ryu::Buffer::new();
+            let control_y_str = control_y_buffer.format(control_y);
+            format!(
+                "M {start_x_str} {start_y_str} Q {control_x_str} {control_y_str}, {end_x_str} \
+                 {end_y_str}"
+            )
+        } else {
+            format!(
+                "M {start_x_str} {start_y_str} C {control_1_x_str} {control_1_y_str}, \
+                 {control_2_x_str} {control_2_y_str}, {end_x_str} {end_y_str}"
+            )
+        };
+        write!(
+            svg_buffer,
+            "d=\"{d}\" fill=\"none\" stroke=\"{edge_color}\" stroke-width=\"{stroke_width_str}\"",
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    } else {
+        write!(
+            svg_buffer,
+            "x1=\"{start_x_str}\" y1=\"{start_y_str}\" x2=\"{end_x_str}\" y2=\"{end_y_str}\" stroke=\"{edge_color}\" \
+             stroke-width=\"{stroke_width_str}\"",
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    if let Some(arrowhead_id) = arrowhead_id.filter(|_| arrowhead_length > 0.0) {
+        if draw_start_arrow {
+            write!(svg_buffer, " marker-start=\"url(#arrowhead-{arrowhead_id})\"")
+                .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+        if draw_end_arrow {
+            write!(svg_buffer, " marker-end=\"url(#arrowhead-{arrowhead_id})\"")
+                .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+    }
+    svg_buffer.push_str("/>\n");
 
+    let Some(edge_label) = edge_label else {
+        return;
+    };
+    // Position of the label and direction of the edge at the label
+    let ((label_x, label_y), (label_dir_x, label_dir_y)) = match path {
+        Some(route) if matches!(shape, EdgeShape::Orthogonal(_)) => {
+            polyline_point_and_direction(route, edge_label_position)
+        }
+        Some(curve) => bezier_point_and_tangent(curve, edge_label_position),
+        None => (
+            interpolate((start_x, start_y), (end_x, end_y), edge_label_position),
+            (unit_dir_vec_x, unit_dir_vec_y),
+        ),
+    };
     let mut x_buffer = ryu::Buffer::new();
-    let x_str = x_buffer.format((start_x + end_x) / 2.0);
+    let x_str = x_buffer.format(label_x);
     let mut y_buffer = ryu::Buffer::new();
-    let y_str = y_buffer.format((start_y + end_y) / 2.0);
-
+    let y_str = y_buffer.format(label_y);
     let mut font_size_buffer = ryu::Buffer::new();
     let font_size_str = font_size_buffer.format(font_size);
-    let mut stroke_width_buffer = ryu::Buffer::new();
-    let stroke_width_str = stroke_width_buffer.format(stroke_width);
 
     write!(
         svg_buffer,
-        "
-    <line x1=\"{start_x_str}\" y1=\"{start_y_str}\" x2=\"{end_x_str}\" y2=\"{end_y_str}\" stroke=\"{edge_color}\" \
-         stroke-width=\"{stroke_width_str}\"/>
-    <text x= \"{x_str}\" y=\"{y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, sans-serif\" \
-         fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\">{edge_label}</text>\n",
+        "    <text x= \"{x_str}\" y=\"{y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, sans-serif\" \
+         fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"",
     )
     .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    if rotate_edge_label {
+        // Keep the angle in (-90, 90] degrees such that the text reads from left to right
+        let mut angle = label_dir_y.atan2(label_dir_x).to_degrees();
+        if angle > 90.0 {
+            angle -= 180.0;
+        } else if angle <= -90.0 {
+            angle += 180.0;
+        }
+        let mut angle_buffer = ryu::Buffer::new();
+        let angle_str = angle_buffer.format(angle);
+        write!(
+            svg_buffer,
+            " transform=\"rotate({angle_str} {x_str} {y_str})\""
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    writeln!(svg_buffer, ">{edge_label}</text>")
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+}
+
+/// Returns the point at the given `distance` from `from` in the direction of `towards`, or `from` if
+/// both points coincide.
+fn move_towards(from: (f32, f32), towards: (f32, f32), distance: f32) -> (f32, f32) {
+    let (dir_x, dir_y) = (towards.0 - from.0, towards.1 - from.1);
+    let length = (dir_x * dir_x + dir_y * dir_y).sqrt();
+    if length == 0.0 {
+        return from;
+    }
+    (
+        from.0 + distance * dir_x / length,
+        from.1 + distance * dir_y / length,
+    )
+}
+
+/// Returns the point at the fraction `t` of the way from `start` to `end`. For `t` = 0.5, this is
+/// exactly the midpoint `(start + end) / 2`, since halving is exact in floating point arithmetic.
+fn interpolate(start: (f32, f32), end: (f32, f32), t: f32) -> (f32, f32) {
+    (
+        start.0 * (1.0 - t) + end.0 * t,
+        start.1 * (1.0 - t) + end.1 * t,
+    )
+}
+
+/// Returns the anchor of the label of an edge drawn by [`draw_edge`] with the same arguments, or
+/// `None` if the edge is not drawn since its nodes are too close.
+fn edge_label_anchor(
+    coord_source: (f32, f32),
+    coord_target: (f32, f32),
+    edge_label_position: f32,
+    radius: f32,
+    min_edge_length: f32,
+    shape: EdgeShape,
+) -> Option<(f32, f32)> {
+    let (coord_x_source, coord_y_source) = coord_source;
+    let (coord_x_target, coord_y_target) = coord_target;
+    let dir_vec_x = coord_x_target - coord_x_source;
+    let dir_vec_y = coord_y_target - coord_y_source;
+    let distance = (dir_vec_x * dir_vec_x + dir_vec_y * dir_vec_y).sqrt();
+    if distance < min_edge_length {
+        return None;
+    }
+
+    match shape {
+        EdgeShape::Line => {}
+        EdgeShape::ReciprocalLine(offset) => {
+            let (start, end) = reciprocal_line_ends(coord_source, coord_target, radius, offset);
+            return Some(interpolate(start, end, edge_label_position));
+        }
+        EdgeShape::TreeCurve(orientation) => {
+            let curve = tree_edge_curve(coord_source, coord_target, radius, orientation);
+            return Some(bezier_point_and_tangent(curve, edge_label_position).0);
+        }
+        EdgeShape::Orthogonal(orientation) => {
+            let route = orthogonal_route(coord_source, coord_target, radius, orientation);
+            return Some(polyline_point_and_direction(route, edge_label_position).0);
+        }
+        EdgeShape::QuadraticCurve(curvature) => {
+            let curve = quadratic_edge_curve(coord_source, coord_target, radius, curvature);
+            return Some(bezier_point_and_tangent(curve, edge_label_position).0);
+        }
+    }
+    // The label is placed along the line between the boundaries of the circles
+    let offset = radius * (1.0 - 2.0 * edge_label_position) / distance;
+    Some((
+        coord_x_source + dir_vec_x * (edge_label_position + offset),
+        coord_y_source + dir_vec_y * (edge_label_position + offset),
+    ))
 }
 
-/// Scales normalized coordinates (0.0 to 1.0, 0.0 to 1.0) to actual canvas coordinates (0 to width,
-/// 0 to height). Takes into account the margins specified in the settings. Margins are specified as
-/// a fraction of the total width/height and are applied on both sides (left/right and top/bottom).
+/// Returns the start and end point of a reciprocal edge, which is shifted sideways by `offset` to
+/// the right of its direction, see [`EdgeShape::ReciprocalLine`].
 ///
-/// E.g. if `margin_x` is 0.1, then 10% of the width is reserved as margin on the left and 10% on
-/// the right, leaving 80% of the width for the actual graph drawing area.
-fn scale(
-    (normalized_x, normalized_y): (f32, f32),
-    margin_x: f32,
-    margin_y: f32,
-    width: f32,
-    height: f32,
+/// Both points lie on the boundaries of the circles of the nodes. Offsets larger than the `radius`
+/// are reduced to it.
+fn reciprocal_line_ends(
+    (coord_x_source, coord_y_source): (f32, f32),
+    (coord_x_target, coord_y_target): (f32, f32),
+    radius: f32,
+    offset: f32,
+) -> ((f32, f32), (f32, f32)) {
+    let dir_vec_x = coord_x_target - coord_x_source;
+    let dir_vec_y = coord_y_target - coord_y_source;
+    let distance = (dir_vec_x * dir_vec_x + dir_vec_y * dir_vec_y).sqrt();
+    let (unit_dir_vec_x, unit_dir_vec_y) = (dir_vec_x / distance, dir_vec_y / distance);
+
+    // Since the y-axis points down, rotating the direction by 90 degrees clockwise points right
+    let (normal_x, normal_y) = (-unit_dir_vec_y, unit_dir_vec_x);
+    let offset = offset.min(radius);
+    let along = (radius * radius - offset * offset).sqrt();
+    (
+        (
+            coord_x_source + along * unit_dir_vec_x + offset * normal_x,
+            coord_y_source + along * unit_dir_vec_y + offset * normal_y,
+        ),
+        (
+            coord_x_target - along * unit_dir_vec_x + offset * normal_x,
+            coord_y_target - along * unit_dir_vec_y + offset * normal_y,
+        ),
+    )
+}
+
+/// Defines an arrowhead marker of the given `style` for each color in `arrowhead_ids` by writing a
+/// <defs> tag to the provided `svg_buffer`. The marker of a color has the id
+/// `arrowhead-<id>`, where `id` is the value of the color in `arrowhead_ids`.
+///
+/// The markers are sized relative to the stroke width of the edge, see [`ARROWHEAD_SIZE_FACTOR`],
+/// and start at the end of the line, such that lines have to stop short of their target by the
+/// size of the arrowhead. They are painted in their color instead of using `context-stroke`, since
+/// not all renderers support the latter, e.g. the one used for PDF documents.
+fn draw_arrowhead_defs(
+    svg_buffer: &mut String,
+    style: ArrowStyle,
+    arrowhead_ids: &HashMap<Cow<'_, str>, usize>,
+) {
+    let mut arrowheads: Vec<_> = arrowhead_ids.iter().collect();
+    arrowheads.sort_unstable_by_key(|(_, id)| **id);
+    let mut size_buffer = ryu::Buffer::new();
+    let size_str = size_buffer.format(ARROWHEAD_SIZE_FACTOR);
+
+    svg_buffer.push_str("\n    <defs>\n");
+    for (color, id) in arrowheads {
+        write!(
+            svg_buffer,
+            "        <marker id=\"arrowhead-{id}\" viewBox=\"0 0 10 10\" refX=\"0\" refY=\"5\" \
+             markerWidth=\"{size_str}\" markerHeight=\"{size_str}\" markerUnits=\"strokeWidth\" \
+             orient=\"auto-start-reverse\">
+            "
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        match style {
+            ArrowStyle::FilledTriangle => {
+                write!(svg_buffer, "<path d=\"M 0 0 L 10 5 L 0 10 Z\" fill=\"{color}\"/>")
+            }
+            ArrowStyle::OpenTriangle => write!(
+                svg_buffer,
+                "<path d=\"M 1 1 L 9 5 L 1 9 Z\" fill=\"none\" stroke=\"{color}\" \
+                 stroke-width=\"2.5\"/>"
+            ),
+            ArrowStyle::Diamond => {
+                write!(svg_buffer, "<path d=\"M 0 5 L 5 0 L 10 5 L 5 10 Z\" fill=\"{color}\"/>")
+            }
+            ArrowStyle::Circle => {
+                write!(svg_buffer, "<circle cx=\"5\" cy=\"5\" r=\"5\" fill=\"{color}\"/>")
+            }
+        }
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        svg_buffer.push_str("\n        </marker>\n");
+    }
+    svg_buffer.push_str("    </defs>\n");
+}
+
+/// Returns the control point of the quadratic Bézier curve of an edge with the given `curvature`,
+/// see [`EdgeShape::QuadraticCurve`].
+///
+/// The control point is shifted from the midpoint between the centers of the nodes by `curvature`
+/// times their distance, perpendicular to the direction from source to target.
+fn quadratic_control_point(
+    (coord_x_source, coord_y_source): (f32, f32),
+    (coord_x_target, coord_y_target): (f32, f32),
+    curvature: f32,
 ) -> (f32, f32) {
-    let upscaled_x = normalized_x * width;
-    let upscaled_y = normalized_y * height;
+    // Since the y-axis points down, rotating the direction by 90 degrees clockwise points right.
+    // The normal is not normalized, such that the offset is proportional to the distance.
+    let (normal_x, normal_y) = (
+        coord_y_source - coord_y_target,
+        coord_x_target - coord_x_source,
+    );
+    (
+        (coord_x_source + coord_x_target) / 2.0 + curvature * normal_x,
+        (coord_y_source + coord_y_target) / 2.0 + curvature * normal_y,
+    )
+}
 
-    let margin_adjusted_upscaled_x = margin_x * width + upscaled_x * (1.0 - 2.0 * margin_x);
-    let margin_adjusted_upscaled_y = margin_y * height + upscaled_y * (1.0 - 2.0 * margin_y);
+/// Returns the start point, the two control points and the end point of the cubic Bézier curve
+/// equal to the quadratic Bézier curve of an edge with the given `curvature`, see
+/// [`quadratic_control_point`].
+///
+/// The curve leaves the source and enters the target towards the control point, starting and
+/// ending on the boundaries of the circles of the nodes.
+fn quadratic_edge_curve(
+    coord_source: (f32, f32),
+    coord_target: (f32, f32),
+    radius: f32,
+    curvature: f32,
+) -> [(f32, f32); 4] {
+    let control = quadratic_control_point(coord_source, coord_target, curvature);
+    let start = move_towards(coord_source, control, radius);
+    let end = move_towards(coord_target, control, radius);
+    // Degree elevation: the inner control points of the cubic curve lie two thirds of the way
+    // from the ends to the quadratic control point
+    let elevate = |(x, y): (f32, f32)| {
+        (
+            x + 2.0 / 3.0 * (control.0 - x),
+            y + 2.0 / 3.0 * (control.1 - y),
+        )
+    };
+    [start, elevate(start), elevate(end), end]
+}
 
-    (margin_adjusted_upscaled_x, margin_adjusted_upscaled_y)
+/// Returns the start point, the two control points and the end point of the cubic Bézier curve of
+/// a tree edge of the hierarchical layout with the given `orientation`.
+///
+/// The curve leaves the source and enters the target along the direction of the hierarchy and
+/// bends halfway between their levels, like the edges of an org chart.
+fn tree_edge_curve(
+    (coord_x_source, coord_y_source): (f32, f32),
+    (coord_x_target, coord_y_target): (f32, f32),
+    radius: f32,
+    orientation: Orientation,
+) -> [(f32, f32); 4] {
+    // Work in coordinates where the levels are stacked along the second axis
+    let vertical = matches!(
+        orientation,
+        Orientation::TopToBottom | Orientation::BottomToTop
+    );
+    let ((source_col, source_row), (target_col, target_row)) = if vertical {
+        (
+            (coord_x_source, coord_y_source),
+            (coord_x_target, coord_y_target),
+        )
+    } else {
+        (
+            (coord_y_source, coord_x_source),
+            (coord_y_target, coord_x_target),
+        )
+    };
+
+    let sign = if target_row >= source_row { 1.0 } else { -1.0 };
+    let start_row = source_row + sign * radius;
+    let end_row = target_row - sign * radius;
+    let middle_row = (start_row + end_row) / 2.0;
+    let points = [
+        (source_col, start_row),
+        (source_col, middle_row),
+        (target_col, middle_row),
+        (target_col, end_row),
+    ];
+
+    if vertical {
+        points
+    } else {
+        points.map(|(col, row)| (row, col))
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{graph_to_svg::graph_to_svg_string, tests::position_map_test_case};
+/// Returns the start point, the two corners and the end point of the orthogonal route of an edge,
+/// see [`EdgeRouting::Orthogonal`].
+///
+/// The route leaves the source and enters the target along the direction of the hierarchy with the
+/// given `orientation` or, if `None`, along the axis along which the nodes are further apart. The
+/// corners are the control points of the corresponding [`tree_edge_curve`].
+fn orthogonal_route(
+    coord_source: (f32, f32),
+    coord_target: (f32, f32),
+    radius: f32,
+    orientation: Option<Orientation>,
+) -> [(f32, f32); 4] {
+    let orientation = orientation.unwrap_or_else(|| {
+        let (delta_x, delta_y) = (
+            coord_target.0 - coord_source.0,
+            coord_target.1 - coord_source.1,
+        );
+        if delta_y.abs() >= delta_x.abs() {
+            Orientation::TopToBottom
+        } else {
+            Orientation::LeftToRight
+        }
+    });
+    tree_edge_curve(coord_source, coord_target, radius, orientation)
+}
 
-    #[test]
-    fn test_scale() {
-        let (scaled_x, scaled_y) = super::scale((0.5, 0.5), 0.1, 0.1, 1000.0, 1000.0);
-        assert!((scaled_x - 500.0).abs() < f32::EPSILON);
-        assert!((scaled_y - 500.0).abs() < f32::EPSILON);
+/// Returns the point at the fraction `t` in [0.0, 1.0] of the length of the polyline through the
+/// given points and the direction of the segment containing it.
+fn polyline_point_and_direction(points: [(f32, f32); 4], t: f32) -> ((f32, f32), (f32, f32)) {
+    let segment_length = |((x_0, y_0), (x_1, y_1)): ((f32, f32), (f32, f32))| {
+        ((x_1 - x_0).powi(2) + (y_1 - y_0).powi(2)).sqrt()
+    };
+    let segments = [
+        (points[0], points[1]),
+        (points[1], points[2]),
+        (points[2], points[3]),
+    ];
+    let total_length: f32 = segments
+        .iter()
+        .map(|&segment| segment_length(segment))
+        .sum();
 
-        let (scaled_x, scaled_y) = super::scale((0.0, 0.0), 0.1, 0.1, 1000.0, 1000.0);
-        assert!((scaled_x - 100.0).abs() < f32::EPSILON);
-        assert!((scaled_y - 100.0).abs() < f32::EPSILON);
+    let mut remaining = t * total_length;
+    for (i, segment) in segments.into_iter().enumerate() {
+        let length = segment_length(segment);
+        let ((x_0, y_0), (x_1, y_1)) = segment;
+        // The last segment also takes the remainder due to rounding errors
+        if length > 0.0 && (remaining <= length || i == segments.len() - 1) {
+            let fraction = (remaining / length).min(1.0);
+            return (
+                (x_0 + (x_1 - x_0) * fraction, y_0 + (y_1 - y_0) * fraction),
+                ((x_1 - x_0) / length, (y_1 - y_0) / length),
+            );
+        }
+        remaining -= length;
+    }
 
-        let (scaled_x, scaled_y) = super::scale((1.0, 1.0), 0.1, 0.1, 1000.0, 1000.0);
-        assert!((scaled_x - 900.0).abs() < f32::EPSILON);
-        assert!((scaled_y - 900.0).abs() < f32::EPSILON);
+    // All segments are degenerate
+    let (x_0, y_0) = points[0];
+    let (x_3, y_3) = points[3];
+    ((x_0, y_0), (x_3 - x_0, y_3 - y_0))
+}
+
+/// Returns the point and the direction of the tangent of the cubic Bézier curve given by its start
+/// point, control points and end point at the parameter `t` in [0.0, 1.0].
+fn bezier_point_and_tangent(curve: [(f32, f32); 4], t: f32) -> ((f32, f32), (f32, f32)) {
+    let [(x_0, y_0), (x_1, y_1), (x_2, y_2), (x_3, y_3)] = curve;
+    let s = 1.0 - t;
+
+    let point = |p_0: f32, p_1: f32, p_2: f32, p_3: f32| {
+        s * s * s * p_0 + 3.0 * s * s * t * p_1 + 3.0 * s * t * t * p_2 + t * t * t * p_3
+    };
+    let derivative = |p_0: f32, p_1: f32, p_2: f32, p_3: f32| {
+        3.0 * s * s * (p_1 - p_0) + 6.0 * s * t * (p_2 - p_1) + 3.0 * t * t * (p_3 - p_2)
+    };
+
+    let (tangent_x, tangent_y) = (
+        derivative(x_0, x_1, x_2, x_3),
+        derivative(y_0, y_1, y_2, y_3),
+    );
+    let length = (tangent_x * tangent_x + tangent_y * tangent_y).sqrt();
+    // The tangent vanishes at the ends of curves whose control points coincide with the end
+    // points, in which case the direction from start to end is used
+    let tangent = if length > 0.0 {
+        (tangent_x / length, tangent_y / length)
+    } else {
+        (x_3 - x_0, y_3 - y_0)
+    };
+
+    (
+        (point(x_0, x_1, x_2, x_3), point(y_0, y_1, y_2, y_3)),
+        tangent,
+    )
+}
+
+/// Returns the nodes of the graph in the order in which they are drawn: First the nodes listed in
+/// `node_draw_order`, skipping indices of missing nodes and repeated indices, followed by the
+/// remaining nodes in index order.
+fn draw_order<G>(graph: &G, node_draw_order: Option<&[NodeIndex]>) -> Vec<G::NodeId>
+where
+    G: IntoNodeReferences + NodeIndexable,
+{
+    let Some(node_draw_order) = node_draw_order else {
+        return graph.node_references().map(|node| node.id()).collect();
+    };
+
+    let mut exists = FixedBitSet::with_capacity(graph.node_bound());
+    for node in graph.node_references() {
+        exists.insert(NodeIndexable::to_index(graph, node.id()));
+    }
+    let mut drawn = FixedBitSet::with_capacity(graph.node_bound());
+    let mut nodes = Vec::with_capacity(exists.count_ones(..));
+    for node in node_draw_order {
+        let index = node.index();
+        if index < graph.node_bound() && exists.contains(index) && !drawn.put(index) {
+            nodes.push(NodeIndexable::from_index(graph, index));
+        }
+    }
+    for node in graph.node_references() {
+        if !drawn.contains(NodeIndexable::to_index(graph, node.id())) {
+            nodes.push(node.id());
+        }
     }
+    nodes
+}
 
-    #[test]
-    fn test_graph_to_svg_with_position_map() {
-        let (graph, settings) = position_map_test_case();
-        let svg_output = graph_to_svg_string(&graph, &settings);
+/// Returns the smallest distance between two distinct `positions`, or `None` if there are fewer
+/// than two distinct positions. Coinciding positions are ignored, since no radius would keep their
+/// nodes apart.
+///
+/// The positions are sorted by their x coordinate, such that only pairs which are closer than the
+/// smallest distance found so far in x direction have to be compared.
+fn min_distance(mut positions: Vec<(f32, f32)>) -> Option<f32> {
+    positions.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
 
-        println!("SVG Output:\n{}", svg_output);
+    let mut min_distance = f32::INFINITY;
+    for (i, &(x_i, y_i)) in positions.iter().enumerate() {
+        for &(x_j, y_j) in &positions[i + 1..] {
+            if x_j - x_i >= min_distance {
+                break;
+            }
+            let distance = ((x_j - x_i).powi(2) + (y_j - y_i).powi(2)).sqrt();
+            if distance > 0.0 {
+                min_distance = min_distance.min(distance);
+            }
+        }
+    }
+    min_distance.is_finite().then_some(min_distance)
+}
 
-        let expected_output =
-            "<svg width=\"500.0\" height=\"500.0\" xmlns=\"http://www.w3.org/2000/svg\">
+/// Groups the edges of the graph into bundles of parallel edges, that is, edges between the same
+/// pair of nodes. In directed graphs, only edges in the same direction are parallel.
+///
+/// Returns the first edge and the number of edges of each bundle, in the order of the first edges.
+fn bundle_parallel_edges<G>(graph: &G) -> Vec<(G::EdgeId, G::NodeId, G::NodeId, usize)>
+where
+    G: IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    let mut bundles: Vec<(G::EdgeId, G::NodeId, G::NodeId, usize)> = Vec::new();
+    let mut bundle_indices: HashMap<(usize, usize), usize> = HashMap::new();
+    for edge in graph.edge_references() {
+        let source = NodeIndexable::to_index(graph, edge.source());
+        let target = NodeIndexable::to_index(graph, edge.target());
+        let key = if graph.is_directed() {
+            (source, target)
+        } else {
+            (source.min(target), source.max(target))
+        };
+        match bundle_indices.get(&key) {
+            Some(&bundle_index) => bundles[bundle_index].3 += 1,
+            None => {
+                bundle_indices.insert(key, bundles.len());
+                bundles.push((edge.id(), edge.source(), edge.target(), 1));
+            }
+        }
+    }
+    bundles
+}
 
-    <circle cx=\"137.5\" cy=\"137.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
-    <text x=\"137.5\" y=\"137.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">0</text>
+/// Escapes the characters of `value` which are not allowed in a double-quoted XML attribute or
+/// in the text content of an element.
+fn escape_attribute(value: &str) -> Cow<'_, str> {
+    if !value.contains(['&', '<', '"']) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
 
-    <circle cx=\"362.5\" cy=\"137.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
-    <text x=\"362.5\" y=\"137.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">1</text>
+/// Converts the subscripts and superscripts of a rich label to <tspan> elements.
+///
+/// `_` starts a subscript and `^` a superscript. The script consists of the following
+/// alphanumeric characters or, if enclosed in braces, of everything up to the closing brace. A `_`
+/// or `^` which is not followed by a script is kept as is. Labels without `_` and `^` are returned
+/// as is without allocating.
+fn rich_label_markup(label: &str) -> Cow<'_, str> {
+    if !label.contains(['_', '^']) {
+        return Cow::Borrowed(label);
+    }
+    let mut markup = String::with_capacity(label.len());
+    let mut chars = label.chars().peekable();
+    while let Some(c) = chars.next() {
+        let baseline_shift = match c {
+            '_' => "sub",
+            '^' => "super",
+            _ => {
+                markup.push(c);
+                continue;
+            }
+        };
 
-    <circle cx=\"362.5\" cy=\"362.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
-    <text x=\"362.5\" y=\"362.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">2</text>
+        let mut script = String::new();
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c == '}' {
+                    break;
+                }
+                script.push(c);
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if !c.is_alphanumeric() {
+                    break;
+                }
+                script.push(c);
+                chars.next();
+            }
+        }
 
-    <circle cx=\"137.5\" cy=\"362.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
-    <text x=\"137.5\" y=\"362.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">3</text>
+        if script.is_empty() {
+            markup.push(c);
+        } else {
+            write!(
+                markup,
+                "<tspan baseline-shift=\"{baseline_shift}\" font-size=\"70%\">{script}</tspan>"
+            )
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+    }
+    Cow::Owned(markup)
+}
 
-    <line x1=\"162.5\" y1=\"137.5\" x2=\"337.5\" y2=\"137.5\" stroke=\"black\" \
-             stroke-width=\"5.0\"/>
-    <text x= \"250.0\" y=\"137.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+/// Truncates the `label` to `max_chars` characters, replacing its last character by an ellipsis `…`
+/// if it is longer. If `max_chars` is `None`, the label is returned as is.
+fn truncate_label(label: String, max_chars: Option<usize>) -> String {
+    match max_chars {
+        Some(max_chars) if label.chars().count() > max_chars => {
+            let mut truncated: String = label.chars().take(max_chars.saturating_sub(1)).collect();
+            truncated.push('…');
+            truncated
+        }
+        _ => label,
+    }
+}
 
-    <line x1=\"362.5\" y1=\"162.5\" x2=\"362.5\" y2=\"337.5\" stroke=\"black\" \
-             stroke-width=\"5.0\"/>
-    <text x= \"362.5\" y=\"250.0\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+/// Returns the value of the `class` attribute of a node or edge, consisting of the `base` class
+/// followed by the `additional` classes, if any.
+fn css_class(base: &str, additional: &str) -> String {
+    let additional = additional.trim();
+    if additional.is_empty() {
+        base.to_owned()
+    } else {
+        format!("{base} {additional}")
+    }
+}
 
-    <line x1=\"337.5\" y1=\"362.5\" x2=\"162.5\" y2=\"362.5\" stroke=\"black\" \
-             stroke-width=\"5.0\"/>
-    <text x= \"250.0\" y=\"362.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+/// Defines the drop shadow of the nodes with the id `node-shadow` by writing a <defs> tag containing
+/// a <filter> to the provided `svg_buffer`.
+///
+/// The filter blurs the alpha channel of the node, offsets it to the bottom right and draws the node
+/// on top of it. Offset and blur are relative to the `radius` of the nodes, see
+/// [`NODE_SHADOW_OFFSET_FACTOR`] and [`NODE_SHADOW_BLUR_FACTOR`].
+fn draw_shadow_defs(svg_buffer: &mut String, radius: f32) {
+    let mut offset_buffer = ryu::Buffer::new();
+    let offset_str = offset_buffer.format(NODE_SHADOW_OFFSET_FACTOR * radius);
+    let mut blur_buffer = ryu::Buffer::new();
+    let blur_str = blur_buffer.format(NODE_SHADOW_BLUR_FACTOR * radius);
+    let mut opacity_buffer = ryu::Buffer::new();
+    let opacity_str = opacity_buffer.format(NODE_SHADOW_OPACITY);
 
-    <line x1=\"137.5\" y1=\"337.5\" x2=\"137.5\" y2=\"162.5\" stroke=\"black\" \
-             stroke-width=\"5.0\"/>
-    <text x= \"137.5\" y=\"250.0\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
-             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+    writeln!(
+        svg_buffer,
+        "\n    <defs>
+        <filter id=\"node-shadow\" x=\"-50%\" y=\"-50%\" width=\"200%\" height=\"200%\">
+            <feGaussianBlur in=\"SourceAlpha\" stdDeviation=\"{blur_str}\"/>
+            <feOffset dx=\"{offset_str}\" dy=\"{offset_str}\"/>
+            <feComponentTransfer>
+                <feFuncA type=\"linear\" slope=\"{opacity_str}\"/>
+            </feComponentTransfer>
+            <feMerge>
+                <feMergeNode/>
+                <feMergeNode in=\"SourceGraphic\"/>
+            </feMerge>
+        </filter>
+    </defs>",
+    )
+    .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+}
+
+/// Draws a <defs> tag containing a <radialGradient> for each `(inner, outer)` pair of colors to the
+/// provided `svg_buffer`. Each gradient can be referenced by `url(#node-gradient-<id>)`.
+fn draw_gradient_defs(svg_buffer: &mut String, gradient_ids: HashMap<(String, String), usize>) {
+    let mut gradients: Vec<_> = gradient_ids.into_iter().collect();
+    gradients.sort_unstable_by_key(|(_, id)| *id);
+
+    svg_buffer.push_str("\n    <defs>\n");
+    for ((inner, outer), id) in gradients {
+        writeln!(
+            svg_buffer,
+            "        <radialGradient id=\"node-gradient-{id}\">
+            <stop offset=\"0%\" stop-color=\"{inner}\"/>
+            <stop offset=\"100%\" stop-color=\"{outer}\"/>
+        </radialGradient>",
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+    svg_buffer.push_str("    </defs>\n");
+}
+
+/// Draws the title centered at the top of the canvas by writing a <text> tag to the provided
+/// `svg_buffer`.
+///
+/// Returns the height of the area reserved for the title.
+fn draw_title(svg_buffer: &mut String, title: &str, width: f32, font_size: f32) -> f32 {
+    let title_font_size = font_size * TITLE_FONT_SIZE_FACTOR;
+    let title_height = title_height(font_size);
+
+    let mut x_buffer = ryu::Buffer::new();
+    let x_str = x_buffer.format(width / 2.0);
+    let mut y_buffer = ryu::Buffer::new();
+    let y_str = y_buffer.format(title_height / 2.0);
+    let mut font_size_buffer = ryu::Buffer::new();
+    let font_size_str = font_size_buffer.format(title_font_size);
+    let title = escape_attribute(title);
+
+    write!(
+        svg_buffer,
+        "
+    <text x=\"{x_str}\" y=\"{y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, \
+         sans-serif\" fill=\"black\" text-anchor=\"middle\" \
+         dominant-baseline=\"central\">{title}</text>\n",
+    )
+    .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+
+    title_height
+}
+
+/// Returns the height of the space reserved for the title at the top of the canvas, which is twice
+/// the font size of the title.
+pub(crate) fn title_height(font_size: f32) -> f32 {
+    2.0 * font_size * TITLE_FONT_SIZE_FACTOR
+}
+
+/// Draws a grid of the normalized coordinates by writing a <line> tag for each gridline and a <text>
+/// tag for its coordinate to the provided `svg_buffer`. The gridlines are placed at every
+/// `1 / DEBUG_GRID_DIVISIONS` and mapped to the canvas using `scale_position`.
+fn draw_debug_grid(
+    svg_buffer: &mut String,
+    scale_position: impl Fn((f32, f32)) -> (f32, f32),
+    font_size: f32,
+) {
+    let mut font_size_buffer = ryu::Buffer::new();
+    let font_size_str = font_size_buffer.format(font_size * DEBUG_GRID_FONT_SIZE_FACTOR);
+
+    svg_buffer.push_str("    <g stroke=\"lightgray\" stroke-width=\"1\">\n");
+    for step in 0..=DEBUG_GRID_DIVISIONS {
+        let coordinate = step as f32 / DEBUG_GRID_DIVISIONS as f32;
+        // Vertical gridline at x = coordinate and horizontal gridline at y = coordinate
+        for (start, end) in [
+            ((coordinate, 0.0), (coordinate, 1.0)),
+            ((0.0, coordinate), (1.0, coordinate)),
+        ] {
+            let ((start_x, start_y), (end_x, end_y)) = (scale_position(start), scale_position(end));
+            let mut start_x_buffer = ryu::Buffer::new();
+            let start_x_str = start_x_buffer.format(start_x);
+            let mut start_y_buffer = ryu::Buffer::new();
+            let start_y_str = start_y_buffer.format(start_y);
+            let mut end_x_buffer = ryu::Buffer::new();
+            let end_x_str = end_x_buffer.format(end_x);
+            let mut end_y_buffer = ryu::Buffer::new();
+            let end_y_str = end_y_buffer.format(end_y);
+            writeln!(
+                svg_buffer,
+                "        <line x1=\"{start_x_str}\" y1=\"{start_y_str}\" x2=\"{end_x_str}\" y2=\"{end_y_str}\"/>"
+            )
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+    }
+    svg_buffer.push_str("    </g>\n");
+
+    // The coordinates are labeled at the start of each gridline
+    let mut coordinate_buffer = ryu::Buffer::new();
+    for step in 0..=DEBUG_GRID_DIVISIONS {
+        let coordinate = step as f32 / DEBUG_GRID_DIVISIONS as f32;
+        let coordinate_str = coordinate_buffer.format(coordinate);
+        for (anchor, text_anchor, baseline) in [
+            ((coordinate, 0.0), "middle", "text-after-edge"),
+            ((0.0, coordinate), "end", "central"),
+        ] {
+            let (x, y) = scale_position(anchor);
+            let mut x_buffer = ryu::Buffer::new();
+            let x_str = x_buffer.format(x);
+            let mut y_buffer = ryu::Buffer::new();
+            let y_str = y_buffer.format(y);
+            writeln!(
+                svg_buffer,
+                "    <text x=\"{x_str}\" y=\"{y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, \
+                 sans-serif\" fill=\"gray\" text-anchor=\"{text_anchor}\" \
+                 dominant-baseline=\"{baseline}\">{coordinate_str}</text>",
+            )
+            .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+        }
+    }
+}
+
+/// Returns the position of the top left corner and the size `((x, y), (width, height))` of the
+/// legend box drawn by [`draw_legend`] with the same arguments.
+fn legend_box(
+    entries: &[(String, String)],
+    corner: Corner,
+    width: f32,
+    height: f32,
+    title_height: f32,
+    font_size: f32,
+) -> ((f32, f32), (f32, f32)) {
+    let padding = font_size / 2.0;
+    let row_height = font_size * LEGEND_ROW_HEIGHT_FACTOR;
+    let swatch_size = font_size;
+    let max_label_len = entries
+        .iter()
+        .map(|(_, label)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let label_width = max_label_len as f32 * font_size * ESTIMATED_CHAR_WIDTH_FACTOR;
+
+    let box_width = 3.0 * padding + swatch_size + label_width;
+    let box_height = 2.0 * padding + entries.len() as f32 * row_height;
+    let box_x = match corner {
+        Corner::TopLeft | Corner::BottomLeft => padding,
+        Corner::TopRight | Corner::BottomRight => width - padding - box_width,
+    };
+    let box_y = match corner {
+        Corner::TopLeft | Corner::TopRight => title_height + padding,
+        Corner::BottomLeft | Corner::BottomRight => height - padding - box_height,
+    };
+
+    ((box_x, box_y), (box_width, box_height))
+}
+
+/// Draws a legend box in the provided corner of the canvas by writing a <rect> tag for the box and
+/// a <rect> swatch and <text> label for each `(color, label)` entry to the provided `svg_buffer`.
+///
+/// The legend is sized according to the font size and kept half a font size away from the borders
+/// of the canvas. In the top corners, it is additionally placed below the title area of height
+/// `title_height`.
+fn draw_legend(
+    svg_buffer: &mut String,
+    entries: &[(String, String)],
+    corner: Corner,
+    width: f32,
+    height: f32,
+    title_height: f32,
+    font_size: f32,
+) {
+    let padding = font_size / 2.0;
+    let row_height = font_size * LEGEND_ROW_HEIGHT_FACTOR;
+    let swatch_size = font_size;
+    let ((box_x, box_y), (box_width, box_height)) =
+        legend_box(entries, corner, width, height, title_height, font_size);
+
+    let mut x_buffer = ryu::Buffer::new();
+    let mut y_buffer = ryu::Buffer::new();
+    let mut box_width_buffer = ryu::Buffer::new();
+    let mut box_height_buffer = ryu::Buffer::new();
+    write!(
+        svg_buffer,
+        "
+    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"black\"/>\n",
+        x_buffer.format(box_x),
+        y_buffer.format(box_y),
+        box_width_buffer.format(box_width),
+        box_height_buffer.format(box_height),
+    )
+    .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+
+    let mut swatch_size_buffer = ryu::Buffer::new();
+    let swatch_size_str = swatch_size_buffer.format(swatch_size);
+    let mut font_size_buffer = ryu::Buffer::new();
+    let font_size_str = font_size_buffer.format(font_size);
+
+    for (i, (color, label)) in entries.iter().enumerate() {
+        let row_y = box_y + padding + i as f32 * row_height;
+
+        let mut swatch_x_buffer = ryu::Buffer::new();
+        let swatch_x_str = swatch_x_buffer.format(box_x + padding);
+        let mut swatch_y_buffer = ryu::Buffer::new();
+        let swatch_y_str = swatch_y_buffer.format(row_y + (row_height - swatch_size) / 2.0);
+        let mut text_x_buffer = ryu::Buffer::new();
+        let text_x_str = text_x_buffer.format(box_x + 2.0 * padding + swatch_size);
+        let mut text_y_buffer = ryu::Buffer::new();
+        let text_y_str = text_y_buffer.format(row_y + row_height / 2.0);
+        let color = escape_attribute(color);
+        let label = escape_attribute(label);
+
+        write!(
+            svg_buffer,
+            "    <rect x=\"{swatch_x_str}\" y=\"{swatch_y_str}\" width=\"{swatch_size_str}\" \
+             height=\"{swatch_size_str}\" fill=\"{color}\" stroke=\"black\"/>
+    <text x=\"{text_x_str}\" y=\"{text_y_str}\" font-size=\"{font_size_str}px\" font-family=\"DejaVu Sans, \
+             sans-serif\" fill=\"black\" text-anchor=\"start\" \
+             dominant-baseline=\"central\">{label}</text>\n",
+        )
+        .expect("This should not fail according to the std lib: https://doc.rust-lang.org/src/alloc/string.rs.html#3276");
+    }
+}
+
+/// Computes the bounding box `((min_x, min_y), (max_x, max_y))` of the positions of all nodes.
+fn bounding_box<G, PositionMapFn>(
+    graph: &G,
+    position_map: &PositionMapFn,
+) -> ((f32, f32), (f32, f32))
+where
+    G: IntoNodeReferences,
+    PositionMapFn: Fn(G::NodeId) -> (f32, f32),
+{
+    let mut min = (f32::INFINITY, f32::INFINITY);
+    let mut max = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for node in graph.node_references() {
+        let (x, y) = position_map(node.id());
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+    }
+    (min, max)
+}
+
+/// Brings a position into the range [0.0, 1.0] according to the provided [`NormalizeMode`].
+///
+/// For [`NormalizeMode::Fit`], the bounding box of all positions has to be provided.
+fn normalize_position(
+    (x, y): (f32, f32),
+    mode: NormalizeMode,
+    bounding_box: Option<((f32, f32), (f32, f32))>,
+) -> (f32, f32) {
+    match (mode, bounding_box) {
+        (NormalizeMode::Clamp, _) => (x.clamp(0.0, 1.0), y.clamp(0.0, 1.0)),
+        (NormalizeMode::Fit, Some(((min_x, min_y), (max_x, max_y)))) => {
+            let fit = |value: f32, min: f32, max: f32| {
+                if max - min > 0.0 {
+                    (value - min) / (max - min)
+                } else {
+                    0.5
+                }
+            };
+            (fit(x, min_x, max_x), fit(y, min_y, max_y))
+        }
+        _ => (x, y),
+    }
+}
+
+/// Shrinks the normalized `position` towards the center (0.5, 0.5) along the longer side of the
+/// `drawing_area`, such that equal distances in normalized coordinates result in equal distances in
+/// pixels along both axes after scaling.
+fn correct_aspect_ratio((x, y): (f32, f32), (width, height): (f32, f32)) -> (f32, f32) {
+    if width <= 0.0 || height <= 0.0 {
+        return (x, y);
+    }
+    let side = width.min(height);
+    (
+        0.5 + (x - 0.5) * side / width,
+        0.5 + (y - 0.5) * side / height,
+    )
+}
+
+/// Rounds both coordinates of the normalized `position` to the nearest multiple of
+/// `1 / divisions`.
+fn snap_to_grid((x, y): (f32, f32), divisions: usize) -> (f32, f32) {
+    let divisions = divisions as f32;
+    (
+        (x * divisions).round() / divisions,
+        (y * divisions).round() / divisions,
+    )
+}
+
+/// Scales the normalized position to the canvas of the given `width` and `height`, leaving the
+/// margins free. With [`YAxis::Up`], the y-coordinate is flipped first.
+///
+/// Margins are specified as a fraction of the total width/height and are applied on both sides.
+/// E.g. if `margin_x` is 0.1, then 10% of the width is reserved as margin on the left and 10% on
+/// the right, leaving 80% of the width for the actual graph drawing area.
+fn scale(
+    (normalized_x, normalized_y): (f32, f32),
+    margin_x: f32,
+    margin_y: f32,
+    width: f32,
+    height: f32,
+    y_axis: YAxis,
+) -> (f32, f32) {
+    let normalized_y = match y_axis {
+        YAxis::Down => normalized_y,
+        YAxis::Up => 1.0 - normalized_y,
+    };
+    let upscaled_x = normalized_x * width;
+    let upscaled_y = normalized_y * height;
+
+    let margin_adjusted_upscaled_x = margin_x * width + upscaled_x * (1.0 - 2.0 * margin_x);
+    let margin_adjusted_upscaled_y = margin_y * height + upscaled_y * (1.0 - 2.0 * margin_y);
+
+    (margin_adjusted_upscaled_x, margin_adjusted_upscaled_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::Cow,
+        collections::{HashMap, HashSet},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use petgraph::{
+        graph::{DiGraph, EdgeIndex, NodeIndex, UnGraph},
+        stable_graph::StableGraph,
+    };
+
+    use crate::{
+        errors::{GraphElement, InvalidSettingsError, VisGraphError},
+        graph_to_svg::{
+            graph_to_svg, graph_to_svg_elements, graph_to_svg_stream, graph_to_svg_string,
+            graph_to_svg_string_cropped, rich_label_markup, truncate_label,
+            try_graph_to_svg_string, XML_DECLARATION,
+        },
+        layout::{cache::LayoutCache, positions_from_map, NormalizeMode, YAxis},
+        settings::{
+            ArrowMode, ArrowStyle, Color, Corner, EdgeRouting, EdgeStyle, NodeFill,
+            SettingsBuilder, Units,
+        },
+        tests::{custom_labels_test_case, position_map_test_case},
+        Layout, Orientation,
+    };
+
+    /// Renders a path of three nodes at x-positions -1.0, 0.0 and 3.0 using the provided
+    /// normalization mode.
+    fn out_of_range_svg(mode: NormalizeMode) -> String {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(nodes[0], nodes[1], ());
+        graph.add_edge(nodes[1], nodes[2], ());
+
+        let position_map = |node_id: NodeIndex| match node_id.index() {
+            0 => (-1.0, 0.5),
+            1 => (0.0, 0.5),
+            _ => (3.0, 0.5),
+        };
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .position_map(position_map)
+            .normalize_positions(mode)
+            .build()
+            .expect("Values should be valid.");
+
+        graph_to_svg_string(&graph, &settings)
+    }
+
+    #[test]
+    fn test_trivial_graphs() {
+        let empty_graph = UnGraph::<(), ()>::new_undirected();
+        let mut single_node_graph = UnGraph::<(), ()>::new_undirected();
+        single_node_graph.add_node(());
+
+        for layout in [
+            Layout::Circular,
+            Layout::Hierarchical(Orientation::TopToBottom),
+            Layout::ForceDirected,
+            Layout::Bipartite(None),
+            Layout::Random,
+            Layout::Spiral,
+            Layout::Clustered(vec![0, 0, 1, 1, 1, 1]),
+            Layout::HexGrid,
+            Layout::Auto,
+        ] {
+            let settings = SettingsBuilder::new()
+                .width(500.0)
+                .height(500.0)
+                .layout(layout)
+                .normalize_positions(NormalizeMode::Fit)
+                .avoid_overlaps(true)
+                .build()
+                .expect("Values should be valid.");
+
+            assert_eq!(
+                graph_to_svg_string(&empty_graph, &settings),
+                "<svg width=\"500.0\" height=\"500.0\" xmlns=\"http://www.w3.org/2000/svg\">\n</svg>"
+            );
+
+            let svg_output = graph_to_svg_string(&single_node_graph, &settings);
+            assert!(!svg_output.contains("NaN") && !svg_output.contains("inf"));
+            assert!(svg_output.contains("<circle cx=\"250.0\" cy=\"250.0\""));
+        }
+    }
+
+    #[test]
+    fn test_stable_graph_with_holes() {
+        let mut graph = StableGraph::<(), ()>::new();
+        let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+        graph.remove_node(nodes[2]);
+
+        for layout in [
+            Layout::Circular,
+            Layout::Hierarchical(Orientation::TopToBottom),
+            Layout::ForceDirected,
+            Layout::Bipartite(None),
+            Layout::Random,
+            Layout::Spiral,
+            Layout::Clustered(vec![0, 0, 1, 1, 1, 1]),
+            Layout::HexGrid,
+            Layout::Auto,
+        ] {
+            let settings = SettingsBuilder::new()
+                .layout(layout)
+                .emit_element_ids(true)
+                .build()
+                .unwrap();
+            let svg_output = graph_to_svg_string(&graph, &settings);
+
+            assert_eq!(svg_output.matches("<circle ").count(), 5);
+            assert_eq!(svg_output.matches("<line ").count(), 3);
+            assert!(!svg_output.contains("id=\"node-2\""));
+            assert!(!svg_output.contains("NaN") && !svg_output.contains("inf"));
+        }
+    }
+
+    #[test]
+    fn test_circular_layout_preserves_aspect_ratio() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        for _ in 0..8 {
+            graph.add_node(());
+        }
+        let settings = SettingsBuilder::new()
+            .width(1000.0)
+            .height(500.0)
+            .layout(Layout::Circular)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        let coordinate = |circle: &str, attribute: &str| -> f32 {
+            let start = circle.find(attribute).expect("Attribute should exist") + attribute.len();
+            let value = &circle[start..];
+            value[..value.find('"').expect("Value should be quoted")]
+                .parse()
+                .expect("Value should be a number")
+        };
+        let distances: Vec<f32> = svg_output
+            .split("<circle ")
+            .skip(1)
+            .map(|circle| {
+                let (x, y) = (coordinate(circle, "cx=\""), coordinate(circle, "cy=\""));
+                ((x - 500.0).powi(2) + (y - 250.0).powi(2)).sqrt()
+            })
+            .collect();
+
+        assert_eq!(distances.len(), 8);
+        for distance in &distances {
+            assert!((distance - distances[0]).abs() < 0.01, "{distances:?}");
+        }
+        // The circle fills the shorter side of the drawing area
+        assert!((distances[0] - 225.0).abs() < 0.01, "{distances:?}");
+    }
+
+    #[test]
+    fn test_show_index_badge() {
+        let (graph, _) = custom_labels_test_case();
+        let svg_for = |show_index_badge| {
+            let settings = SettingsBuilder::new()
+                .node_label_fn(|_| "label".to_string())
+                .show_index_badge(show_index_badge)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        let svg_output = svg_for(true);
+        let badges: Vec<&str> = svg_output
+            .split("<text class=\"index-badge\"")
+            .skip(1)
+            .map(|badge| {
+                let start = badge.find('>').expect("Tag should be closed") + 1;
+                &badge[start..start + badge[start..].find('<').expect("Text should end")]
+            })
+            .collect();
+        assert_eq!(badges, ["0", "1", "2", "3"]);
+        assert_eq!(svg_output.matches(">label</text>").count(), 4);
+
+        assert!(!svg_for(false).contains("index-badge"));
+    }
+
+    #[test]
+    fn test_layout_cache() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        for window in nodes.windows(2) {
+            graph.add_edge(window[0], window[1], ());
+        }
+
+        let cache = Arc::new(LayoutCache::new());
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .layout(Layout::Random)
+            .layout_cache(Arc::clone(&cache), "path")
+            .build()
+            .expect("Values should be valid.");
+
+        // The random layout is computed once and reused afterwards
+        let first = graph_to_svg_string(&graph, &settings);
+        assert_eq!(cache.len(), 1);
+        let positions = cache.get("path").expect("Positions should be cached");
+        assert_eq!(positions.len(), nodes.len());
+        assert_eq!(graph_to_svg_string(&graph, &settings), first);
+
+        // Replacing the cached positions shows that the layout is not recomputed
+        cache.insert("path", vec![(0.2, 0.4); nodes.len()]);
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert_eq!(
+            svg_output
+                .matches("<circle cx=\"100.0\" cy=\"200.0\"")
+                .count(),
+            nodes.len()
+        );
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_node_stroke_style() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        graph.add_node(());
+
+        let svg_for = |node_stroke_style| {
+            let settings = SettingsBuilder::new()
+                .node_stroke_style(node_stroke_style)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        assert!(
+            svg_for(EdgeStyle::Dashed).contains("stroke-dasharray=\"3.0 1.0\" stroke=\"black\"/>")
+        );
+        assert!(
+            svg_for(EdgeStyle::Dotted).contains("stroke-dasharray=\"1.0 1.0\" stroke=\"black\"/>")
+        );
+        assert!(!svg_for(EdgeStyle::Solid).contains("stroke-dasharray"));
+    }
+
+    #[test]
+    fn test_node_images() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .node_radius(20.0)
+            .position_map(|node_id: NodeIndex| (0.5, node_id.index() as f32))
+            .node_image_fn(|node_id: NodeIndex| {
+                (node_id.index() == 0).then(|| "icons/server.svg?size=2&theme=dark".to_string())
+            })
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // The image is centered at the node and as large as its circle would be
+        assert!(svg_output.contains(
+            "<image x=\"230.0\" y=\"5.0\" width=\"40.0\" height=\"40.0\" \
+             href=\"icons/server.svg?size=2&amp;theme=dark\" />"
+        ));
+        assert_eq!(svg_output.matches("<image").count(), 1);
+        assert_eq!(svg_output.matches("<circle").count(), 1);
+    }
+
+    #[test]
+    fn test_separate_reciprocal_edges() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, a, ());
+
+        let svg_for = |separate_reciprocal_edges| {
+            let settings = SettingsBuilder::new()
+                .width(1000.0)
+                .height(1000.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .position_map(|node_id: NodeIndex| (0.25 + 0.5 * node_id.index() as f32, 0.5))
+                .separate_reciprocal_edges(separate_reciprocal_edges)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+        // Returns the value of the first occurrence of the attribute in the fragment
+        let attribute = |fragment: &str, name: &str| -> f32 {
+            let start = fragment
+                .find(&format!(" {name}=\""))
+                .expect("Attribute should exist")
+                + name.len()
+                + 3;
+            let end = start
+                + fragment[start..]
+                    .find('"')
+                    .expect("Attribute should be closed");
+            fragment[start..end]
+                .parse()
+                .expect("Attribute should be a number")
+        };
+
+        let svg_output = svg_for(true);
+        let lines: Vec<_> = svg_output.split("<line").skip(1).collect();
+        assert_eq!(lines.len(), 2);
+        // Both lines are horizontal, but on opposite sides of the line between the nodes
+        let (y_forward, y_backward) = (attribute(lines[0], "y1"), attribute(lines[1], "y1"));
+        assert_eq!(y_forward, attribute(lines[0], "y2"));
+        assert_eq!(y_backward, attribute(lines[1], "y2"));
+        assert!(y_forward > 500.0 && y_backward < 500.0);
+
+        // The arrowheads are placed at the ends of the lines, that is, at opposite ends
+        assert_eq!(svg_output.matches("<marker id=\"arrowhead-0\"").count(), 1);
+        assert!(lines
+            .iter()
+            .all(|line| line.contains("marker-end=\"url(#arrowhead-0)\"")));
+        assert!(attribute(lines[0], "x2") > 700.0 && attribute(lines[1], "x2") < 300.0);
+
+        // Disabled by default
+        let svg_output = svg_for(false);
+        assert!(!svg_output.contains("marker"));
+        let lines: Vec<_> = svg_output.split("<line").skip(1).collect();
+        assert_eq!(attribute(lines[0], "y1"), 500.0);
+        assert_eq!(attribute(lines[1], "y1"), 500.0);
+    }
+
+    #[test]
+    fn test_arrow_style() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, a, ());
+
+        let marker_for = |arrow_style| {
+            let settings = SettingsBuilder::new()
+                .separate_reciprocal_edges(true)
+                .arrow_style(arrow_style)
+                .build()
+                .expect("Values should be valid.");
+            let svg_output = graph_to_svg_string(&graph, &settings);
+            let start = svg_output
+                .find("<marker")
+                .expect("Marker should be defined");
+            let end = svg_output
+                .find("</marker>")
+                .expect("Marker should be closed");
+            svg_output[start..end].to_string()
+        };
+
+        let filled = marker_for(ArrowStyle::FilledTriangle);
+        let open = marker_for(ArrowStyle::OpenTriangle);
+        assert_ne!(filled, open);
+        assert!(filled.contains("fill=\"black\""));
+        assert!(open.contains("fill=\"none\" stroke=\"black\""));
+        assert_ne!(marker_for(ArrowStyle::Diamond), filled);
+        assert!(marker_for(ArrowStyle::Circle).contains("<circle"));
+    }
+
+    #[test]
+    fn test_arrowhead_colors() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(c, a, ());
+
+        // Two edges share a color, such that two markers are defined
+        let settings = SettingsBuilder::new()
+            .edge_arrow_mode(ArrowMode::End)
+            .edge_coloring_fn(
+                |edge_id: EdgeIndex| if edge_id.index() == 1 { "red" } else { "blue" },
+            )
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert_eq!(svg_output.matches("<marker ").count(), 2);
+        assert!(svg_output.contains("fill=\"blue\"/>\n        </marker>"));
+        assert!(svg_output.contains("fill=\"red\"/>\n        </marker>"));
+        assert!(!svg_output.contains("context-stroke"));
+        let blue = if svg_output.find("fill=\"blue\"") < svg_output.find("fill=\"red\"") {
+            0
+        } else {
+            1
+        };
+        for line in svg_output.split("<line").skip(1) {
+            let marker = if line.contains("stroke=\"blue\"") {
+                blue
+            } else {
+                1 - blue
+            };
+            assert!(line.contains(&format!("marker-end=\"url(#arrowhead-{marker})\"")));
+        }
+    }
+
+    #[test]
+    fn test_edge_arrow_mode() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+
+        let line_for = |edge_arrow_mode| {
+            let settings = SettingsBuilder::new()
+                .width(1000.0)
+                .height(1000.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .position_map(|node_id: NodeIndex| (0.25 + 0.5 * node_id.index() as f32, 0.5))
+                .edge_arrow_mode(edge_arrow_mode)
+                .build()
+                .expect("Values should be valid.");
+            let svg_output = graph_to_svg_string(&graph, &settings);
+            let start = svg_output.find("<line").expect("Edge should be drawn");
+            let end = start
+                + svg_output[start..]
+                    .find("/>")
+                    .expect("Line should be closed");
+            (
+                svg_output.contains("<marker"),
+                svg_output[start..end].to_string(),
+            )
+        };
+
+        let (has_marker, both) = line_for(ArrowMode::Both);
+        assert!(has_marker);
+        assert!(both.contains("marker-start=\"url(#arrowhead-0)\""));
+        assert!(both.contains("marker-end=\"url(#arrowhead-0)\""));
+
+        let (_, start) = line_for(ArrowMode::Start);
+        assert!(start.contains("marker-start") && !start.contains("marker-end"));
+        let (_, end) = line_for(ArrowMode::End);
+        assert!(!end.contains("marker-start") && end.contains("marker-end"));
+
+        // The line is shortened at the ends with arrowheads only
+        let (has_marker, none) = line_for(ArrowMode::None);
+        assert!(!has_marker && !none.contains("marker"));
+        let x2 = |line: &str| {
+            line.split(' ')
+                .find(|attribute| attribute.starts_with("x2="))
+                .map(str::to_string)
+        };
+        assert_eq!(x2(&start), x2(&none));
+        assert_ne!(x2(&end), x2(&none));
+        assert_ne!(x2(&both), x2(&none));
+    }
+
+    #[test]
+    fn test_arrow_tip_touches_target() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+
+        let (radius, stroke_width) = (20.0, 2.0);
+        let settings = SettingsBuilder::new()
+            .width(1000.0)
+            .height(1000.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .node_radius(radius)
+            .stroke_width(stroke_width)
+            .position_map(|node_id: NodeIndex| {
+                if node_id.index() == 0 {
+                    (0.2, 0.2)
+                } else {
+                    (0.8, 0.6)
+                }
+            })
+            .edge_arrow_mode(ArrowMode::End)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // The marker starts at the end of the line and is as long as its width in stroke widths
+        assert!(svg_output.contains(
+            "refX=\"0\" refY=\"5\" markerWidth=\"4.0\" markerHeight=\"4.0\" \
+             markerUnits=\"strokeWidth\""
+        ));
+        let arrowhead_length = 4.0 * stroke_width;
+
+        let start = svg_output.find("<line").expect("Edge should be drawn");
+        let line = &svg_output[start..];
+        let attribute = |name: &str| -> f32 {
+            let value = line
+                .split(&format!(" {name}=\""))
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .expect("Attribute should be present");
+            value.parse().expect("Attribute should be a number")
+        };
+        let (end_x, end_y) = (attribute("x2"), attribute("y2"));
+
+        // The line stops the length of the arrowhead before the circle of the target, such that
+        // the tip of the arrowhead touches the circle instead of overlapping it
+        let (target_x, target_y) = (800.0f32, 600.0f32);
+        let length = ((target_x - 200.0).powi(2) + (target_y - 200.0).powi(2)).sqrt();
+        let (unit_x, unit_y) = ((target_x - 200.0) / length, (target_y - 200.0) / length);
+        let expected_end = (
+            target_x - (radius + arrowhead_length) * unit_x,
+            target_y - (radius + arrowhead_length) * unit_y,
+        );
+        assert!((end_x - expected_end.0).abs() < 1e-3, "{end_x}");
+        assert!((end_y - expected_end.1).abs() < 1e-3, "{end_y}");
+
+        let (tip_x, tip_y) = (
+            end_x + arrowhead_length * unit_x,
+            end_y + arrowhead_length * unit_y,
+        );
+        let tip_distance = ((target_x - tip_x).powi(2) + (target_y - tip_y).powi(2)).sqrt();
+        assert!((tip_distance - radius).abs() < 1e-3, "{tip_distance}");
+    }
+
+    #[test]
+    fn test_edge_curvature() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        let curved = graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+
+        let settings = SettingsBuilder::new()
+            .width(1000.0)
+            .height(1000.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .position_map(|node_id: NodeIndex| (0.25 * (node_id.index() + 1) as f32, 0.5))
+            .edge_curvature_fn(move |edge_id| if edge_id == curved { 0.25 } else { 0.0 })
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // The curved edge is drawn as a path bending downwards, i.e. to the right of its direction,
+        // while the other edge stays a straight line
+        assert_eq!(svg_output.matches("<path").count(), 1);
+        assert_eq!(svg_output.matches("<line").count(), 1);
+        assert!(svg_output.contains(" Q 375.0 562.5, "));
+    }
+
+    #[test]
+    fn test_node_shadow() {
+        let (graph, _) = position_map_test_case();
+        let svg_for = |node_shadow| {
+            let settings = SettingsBuilder::new()
+                .node_radius(20.0)
+                .node_shadow(node_shadow)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        let svg_output = svg_for(true);
+        assert_eq!(svg_output.matches("<filter id=\"node-shadow\"").count(), 1);
+        assert!(svg_output.contains("<feGaussianBlur in=\"SourceAlpha\" stdDeviation=\"2.0\"/>"));
+        assert!(svg_output.contains("<feOffset dx=\"2.0\" dy=\"2.0\"/>"));
+        assert_eq!(
+            svg_output.matches("filter=\"url(#node-shadow)\"").count(),
+            4
+        );
+
+        // Disabled by default
+        let svg_output = svg_for(false);
+        assert!(!svg_output.contains("<filter") && !svg_output.contains("filter=\""));
+    }
+
+    #[test]
+    fn test_draw_debug_grid() {
+        let (graph, _) = position_map_test_case();
+        let svg_for = |draw_debug_grid| {
+            let settings = SettingsBuilder::new()
+                .width(100.0)
+                .height(100.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .draw_debug_grid(draw_debug_grid)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        let svg_output = svg_for(true);
+        // Eleven vertical and eleven horizontal gridlines and the four edges of the graph
+        assert_eq!(svg_output.matches("<line ").count(), 22 + 4);
+        assert!(svg_output.contains("<line x1=\"50.0\" y1=\"0.0\" x2=\"50.0\" y2=\"100.0\"/>"));
+        assert!(svg_output.contains("<line x1=\"0.0\" y1=\"50.0\" x2=\"100.0\" y2=\"50.0\"/>"));
+        assert!(svg_output.contains(">0.3</text>"));
+        // The grid is drawn beneath the nodes and edges
+        assert!(svg_output.find("stroke=\"lightgray\"") < svg_output.find("<circle"));
+
+        let svg_output = svg_for(false);
+        assert_eq!(svg_output.matches("<line ").count(), 4);
+    }
+
+    #[test]
+    fn test_embed_metadata() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, c, ());
+
+        let settings = SettingsBuilder::new()
+            .layout(Layout::Spiral)
+            .embed_metadata(true)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        let mut lines = svg_output.lines();
+        assert!(matches!(lines.next(), Some(line) if line.starts_with("<svg ")));
+        let comment = lines
+            .next()
+            .expect("The comment should follow the <svg> tag");
+        assert!(comment.starts_with("<!-- Generated by visgraph "));
+        assert!(comment.ends_with(" -->"));
+        assert!(comment.contains("layout: Spiral"));
+        assert!(comment.contains("nodes: 3 | edges: 2"));
+        assert!(comment.contains("timestamp: "));
+
+        // The automatic layout is reported as the layout it resolves to, here a path is a tree
+        let settings = SettingsBuilder::new()
+            .layout(Layout::Auto)
+            .embed_metadata(true)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output.contains("layout: Hierarchical"));
+
+        // Disabled by default
+        let settings = SettingsBuilder::new()
+            .layout(Layout::Spiral)
+            .build()
+            .expect("Values should be valid.");
+        assert!(!graph_to_svg_string(&graph, &settings).contains("<!--"));
+    }
+
+    #[test]
+    fn test_extra_defs() {
+        let (graph, _) = position_map_test_case();
+        let pattern = "<pattern id=\"stripes\" width=\"4\" height=\"4\" \
+                       patternUnits=\"userSpaceOnUse\"><rect width=\"2\" height=\"4\" \
+                       fill=\"black\"/></pattern>";
+        let settings = SettingsBuilder::new()
+            .extra_defs(pattern.to_string())
+            .node_coloring_fn(|_| "url(#stripes)")
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        let mut lines = svg_output.lines();
+        assert!(matches!(lines.next(), Some(line) if line.starts_with("<svg ")));
+        assert_eq!(
+            lines.next(),
+            Some(format!("    <defs>{pattern}</defs>").as_str())
+        );
+        assert_eq!(svg_output.matches("fill=\"url(#stripes)\"").count(), 4);
+
+        // The metadata comment stays right after the opening <svg> tag
+        let settings = SettingsBuilder::new()
+            .extra_defs(pattern.to_string())
+            .embed_metadata(true)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        let mut lines = svg_output.lines().skip(1);
+        assert!(matches!(lines.next(), Some(line) if line.starts_with("<!-- Generated by")));
+        assert_eq!(
+            lines.next(),
+            Some(format!("    <defs>{pattern}</defs>").as_str())
+        );
+
+        // References to the definitions are valid colors
+        let settings = SettingsBuilder::new()
+            .extra_defs(pattern.to_string())
+            .node_coloring_fn(|_| "url(#stripes)")
+            .validate_colors(true)
+            .build()
+            .expect("Values should be valid.");
+        assert!(graph_to_svg(&graph, &settings, "target/test_output/extra_defs.svg").is_ok());
+
+        // No <defs> tag is added by default
+        let settings = SettingsBuilder::new()
+            .build()
+            .expect("Values should be valid.");
+        assert!(!graph_to_svg_string(&graph, &settings).contains("<defs>"));
+    }
+
+    #[test]
+    fn test_positions_from_map() {
+        let (graph, _) = position_map_test_case();
+        // The last node has no stored position and is placed at the center
+        let positions = HashMap::from([
+            (NodeIndex::new(0), (0.25, 0.25)),
+            (NodeIndex::new(1), (0.75, 0.25)),
+            (NodeIndex::new(2), (0.75, 0.75)),
+        ]);
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .position_map(positions_from_map(positions))
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert!(svg_output.contains("<circle cx=\"137.5\" cy=\"137.5\""));
+        assert!(svg_output.contains("<circle cx=\"362.5\" cy=\"137.5\""));
+        assert!(svg_output.contains("<circle cx=\"362.5\" cy=\"362.5\""));
+        assert!(svg_output.contains("<circle cx=\"250.0\" cy=\"250.0\""));
+    }
+
+    #[test]
+    fn test_units() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .width(210.0)
+            .height(297.0)
+            .units(Units::Mm)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output
+            .starts_with("<svg width=\"210.0mm\" height=\"297.0mm\" viewBox=\"0 0 210.0 297.0\""));
+
+        // Pixels are written without a suffix and without a viewBox
+        let settings = SettingsBuilder::new()
+            .width(210.0)
+            .height(297.0)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output.starts_with("<svg width=\"210.0\" height=\"297.0\" xmlns"));
+
+        assert_eq!(Units::Mm.to_pixels(25.4, 96.0), 96.0);
+        assert_eq!(Units::Pt.to_pixels(72.0, 96.0), 96.0);
+        assert_eq!(Units::Px.to_pixels(42.0, 96.0), 42.0);
+    }
+
+    #[test]
+    fn test_xml_declaration() {
+        let (graph, _) = position_map_test_case();
+        let settings_for = |xml_declaration| {
+            SettingsBuilder::new()
+                .xml_declaration(xml_declaration)
+                .build()
+                .expect("Values should be valid.")
+        };
+
+        let declaration = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg ";
+        let settings = settings_for(true);
+        assert!(graph_to_svg_string(&graph, &settings).starts_with(declaration));
+        let cropped = graph_to_svg_string_cropped(&graph, &settings, 5.0);
+        assert!(cropped.starts_with(declaration));
+        assert_eq!(cropped.matches("<svg ").count(), 1);
+
+        // Disabled by default
+        let settings = settings_for(false);
+        assert!(graph_to_svg_string(&graph, &settings).starts_with("<svg "));
+    }
+
+    #[test]
+    fn test_snap_to_grid() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..8).map(|_| graph.add_node(())).collect();
+        for window in nodes.windows(2) {
+            graph.add_edge(window[0], window[1], ());
+        }
+        graph.add_edge(nodes[0], nodes[4], ());
+        let settings = SettingsBuilder::new()
+            .width(1000.0)
+            .height(1000.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .layout(Layout::ForceDirected)
+            .seed(Some(42))
+            .snap_to_grid(Some(4))
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // With a 1000x1000 canvas and no margins, multiples of 1/4 are drawn at multiples of 250
+        let coordinates: Vec<f32> = svg_output
+            .split(['"', ' '])
+            .collect::<Vec<_>>()
+            .windows(2)
+            .filter(|window| window[0] == "cx=" || window[0] == "cy=")
+            .map(|window| window[1].parse().expect("Coordinates should be numbers"))
+            .collect();
+        assert_eq!(coordinates.len(), 2 * graph.node_count());
+        for coordinate in coordinates {
+            assert_eq!(coordinate % 250.0, 0.0, "{coordinate} is not on the grid");
+        }
+
+        assert_eq!(
+            SettingsBuilder::new().snap_to_grid(Some(0)).build().err(),
+            Some(InvalidSettingsError::GridDivisions(0))
+        );
+    }
+
+    #[test]
+    fn test_y_axis_up() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        graph.add_node(());
+        graph.add_node(());
+
+        let svg_for = |y_axis| {
+            let settings = SettingsBuilder::new()
+                .width(500.0)
+                .height(500.0)
+                .margin_x(0.1)
+                .margin_y(0.1)
+                .position_map(|node_id: NodeIndex| (0.5, node_id.index() as f32))
+                .y_axis(y_axis)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        // The node at y = 0.0 is drawn at the bottom of the drawing area
+        let svg_output = svg_for(YAxis::Up);
+        assert!(svg_output.contains("<circle cx=\"250.0\" cy=\"450.0\""));
+        assert!(svg_output.find("cy=\"450.0\"") < svg_output.find("cy=\"50.0\""));
+
+        let svg_output = svg_for(YAxis::Down);
+        assert!(svg_output.find("cy=\"50.0\"") < svg_output.find("cy=\"450.0\""));
+    }
+
+    #[test]
+    fn test_scale() {
+        let (scaled_x, scaled_y) = super::scale((0.5, 0.5), 0.1, 0.1, 1000.0, 1000.0, YAxis::Down);
+        assert!((scaled_x - 500.0).abs() < f32::EPSILON);
+        assert!((scaled_y - 500.0).abs() < f32::EPSILON);
+
+        let (scaled_x, scaled_y) = super::scale((0.0, 0.0), 0.1, 0.1, 1000.0, 1000.0, YAxis::Down);
+        assert!((scaled_x - 100.0).abs() < f32::EPSILON);
+        assert!((scaled_y - 100.0).abs() < f32::EPSILON);
+
+        let (scaled_x, scaled_y) = super::scale((1.0, 1.0), 0.1, 0.1, 1000.0, 1000.0, YAxis::Down);
+        assert!((scaled_x - 900.0).abs() < f32::EPSILON);
+        assert!((scaled_y - 900.0).abs() < f32::EPSILON);
+
+        let (scaled_x, scaled_y) = super::scale((0.0, 0.0), 0.1, 0.1, 1000.0, 1000.0, YAxis::Up);
+        assert!((scaled_x - 100.0).abs() < f32::EPSILON);
+        assert!((scaled_y - 900.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_normalize_positions_clamp() {
+        let svg_output = out_of_range_svg(NormalizeMode::Clamp);
+
+        assert!(svg_output.contains("<circle cx=\"25.0\" cy=\"250.0\""));
+        assert!(svg_output.contains("<circle cx=\"475.0\" cy=\"250.0\""));
+        assert_eq!(svg_output.matches("<circle cx=\"25.0\"").count(), 2);
+    }
+
+    #[test]
+    fn test_normalize_positions_fit() {
+        let svg_output = out_of_range_svg(NormalizeMode::Fit);
+
+        assert!(svg_output.contains("<circle cx=\"25.0\" cy=\"250.0\""));
+        assert!(svg_output.contains("<circle cx=\"137.5\" cy=\"250.0\""));
+        assert!(svg_output.contains("<circle cx=\"475.0\" cy=\"250.0\""));
+    }
+
+    #[test]
+    fn test_legend() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .legend(vec![
+                ("red".to_string(), "Important".to_string()),
+                ("#00FF00".to_string(), "Unimportant".to_string()),
+            ])
+            .legend_corner(Corner::BottomLeft)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // Legend box and one swatch per entry
+        assert_eq!(svg_output.matches("<rect ").count(), 3);
+        assert!(svg_output.contains("fill=\"red\" stroke=\"black\"/>"));
+        assert!(svg_output.contains("fill=\"#00FF00\" stroke=\"black\"/>"));
+        assert!(svg_output.contains(">Important</text>"));
+        assert!(svg_output.contains(">Unimportant</text>"));
+        // The legend box is placed half a font size away from the bottom left corner
+        assert!(svg_output.contains("<rect x=\"8.0\" y=\"428.0\""));
+
+        // Special characters in the labels and colors are escaped
+        let settings = SettingsBuilder::new()
+            .legend(vec![("\"red".to_string(), "Cats & <Dogs>".to_string())])
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output.contains("fill=\"&quot;red\" stroke=\"black\"/>"));
+        assert!(svg_output.contains(">Cats &amp; &lt;Dogs></text>"));
+    }
+
+    #[test]
+    fn test_title() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(548.0)
+            .margin_y(0.0)
+            .position_map(|_| (0.5, 0.0))
+            .title(Some("A square".to_string()))
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert!(svg_output.contains(
+            "<text x=\"250.0\" y=\"24.0\" font-size=\"24.0px\" font-family=\"DejaVu Sans, \
+             sans-serif\" fill=\"black\" text-anchor=\"middle\" \
+             dominant-baseline=\"central\">A square</text>"
+        ));
+        // The drawing area starts below the title
+        assert!(svg_output.contains("<circle cx=\"250.0\" cy=\"48.0\""));
+
+        // Special characters in the title are escaped
+        let settings = SettingsBuilder::new()
+            .title(Some("x < y & z".to_string()))
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output.contains(">x &lt; y &amp; z</text>"));
+
+        // The title has to leave space for the graph
+        let result = SettingsBuilder::new()
+            .height(40.0)
+            .title(Some("A square".to_string()))
+            .build();
+        assert_eq!(
+            result.err(),
+            Some(InvalidSettingsError::TitleHeight(48.0, 40.0))
+        );
+    }
+
+    #[test]
+    fn test_disable_labels() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32 / 4.0, 0.5))
+            .draw_node_labels(false)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // Only the labels of the four edges remain
+        assert_eq!(svg_output.matches("<circle").count(), 4);
+        assert_eq!(svg_output.matches("<text").count(), 4);
+        assert!(!svg_output.contains("fill=\"black\" text-anchor"));
+
+        let settings = SettingsBuilder::new()
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32 / 4.0, 0.5))
+            .draw_node_labels(false)
+            .draw_edge_labels(false)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert_eq!(svg_output.matches("<line").count(), 4);
+        assert_eq!(svg_output.matches("<text").count(), 0);
+    }
+
+    #[test]
+    fn test_node_opacity() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32 / 4.0, 0.5))
+            .node_opacity_fn(|node_id: NodeIndex| if node_id.index() == 0 { 0.5 } else { 1.0 })
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert!(svg_output.contains("fill=\"white\" fill-opacity=\"0.5\" stroke=\"black\"/>"));
+        assert_eq!(svg_output.matches("fill-opacity").count(), 1);
+    }
+
+    #[test]
+    fn test_graph_to_svg_stream() {
+        /// Writer which discards the data, but records the total and the largest number of bytes
+        /// written at once.
+        #[derive(Default)]
+        struct CountingSink {
+            total: usize,
+            largest_write: usize,
+            flushes: usize,
+        }
+
+        impl std::io::Write for CountingSink {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.total += buf.len();
+                self.largest_write = self.largest_write.max(buf.len());
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.flushes += 1;
+                Ok(())
+            }
+        }
+
+        let size = 100;
+        let mut graph = UnGraph::<(), ()>::with_capacity(size * size, 2 * size * size);
+        let nodes: Vec<_> = (0..size * size).map(|_| graph.add_node(())).collect();
+        for row in 0..size {
+            for col in 0..size {
+                if col + 1 < size {
+                    graph.add_edge(nodes[row * size + col], nodes[row * size + col + 1], ());
+                }
+                if row + 1 < size {
+                    graph.add_edge(nodes[row * size + col], nodes[(row + 1) * size + col], ());
+                }
+            }
+        }
+        let settings = SettingsBuilder::new()
+            .position_map(move |node_id: NodeIndex| {
+                let (row, col) = (node_id.index() / size, node_id.index() % size);
+                (col as f32 / size as f32, row as f32 / size as f32)
+            })
+            .build()
+            .expect("Values should be valid.");
+
+        let mut sink = CountingSink::default();
+        graph_to_svg_stream(&graph, &settings, &mut sink).expect("Writing should succeed.");
+
+        // The document is large, but is written in small fragments
+        assert!(sink.total > 1_000_000, "{} bytes written", sink.total);
+        assert!(
+            sink.largest_write < 1_000,
+            "{} bytes written at once",
+            sink.largest_write
+        );
+        assert!(sink.flushes > 1);
+        assert_eq!(sink.total, graph_to_svg_string(&graph, &settings).len());
+    }
+
+    #[test]
+    fn test_graph_to_svg_string_cropped() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let node_a = graph.add_node(());
+        let node_b = graph.add_node(());
+        graph.add_edge(node_a, node_b, ());
+
+        let builder = || {
+            SettingsBuilder::new()
+                .width(500.0)
+                .height(500.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .node_radius(25.0)
+                .position_map(|node_id: NodeIndex| (0.25 + 0.5 * node_id.index() as f32, 0.5))
+        };
+
+        // The nodes are centered at (125, 250) and (375, 250), so the circles span from 100 to 400
+        // horizontally and from 225 to 275 vertically
+        let settings = builder()
+            .draw_node_labels(false)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string_cropped(&graph, &settings, 5.0);
+        assert!(svg_output.starts_with(
+            "<svg width=\"310.0\" height=\"60.0\" viewBox=\"95.0 220.0 310.0 60.0\" \
+             xmlns=\"http://www.w3.org/2000/svg\">\n"
+        ));
+        assert_eq!(svg_output.matches("<circle ").count(), 2);
+        assert_eq!(svg_output.matches("<line ").count(), 1);
+
+        // Labels wider than the nodes extend the box: 20 characters at 0.6 times the font size
+        // of 10 pixels are 120 pixels wide, i.e. 60 pixels on each side of the node center
+        let settings = builder()
+            .font_size(10.0)
+            .node_label_fn(|_| "A label wider than r".to_string())
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string_cropped(&graph, &settings, 0.0);
+        assert!(svg_output.starts_with(
+            "<svg width=\"370.0\" height=\"50.0\" viewBox=\"65.0 225.0 370.0 50.0\" "
+        ));
+
+        // The background covers the cropped canvas
+        let settings = builder()
+            .draw_node_labels(false)
+            .background_color(Some("white".to_string()))
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string_cropped(&graph, &settings, 5.0);
+        assert!(svg_output.contains(
+            "<rect x=\"95.0\" y=\"220.0\" width=\"310.0\" height=\"60.0\" fill=\"white\"/>"
+        ));
+        assert_eq!(svg_output.matches("<rect ").count(), 1);
+
+        // The XML declaration and the definitions precede the cropped <svg> tag once
+        let settings = builder()
+            .draw_node_labels(false)
+            .xml_declaration(true)
+            .extra_defs("<style/>".to_string())
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string_cropped(&graph, &settings, 5.0);
+        assert!(svg_output.starts_with(&format!(
+            "{XML_DECLARATION}<svg width=\"310.0\" height=\"60.0\" viewBox=\"95.0 220.0 310.0 \
+             60.0\" xmlns=\"http://www.w3.org/2000/svg\">\n    <defs><style/></defs>\n"
+        )));
+        assert_eq!(svg_output.matches("<svg ").count(), 1);
+    }
+
+    #[test]
+    fn test_smooth_tree_edges() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let root = graph.add_node(());
+        for _ in 0..2 {
+            let child = graph.add_node(());
+            graph.add_edge(root, child, ());
+        }
+
+        let svg_for = |layout, smooth_tree_edges| {
+            let settings = SettingsBuilder::new()
+                .width(500.0)
+                .height(500.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .node_radius(10.0)
+                .layout(layout)
+                .smooth_tree_edges(smooth_tree_edges)
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        let svg_output = svg_for(Layout::Hierarchical(Orientation::TopToBottom), true);
+        assert_eq!(svg_output.matches("<path ").count(), 2);
+        assert!(!svg_output.contains("<line "));
+        // The edge to the first child leaves the root downwards and bends halfway between the
+        // levels
+        assert!(svg_output.contains("d=\"M 250.0 10.0 C 250.0 250.0, 0.0 250.0, 0.0 490.0\""));
+
+        // Straight edges are drawn without the option or for other layouts
+        let svg_output = svg_for(Layout::Hierarchical(Orientation::TopToBottom), false);
+        assert_eq!(svg_output.matches("<line ").count(), 2);
+        let svg_output = svg_for(Layout::Circular, true);
+        assert!(!svg_output.contains("<path "));
+    }
+
+    #[test]
+    fn test_orthogonal_edge_routing() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let root = graph.add_node(());
+        for _ in 0..3 {
+            let child = graph.add_node(());
+            graph.add_edge(root, child, ());
+        }
+
+        let svg_for = |layout, edge_routing| {
+            let settings = SettingsBuilder::new()
+                .width(500.0)
+                .height(500.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .node_radius(10.0)
+                .layout(layout)
+                .smooth_tree_edges(true)
+                .edge_routing(edge_routing)
+                .edge_label_fn(|_| "edge".to_string())
+                .build()
+                .expect("Values should be valid.");
+            graph_to_svg_string(&graph, &settings)
+        };
+
+        for layout in [
+            Layout::Hierarchical(Orientation::TopToBottom),
+            Layout::Hierarchical(Orientation::LeftToRight),
+            Layout::Circular,
+        ] {
+            let svg_output = svg_for(layout, EdgeRouting::Orthogonal);
+            assert!(!svg_output.contains("<line "));
+            let paths: Vec<Vec<(f32, f32)>> = svg_output
+                .split("d=\"M ")
+                .skip(1)
+                .map(|path| {
+                    path[..path.find('"').expect("Path should end")]
+                        .split(" L ")
+                        .map(|point| {
+                            let (x, y) = point
+                                .split_once(' ')
+                                .expect("Point should have two coordinates");
+                            (
+                                x.parse().expect("x should be a number"),
+                                y.parse().expect("y should be a number"),
+                            )
+                        })
+                        .collect()
+                })
+                .collect();
+            assert_eq!(paths.len(), 3);
+            for path in paths {
+                assert_eq!(path.len(), 4);
+                for segment in path.windows(2) {
+                    let ((x_0, y_0), (x_1, y_1)) = (segment[0], segment[1]);
+                    assert!(
+                        (x_0 - x_1).abs() < 1e-3 || (y_0 - y_1).abs() < 1e-3,
+                        "{segment:?} is neither horizontal nor vertical"
+                    );
+                }
+            }
+        }
+
+        // The route to the first child leaves the root downwards and bends halfway between the
+        // levels
+        let svg_output = svg_for(
+            Layout::Hierarchical(Orientation::TopToBottom),
+            EdgeRouting::Orthogonal,
+        );
+        assert!(svg_output.contains("d=\"M 250.0 10.0 L 250.0 250.0 L 0.0 250.0 L 0.0 490.0\""));
+        // The label of the edge to the middle child lies on the route
+        assert!(svg_output.contains("<text x= \"250.0\" y=\"250.0\""));
+
+        let svg_output = svg_for(
+            Layout::Hierarchical(Orientation::TopToBottom),
+            EdgeRouting::Straight,
+        );
+        assert!(svg_output.contains(" C "));
+    }
+
+    #[test]
+    fn test_min_edge_length() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let node_a = graph.add_node(());
+        let node_b = graph.add_node(());
+        graph.add_edge(node_a, node_b, ());
+        // The nodes are about 0.0005 pixels apart
+        let position_map = |node_id: NodeIndex| (0.5 + node_id.index() as f32 * 5e-7, 0.5);
+
+        let default_settings = SettingsBuilder::new()
+            .position_map(position_map)
+            .build()
+            .expect("Values should be valid.");
+        assert!(!graph_to_svg_string(&graph, &default_settings).contains("<line "));
+
+        let settings = SettingsBuilder::new()
+            .position_map(position_map)
+            .min_edge_length(0.0001)
+            .build()
+            .expect("Values should be valid.");
+        assert!(graph_to_svg_string(&graph, &settings).contains("<line "));
+    }
+
+    #[test]
+    fn test_typed_edge_colors() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32 / 4.0, 0.5))
+            .edge_coloring_fn(|edge_id: EdgeIndex| match edge_id.index() {
+                0 => Color::rgb(255, 0, 0),
+                _ => Color::from("blue"),
+            })
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert!(svg_output.contains("stroke=\"#ff0000\""));
+        assert_eq!(svg_output.matches("stroke=\"blue\"").count(), 3);
+    }
+
+    #[test]
+    fn test_gradient_fill() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32 / 4.0, 0.5))
+            .node_coloring_fn(|node_id: NodeIndex| match node_id.index() {
+                0 => NodeFill::from("red"),
+                _ => NodeFill::RadialGradient {
+                    inner: "white".to_string(),
+                    outer: "blue".to_string(),
+                },
+            })
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // The gradient shared by three nodes is only defined once
+        assert_eq!(svg_output.matches("<radialGradient").count(), 1);
+        assert!(svg_output.contains(
+            "<radialGradient id=\"node-gradient-0\">
+            <stop offset=\"0%\" stop-color=\"white\"/>
+            <stop offset=\"100%\" stop-color=\"blue\"/>"
+        ));
+        assert_eq!(
+            svg_output.matches("fill=\"url(#node-gradient-0)\"").count(),
+            3
+        );
+        assert_eq!(svg_output.matches("fill=\"red\"").count(), 1);
+    }
+
+    #[test]
+    fn test_responsive() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .width(800.0)
+            .height(600.0)
+            .responsive(true)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert!(svg_output.starts_with(
+            "<svg width=\"100%\" height=\"100%\" viewBox=\"0 0 800.0 600.0\" \
+             xmlns=\"http://www.w3.org/2000/svg\">"
+        ));
+    }
+
+    #[test]
+    fn test_svg_elements() {
+        let (graph, settings) = position_map_test_case();
+        let elements = graph_to_svg_elements(&graph, &settings);
+
+        assert_eq!(elements.nodes.len(), graph.node_count());
+        assert_eq!(elements.edges.len(), graph.edge_count());
+        assert!(elements.nodes.iter().all(|node| node.contains("<circle")));
+        assert!(elements.edges.iter().all(|edge| edge.contains("<line")));
+        assert_eq!(elements.assemble(), graph_to_svg_string(&graph, &settings));
+    }
+
+    #[test]
+    fn test_svg_elements_directed() {
+        let (graph, settings) = position_map_test_case();
+        assert!(!graph_to_svg_elements(&graph, &settings).directed);
+
+        let mut directed_graph = DiGraph::<(), ()>::new();
+        let source = directed_graph.add_node(());
+        let target = directed_graph.add_node(());
+        directed_graph.add_edge(source, target, ());
+        assert!(graph_to_svg_elements(&directed_graph, &settings).directed);
+    }
+
+    #[test]
+    fn test_validate_colors() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .node_coloring_fn(|node_id: NodeIndex| match node_id.index() {
+                2 => "reed",
+                _ => "#FF0000",
+            })
+            .validate_colors(true)
+            .build()
+            .expect("Values should be valid.");
+
+        let result = graph_to_svg(&graph, &settings, "target/test_output/invalid_color.svg");
+        assert!(matches!(
+            result,
+            Err(VisGraphError::InvalidColor {
+                node_or_edge: GraphElement::Node(node),
+                value,
+            }) if node == NodeIndex::new(2) && value == "reed"
+        ));
+        assert!(matches!(
+            try_graph_to_svg_string(&graph, &settings),
+            Err(VisGraphError::InvalidColor { value, .. }) if value == "reed"
+        ));
+
+        // Valid colors result in the same SVG data as without validation
+        let settings = SettingsBuilder::new()
+            .validate_colors(true)
+            .build()
+            .expect("Values should be valid.");
+        assert_eq!(
+            try_graph_to_svg_string(&graph, &settings).expect("Colors should be valid."),
+            graph_to_svg_string(&graph, &settings)
+        );
+
+        // The colors are validated while drawing, such that each coloring function is called once
+        // per node and edge
+        let node_calls = AtomicUsize::new(0);
+        let settings = SettingsBuilder::new()
+            .node_coloring_fn(|_| {
+                node_calls.fetch_add(1, Ordering::Relaxed);
+                "red"
+            })
+            .edge_coloring_fn(|edge_id: EdgeIndex| match edge_id.index() {
+                1 => "bleu",
+                _ => "blue",
+            })
+            .validate_colors(true)
+            .build()
+            .expect("Values should be valid.");
+        let result = graph_to_svg_stream(&graph, &settings, Vec::new());
+        assert!(matches!(
+            result,
+            Err(VisGraphError::InvalidColor {
+                node_or_edge: GraphElement::Edge(edge),
+                value,
+            }) if edge == EdgeIndex::new(1) && value == "bleu"
+        ));
+        assert_eq!(node_calls.load(Ordering::Relaxed), graph.node_count());
+    }
+
+    #[test]
+    fn test_highlight() {
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32 / 4.0, 0.5))
+            .highlighted_nodes(HashSet::from([NodeIndex::new(1)]))
+            .highlighted_edges(HashSet::from([EdgeIndex::new(0)]))
+            .highlight_color("orange".to_string())
+            .highlight_stroke_width(7.0)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        assert_eq!(
+            svg_output
+                .matches("stroke=\"orange\" stroke-width=\"7.0\"")
+                .count(),
+            1
+        );
+        assert_eq!(
+            svg_output
+                .matches("stroke=\"black\" stroke-width=\"5.0\"")
+                .count(),
+            3
+        );
+        assert_eq!(svg_output.matches("fill=\"orange\"").count(), 1);
+    }
+
+    #[test]
+    fn test_edge_label_position() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let source = graph.add_node(());
+        let target = graph.add_node(());
+        graph.add_edge(source, target, ());
+
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .position_map(|node_id: NodeIndex| (node_id.index() as f32, 0.5))
+            .edge_label_fn(|_| "label".to_string())
+            .edge_label_position(0.25)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // The edge runs from x = 25.0 to x = 475.0 after trimming by the radius
+        assert!(svg_output.contains("<text x= \"137.5\" y=\"250.0\""));
+    }
+
+    #[test]
+    fn test_rotate_edge_labels() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let source = graph.add_node(());
+        let target = graph.add_node(());
+        graph.add_edge(source, target, ());
+
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .position_map(|node_id: NodeIndex| (0.5, 1.0 - node_id.index() as f32))
+            .edge_label_fn(|_| "label".to_string())
+            .rotate_edge_labels(true)
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        // The edge points upwards, the label is flipped to point downwards instead
+        assert!(svg_output.contains(
+            "<text x= \"250.0\" y=\"250.0\" font-size=\"16.0px\" font-family=\"DejaVu Sans, \
+             sans-serif\" fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\" \
+             transform=\"rotate(90.0 250.0 250.0)\">label</text>"
+        ));
+    }
+
+    #[test]
+    fn test_emit_element_ids() {
+        let (graph, settings) = position_map_test_case();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(!svg_output.contains("id=\"node-"));
+        assert!(!svg_output.contains("id=\"edge-"));
+
+        let settings = SettingsBuilder::new()
+            .emit_element_ids(true)
+            .build()
+            .unwrap();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        for index in 0..graph.node_count() {
+            assert!(svg_output.contains(&format!("<circle id=\"node-{index}\" cx=")));
+        }
+        for index in 0..graph.edge_count() {
+            assert!(svg_output.contains(&format!("<line id=\"edge-{index}\" x1=")));
+        }
+    }
+
+    #[test]
+    fn test_emit_css_classes() {
+        let (graph, _) = position_map_test_case();
+
+        let settings = SettingsBuilder::new()
+            .node_class_fn(|node_id: NodeIndex| {
+                if node_id.index() == 0 {
+                    "root".to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .edge_class_fn(|edge_id: EdgeIndex| format!("weight-{}", edge_id.index()))
+            .build()
+            .unwrap();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(!svg_output.contains("class="));
+
+        let settings = SettingsBuilder::new()
+            .emit_css_classes(true)
+            .node_class_fn(|node_id: NodeIndex| {
+                if node_id.index() == 0 {
+                    "root".to_string()
+                } else {
+                    String::new()
+                }
+            })
+            .edge_class_fn(|edge_id: EdgeIndex| format!("weight-{}", edge_id.index()))
+            .build()
+            .unwrap();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output.contains("<circle class=\"node root\" cx="));
+        assert_eq!(svg_output.matches("<circle class=\"node\" cx=").count(), 3);
+        assert!(svg_output.contains("<line class=\"edge weight-0\" x1="));
+        assert_eq!(svg_output.matches("class=\"edge ").count(), 4);
+    }
+
+    #[test]
+    fn test_node_draw_order() {
+        let (graph, _) = position_map_test_case();
+
+        // Index 7 does not exist and the repeated index 2 is ignored
+        let settings = SettingsBuilder::new()
+            .emit_element_ids(true)
+            .node_draw_order(Some(vec![
+                NodeIndex::new(2),
+                NodeIndex::new(7),
+                NodeIndex::new(0),
+                NodeIndex::new(2),
+            ]))
+            .build()
+            .unwrap();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        let drawn_nodes: Vec<_> = svg_output
+            .match_indices("<circle id=\"node-")
+            .map(|(start, pattern)| &svg_output[start + pattern.len()..start + pattern.len() + 1])
+            .collect();
+        assert_eq!(drawn_nodes, ["2", "0", "1", "3"]);
+    }
+
+    #[test]
+    fn test_node_z_fn() {
+        let (graph, _) = position_map_test_case();
+
+        // Node 0 is drawn on top, nodes of the same z-order keep their index order
+        let settings = SettingsBuilder::new()
+            .emit_element_ids(true)
+            .node_z_fn(|node_id: NodeIndex| match node_id.index() {
+                0 => 2,
+                3 => -1,
+                _ => 0,
+            })
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        let position = |node: &str| {
+            svg_output
+                .find(&format!("<circle id=\"{node}\""))
+                .expect("Node should be drawn")
+        };
+        assert!(position("node-0") > position("node-2"));
+        assert!(position("node-1") < position("node-2"));
+        assert!(position("node-3") < position("node-1"));
+    }
+
+    #[test]
+    fn test_rich_labels() {
+        assert_eq!(
+            rich_label_markup("x_1"),
+            "x<tspan baseline-shift=\"sub\" font-size=\"70%\">1</tspan>"
+        );
+        assert_eq!(
+            rich_label_markup("e^{i+1} b_"),
+            "e<tspan baseline-shift=\"super\" font-size=\"70%\">i+1</tspan> b_"
+        );
+        assert!(matches!(rich_label_markup("x1"), Cow::Borrowed("x1")));
+
+        let (graph, _) = position_map_test_case();
+        let label_fn = |node_id: NodeIndex| format!("x_{}", node_id.index());
+        let settings = SettingsBuilder::new()
+            .node_label_fn(label_fn)
+            .build()
+            .unwrap();
+        assert!(!graph_to_svg_string(&graph, &settings).contains("<tspan"));
+
+        let settings = SettingsBuilder::new()
+            .node_label_fn(label_fn)
+            .rich_labels(true)
+            .build()
+            .unwrap();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert!(svg_output
+            .contains(">x<tspan baseline-shift=\"sub\" font-size=\"70%\">1</tspan></text>"));
+    }
+
+    #[test]
+    fn test_label_max_chars() {
+        let label = "ABCDEFGHIJKLMNOPQRST";
+        assert_eq!(truncate_label(label.to_string(), Some(8)), "ABCDEFG…");
+        assert_eq!(truncate_label(label.to_string(), Some(20)), label);
+        assert_eq!(truncate_label(label.to_string(), None), label);
+
+        let (graph, _) = position_map_test_case();
+        let settings = SettingsBuilder::new()
+            .node_label_fn(|_| label.to_string())
+            .edge_label_fn(|_| label.to_string())
+            .label_max_chars(Some(8))
+            .build()
+            .expect("Values should be valid.");
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert_eq!(svg_output.matches(">ABCDEFG…</text>").count(), 8);
+        assert!(!svg_output.contains(label));
+
+        let result = SettingsBuilder::new().label_max_chars(Some(0)).build();
+        assert_eq!(result.err(), Some(InvalidSettingsError::LabelMaxChars(0)));
+    }
+
+    #[test]
+    fn test_bundle_parallel_edges() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let source = graph.add_node(());
+        let target = graph.add_node(());
+        for _ in 0..3 {
+            graph.add_edge(source, target, ());
+        }
+        graph.add_edge(target, source, ());
+
+        let settings = SettingsBuilder::new()
+            .edge_label_fn(|_| "label".to_string())
+            .build()
+            .unwrap();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+        assert_eq!(svg_output.matches("<line ").count(), 4);
+
+        let settings = SettingsBuilder::new()
+            .edge_label_fn(|_| "label".to_string())
+            .bundle_parallel_edges(true)
+            .build()
+            .unwrap();
+        let elements = graph_to_svg_elements(&graph, &settings);
+        // The three parallel edges are bundled, the edge in the opposite direction is not
+        assert_eq!(elements.edges.len(), 2);
+        assert!(elements.edges[0].contains("stroke-width=\"8.660254\""));
+        assert!(elements.edges[0].contains(">3</text>"));
+        assert!(elements.edges[1].contains("stroke-width=\"5.0\""));
+        assert!(elements.edges[1].contains(">label</text>"));
+    }
+
+    #[test]
+    fn test_auto_node_radius() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        for _ in 0..8 {
+            graph.add_node(());
+        }
+        let attribute = |tag: &str, name: &str| -> f32 {
+            let start = tag.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+            let end = start + tag[start..].find('"').unwrap();
+            tag[start..end].parse().unwrap()
+        };
+
+        let settings = SettingsBuilder::new()
+            .node_radius(500.0)
+            .auto_node_radius(true)
+            .build()
+            .unwrap();
+        let elements = graph_to_svg_elements(&graph, &settings);
+
+        let circles: Vec<_> = elements
+            .nodes
+            .iter()
+            .map(|node| {
+                (
+                    attribute(node, "cx"),
+                    attribute(node, "cy"),
+                    attribute(node, "r"),
+                )
+            })
+            .collect();
+        let mut nearest_neighbor_distance = f32::INFINITY;
+        for (i, &(x_i, y_i, _)) in circles.iter().enumerate() {
+            for &(x_j, y_j, _) in &circles[i + 1..] {
+                let distance = ((x_j - x_i).powi(2) + (y_j - y_i).powi(2)).sqrt();
+                nearest_neighbor_distance = nearest_neighbor_distance.min(distance);
+            }
+        }
+        for (_, _, radius) in circles {
+            assert!(radius <= nearest_neighbor_distance / 2.0);
+            assert!(radius > 0.0);
+        }
+
+        // The configured radius is an upper bound
+        let settings = SettingsBuilder::new()
+            .node_radius(10.0)
+            .auto_node_radius(true)
+            .build()
+            .unwrap();
+        let elements = graph_to_svg_elements(&graph, &settings);
+        assert_eq!(attribute(&elements.nodes[0], "r"), 10.0);
+
+        // Coinciding nodes do not shrink the radius to zero
+        let settings = SettingsBuilder::new()
+            .width(500.0)
+            .height(500.0)
+            .margin_x(0.0)
+            .margin_y(0.0)
+            .node_radius(500.0)
+            .auto_node_radius(true)
+            .position_map(|node_id: NodeIndex| (0.5 * (node_id.index() % 2) as f32, 0.5))
+            .build()
+            .unwrap();
+        let elements = graph_to_svg_elements(&graph, &settings);
+        let radius = attribute(&elements.nodes[0], "r");
+        assert!(radius > 0.0 && radius <= 125.0);
+    }
+
+    #[test]
+    fn test_graph_to_svg_with_position_map() {
+        let (graph, settings) = position_map_test_case();
+        let svg_output = graph_to_svg_string(&graph, &settings);
+
+        println!("SVG Output:\n{}", svg_output);
+
+        let expected_output =
+            "<svg width=\"500.0\" height=\"500.0\" xmlns=\"http://www.w3.org/2000/svg\">
+
+    <line x1=\"162.5\" y1=\"137.5\" x2=\"337.5\" y2=\"137.5\" stroke=\"black\" \
+             stroke-width=\"5.0\"/>
+    <text x= \"250.0\" y=\"137.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+
+    <line x1=\"362.5\" y1=\"162.5\" x2=\"362.5\" y2=\"337.5\" stroke=\"black\" \
+             stroke-width=\"5.0\"/>
+    <text x= \"362.5\" y=\"250.0\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+
+    <line x1=\"337.5\" y1=\"362.5\" x2=\"162.5\" y2=\"362.5\" stroke=\"black\" \
+             stroke-width=\"5.0\"/>
+    <text x= \"250.0\" y=\"362.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+
+    <line x1=\"137.5\" y1=\"337.5\" x2=\"137.5\" y2=\"162.5\" stroke=\"black\" \
+             stroke-width=\"5.0\"/>
+    <text x= \"137.5\" y=\"250.0\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"blue\" text-anchor=\"middle\" dominant-baseline=\"central\"></text>
+
+    <circle cx=\"137.5\" cy=\"137.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
+    <text x=\"137.5\" y=\"137.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">0</text>
+
+    <circle cx=\"362.5\" cy=\"137.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
+    <text x=\"362.5\" y=\"137.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">1</text>
+
+    <circle cx=\"362.5\" cy=\"362.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
+    <text x=\"362.5\" y=\"362.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">2</text>
+
+    <circle cx=\"137.5\" cy=\"362.5\" r=\"25.0\" fill=\"white\" stroke=\"black\"/>
+    <text x=\"137.5\" y=\"362.5\" font-size=\"16.0px\" font-family=\"DejaVu Sans, sans-serif\" \
+             fill=\"black\" text-anchor=\"middle\" dominant-baseline=\"central\">3</text>
 </svg>"
                 .to_owned();
 
@@ -0,0 +1,351 @@
+//! Helpers for constructing graphs from common external representations.
+//!
+//! These functions allow going straight from plain data, e.g. an adjacency matrix, to a
+//! [`petgraph`] graph which can then be passed to [`graph_to_svg`](crate::graph_to_svg) or
+//! `graph_to_img`.
+
+#[cfg(feature = "json")]
+use std::collections::BTreeMap;
+#[cfg(any(feature = "csv", feature = "json"))]
+use std::collections::HashMap;
+
+use petgraph::graph::{DiGraph, NodeIndex, UnGraph};
+
+use crate::errors::InteropError;
+
+/// Builds an undirected graph from an adjacency matrix.
+///
+/// Node `i` corresponds to row (and column) `i` of the matrix and an edge is added between nodes
+/// `i` and `j` if the entry at (i, j) is `true`. Entries on the diagonal result in self-loops.
+///
+/// Returns an error if the matrix is not square or not symmetric.
+pub fn graph_from_adjacency_matrix(matrix: &[Vec<bool>]) -> Result<UnGraph<(), ()>, InteropError> {
+    validate_square(matrix)?;
+
+    let mut graph = UnGraph::with_capacity(matrix.len(), 0);
+    for _ in 0..matrix.len() {
+        graph.add_node(());
+    }
+
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &entry) in row.iter().enumerate().skip(i) {
+            if entry != matrix[j][i] {
+                return Err(InteropError::NotSymmetric(i, j));
+            }
+            if entry {
+                graph.add_edge(NodeIndex::new(i), NodeIndex::new(j), ());
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Builds a directed graph from a weighted adjacency matrix.
+///
+/// Node `i` corresponds to row (and column) `i` of the matrix and an edge from node `i` to node
+/// `j` with the entry at (i, j) as its weight is added for each non-zero entry. Entries on the
+/// diagonal result in self-loops.
+///
+/// Returns an error if the matrix is not square.
+pub fn graph_from_weighted_adjacency_matrix(
+    matrix: &[Vec<f32>],
+) -> Result<DiGraph<(), f32>, InteropError> {
+    validate_square(matrix)?;
+
+    let mut graph = DiGraph::with_capacity(matrix.len(), 0);
+    for _ in 0..matrix.len() {
+        graph.add_node(());
+    }
+
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &weight) in row.iter().enumerate() {
+            if weight != 0.0 {
+                graph.add_edge(NodeIndex::new(i), NodeIndex::new(j), weight);
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Builds an undirected graph from a list of edges given as pairs of node indices.
+///
+/// The graph contains as many nodes as needed to cover the largest index, so indices that do not
+/// appear in any edge result in isolated nodes. Edges with equal endpoints result in self-loops.
+pub fn graph_from_edge_list(edges: &[(usize, usize)]) -> UnGraph<(), ()> {
+    UnGraph::from_edges(
+        edges
+            .iter()
+            .map(|&(source, target)| (source as u32, target as u32)),
+    )
+}
+
+/// Same as [`graph_from_edge_list`], but builds a directed graph with edges pointing from the
+/// first to the second index of each pair.
+pub fn digraph_from_edge_list(edges: &[(usize, usize)]) -> DiGraph<(), ()> {
+    DiGraph::from_edges(
+        edges
+            .iter()
+            .map(|&(source, target)| (source as u32, target as u32)),
+    )
+}
+
+/// Reads a directed graph from CSV data with one `source,target,weight` row per edge.
+///
+/// The weight column is optional and defaults to 1.0 if missing or empty. Nodes are identified by
+/// their names, which are used as node weights, such that each distinct name results in exactly
+/// one node. Surrounding whitespace of all fields is ignored.
+///
+/// The first row is treated as a header and skipped if its first two fields are `source` and
+/// `target` (case-insensitive).
+///
+/// Returns an error if the data is not valid CSV, a row does not have 2 or 3 fields or a weight
+/// is not a number.
+#[cfg(feature = "csv")]
+pub fn graph_from_csv(reader: impl std::io::Read) -> Result<DiGraph<String, f32>, InteropError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let mut graph = DiGraph::new();
+    let mut node_indices: HashMap<String, NodeIndex> = HashMap::new();
+    let mut node_index = |graph: &mut DiGraph<String, f32>, name: &str| {
+        *node_indices
+            .entry(name.to_owned())
+            .or_insert_with(|| graph.add_node(name.to_owned()))
+    };
+
+    for (row, record) in reader.records().enumerate() {
+        let record = record?;
+        let line = record
+            .position()
+            .map_or(row as u64 + 1, csv::Position::line);
+
+        if !(2..=3).contains(&record.len()) {
+            return Err(InteropError::MalformedRow {
+                line,
+                fields: record.len(),
+            });
+        }
+        if row == 0
+            && record[0].eq_ignore_ascii_case("source")
+            && record[1].eq_ignore_ascii_case("target")
+        {
+            continue;
+        }
+
+        let weight = match record.get(2) {
+            None | Some("") => 1.0,
+            Some(value) => value.parse().map_err(|_| InteropError::InvalidWeight {
+                line,
+                value: value.to_owned(),
+            })?,
+        };
+
+        let source = node_index(&mut graph, &record[0]);
+        let target = node_index(&mut graph, &record[1]);
+        graph.add_edge(source, target, weight);
+    }
+
+    Ok(graph)
+}
+
+/// Writes node positions as JSON, e.g. to save a layout computed by
+/// [`compute_positions`](crate::layout::compute_positions) for later renderings.
+///
+/// The positions are written as an object which maps the index of each node to its `[x, y]`
+/// position, sorted by the index, e.g. `{"0":[0.25,0.5],"1":[0.75,0.5]}`. Use
+/// [`positions_from_json`] to read them back.
+///
+/// Returns an error if a position is not finite, since JSON cannot represent such numbers, or if
+/// writing to the `writer` fails.
+#[cfg(feature = "json")]
+pub fn positions_to_json(
+    positions: &HashMap<NodeIndex, (f32, f32)>,
+    writer: impl std::io::Write,
+) -> Result<(), InteropError> {
+    let positions: BTreeMap<usize, (f32, f32)> = positions
+        .iter()
+        .map(|(node_id, &position)| (node_id.index(), position))
+        .collect();
+    if let Some((&index, &(x, y))) = positions
+        .iter()
+        .find(|(_, (x, y))| !x.is_finite() || !y.is_finite())
+    {
+        return Err(InteropError::NonFinitePosition {
+            node: NodeIndex::new(index),
+            x,
+            y,
+        });
+    }
+    serde_json::to_writer(writer, &positions)?;
+    Ok(())
+}
+
+/// Reads node positions from JSON in the format written by [`positions_to_json`].
+///
+/// The result can be turned into a position map using
+/// [`positions_from_map`](crate::layout::positions_from_map).
+///
+/// Returns an error if the data is not valid JSON or not an object mapping node indices to
+/// `[x, y]` positions.
+#[cfg(feature = "json")]
+pub fn positions_from_json(
+    reader: impl std::io::Read,
+) -> Result<HashMap<NodeIndex, (f32, f32)>, InteropError> {
+    let positions: HashMap<usize, (f32, f32)> = serde_json::from_reader(reader)?;
+    Ok(positions
+        .into_iter()
+        .map(|(index, position)| (NodeIndex::new(index), position))
+        .collect())
+}
+
+fn validate_square<T>(matrix: &[Vec<T>]) -> Result<(), InteropError> {
+    match matrix.iter().position(|row| row.len() != matrix.len()) {
+        Some(row) => Err(InteropError::NotSquare {
+            row,
+            len: matrix[row].len(),
+            expected: matrix.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::NodeIndex;
+
+    use super::{
+        digraph_from_edge_list, graph_from_adjacency_matrix, graph_from_edge_list,
+        graph_from_weighted_adjacency_matrix,
+    };
+    use crate::errors::InteropError;
+
+    #[test]
+    fn test_graph_from_adjacency_matrix() {
+        let triangle = [
+            vec![false, true, true],
+            vec![true, false, true],
+            vec![true, true, false],
+        ];
+        let graph = graph_from_adjacency_matrix(&triangle).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+
+        let weighted = [
+            vec![0.0, 1.0, 2.0],
+            vec![1.0, 0.0, 3.0],
+            vec![2.0, 3.0, 0.0],
+        ];
+        let graph = graph_from_weighted_adjacency_matrix(&weighted).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        // Both directions of each edge are present in the directed graph
+        assert_eq!(graph.edge_count(), 6);
+        assert_eq!(graph.edge_weights().sum::<f32>(), 12.0);
+    }
+
+    #[test]
+    fn test_invalid_adjacency_matrix() {
+        let not_square = [vec![false, true], vec![true]];
+        assert!(matches!(
+            graph_from_adjacency_matrix(&not_square),
+            Err(InteropError::NotSquare {
+                row: 1,
+                len: 1,
+                expected: 2
+            })
+        ));
+
+        let not_symmetric = [vec![false, true], vec![false, false]];
+        assert!(matches!(
+            graph_from_adjacency_matrix(&not_symmetric),
+            Err(InteropError::NotSymmetric(0, 1))
+        ));
+    }
+
+    #[test]
+    fn test_graph_from_edge_list() {
+        // Node 3 is isolated and node 4 has a self-loop
+        let edges = [(0, 1), (1, 2), (2, 0), (4, 4)];
+
+        let graph = graph_from_edge_list(&edges);
+        assert_eq!(graph.node_count(), 5);
+        assert_eq!(graph.edge_count(), 4);
+        assert!(graph.contains_edge(NodeIndex::new(4), NodeIndex::new(4)));
+
+        let digraph = digraph_from_edge_list(&edges);
+        assert_eq!(digraph.node_count(), 5);
+        assert_eq!(digraph.edge_count(), 4);
+        assert!(digraph.contains_edge(NodeIndex::new(2), NodeIndex::new(0)));
+        assert!(!digraph.contains_edge(NodeIndex::new(0), NodeIndex::new(2)));
+
+        assert_eq!(graph_from_edge_list(&[]).node_count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "csv")]
+    fn test_graph_from_csv() {
+        let data = "Source, Target, Weight\n\
+                    Berlin, Paris, 2.5\n\
+                    Paris, Madrid\n\
+                    Madrid, Berlin,\n\
+                    Berlin, Madrid, 4\n";
+
+        let graph = super::graph_from_csv(data.as_bytes()).unwrap();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 4);
+        assert_eq!(graph[NodeIndex::new(0)], "Berlin");
+        assert_eq!(graph.edge_weights().sum::<f32>(), 8.5);
+
+        let malformed = "a, b, 1\nc\n";
+        assert!(matches!(
+            super::graph_from_csv(malformed.as_bytes()),
+            Err(InteropError::MalformedRow { line: 2, fields: 1 })
+        ));
+
+        let invalid_weight = "a, b, heavy\n";
+        assert!(matches!(
+            super::graph_from_csv(invalid_weight.as_bytes()),
+            Err(InteropError::InvalidWeight { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_positions_json() {
+        let positions = std::collections::HashMap::from([
+            (NodeIndex::new(1), (0.75, 0.5)),
+            (NodeIndex::new(0), (0.25, 0.5)),
+        ]);
+
+        let mut json = Vec::new();
+        super::positions_to_json(&positions, &mut json).unwrap();
+        assert_eq!(json, br#"{"0":[0.25,0.5],"1":[0.75,0.5]}"#);
+        assert_eq!(
+            super::positions_from_json(json.as_slice()).unwrap(),
+            positions
+        );
+
+        assert!(matches!(
+            super::positions_from_json(r#"{"0":[0.25]}"#.as_bytes()),
+            Err(InteropError::Json(_))
+        ));
+
+        // Non-finite positions cannot be written and nothing is written in that case
+        for position in [(f32::NAN, 0.5), (0.5, f32::INFINITY)] {
+            let positions = std::collections::HashMap::from([
+                (NodeIndex::new(0), (0.25, 0.5)),
+                (NodeIndex::new(1), position),
+            ]);
+            let mut json = Vec::new();
+            assert!(matches!(
+                super::positions_to_json(&positions, &mut json),
+                Err(InteropError::NonFinitePosition { node, .. }) if node == NodeIndex::new(1)
+            ));
+            assert!(json.is_empty());
+        }
+    }
+}
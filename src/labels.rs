@@ -0,0 +1,84 @@
+//! Ready-made label functions for common use cases.
+//!
+//! The functions of this module return closures which can be passed to
+//! [`SettingsBuilder::node_label_fn`](crate::settings::SettingsBuilder::node_label_fn) and
+//! [`SettingsBuilder::edge_label_fn`](crate::settings::SettingsBuilder::edge_label_fn), e.g.
+//! [`edge_weights`], which labels each edge with its weight.
+
+use std::fmt::Display;
+
+use petgraph::data::DataMap;
+
+/// Returns a node label function which labels each node with its weight, formatted using its
+/// [`Display`] implementation.
+///
+/// Nodes without a weight, i.e. nodes not contained in the graph, get an empty label.
+pub fn node_weights<G>(graph: &G) -> impl Fn(G::NodeId) -> String + '_
+where
+    G: DataMap,
+    G::NodeWeight: Display,
+{
+    move |node_id| {
+        graph
+            .node_weight(node_id)
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+}
+
+/// Returns an edge label function which labels each edge with its weight, formatted using its
+/// [`Display`] implementation.
+///
+/// Edges without a weight, i.e. edges not contained in the graph, get an empty label.
+pub fn edge_weights<G>(graph: &G) -> impl Fn(G::EdgeId) -> String + '_
+where
+    G: DataMap,
+    G::EdgeWeight: Display,
+{
+    move |edge_id| {
+        graph
+            .edge_weight(edge_id)
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{EdgeIndex, NodeIndex};
+
+    use super::{edge_weights, node_weights};
+    use crate::{
+        graph_to_svg::graph_to_svg_string, settings::SettingsBuilder, tests::build_2d_grid,
+    };
+
+    #[test]
+    fn test_weight_labels() {
+        let mut graph = build_2d_grid(3, 2);
+        for (i, weight) in graph.edge_weights_mut().enumerate() {
+            *weight = 10 * (i + 1);
+        }
+
+        let edge_labels = edge_weights(&graph);
+        for edge_id in graph.edge_indices() {
+            assert_eq!(
+                edge_labels(edge_id),
+                (10 * (edge_id.index() + 1)).to_string()
+            );
+        }
+        assert_eq!(edge_labels(EdgeIndex::new(graph.edge_count())), "");
+
+        let graph = graph.map(|_, &(i, j)| format!("({i}, {j})"), |_, &weight| weight);
+        let node_labels = node_weights(&graph);
+        assert_eq!(node_labels(NodeIndex::new(4)), "(1, 1)");
+
+        let settings = SettingsBuilder::new()
+            .node_label_fn(node_weights(&graph))
+            .edge_label_fn(edge_weights(&graph))
+            .build()
+            .expect("Values should be valid.");
+        let svg = graph_to_svg_string(&graph, &settings);
+        assert!(svg.contains(">(1, 1)<"));
+        assert!(svg.contains(">70<"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Module containing functionality for automatically selecting a layout.
+//!
+//! The main function is [`select_layout`], which inspects a graph and returns the [`Layout`] that
+//! [`Layout::Auto`] dispatches to.
+
+use std::{borrow::Cow, collections::HashSet};
+
+use petgraph::{
+    graph::NodeIndex,
+    unionfind::UnionFind,
+    visit::{
+        EdgeRef, GraphProp, IntoEdgeReferences, IntoNeighborsDirected, IntoNodeReferences,
+        NodeIndexable, NodeRef,
+    },
+};
+
+use crate::layout::{hierarchical::Orientation, Layout};
+
+/// Density from which a graph is considered dense, see [`select_layout`].
+const DENSE_GRAPH_DENSITY: f32 = 0.5;
+
+/// Returns a layout which is suitable for the structure of the graph.
+///
+/// The layout is selected by the first of the following rules which applies, ignoring the
+/// directions of edges:
+/// - If the graph is a forest, i.e. contains no cycles (including self-loops and parallel edges),
+///   [`Layout::Hierarchical`] from top to bottom is selected.
+/// - If the graph is bipartite, [`Layout::Bipartite`] is selected, with the partition found by a
+///   breadth-first traversal.
+/// - If the graph is dense, i.e. contains at least half of all possible edges, [`Layout::Circular`]
+///   is selected.
+/// - Otherwise, [`Layout::ForceDirected`] is selected.
+///
+/// The selection only depends on the graph, such that the same graph always results in the same
+/// layout. It never returns [`Layout::Auto`].
+pub fn select_layout<G>(graph: G) -> Layout
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+{
+    if is_forest(graph) {
+        return Layout::Hierarchical(Orientation::TopToBottom);
+    }
+    if let Some(left_partition) = bipartition(graph) {
+        return Layout::Bipartite(Some(left_partition));
+    }
+
+    let node_count = graph.node_references().count() as f32;
+    let edge_count = graph.edge_references().count() as f32;
+    let possible_edges = if graph.is_directed() {
+        node_count * (node_count - 1.0)
+    } else {
+        node_count * (node_count - 1.0) / 2.0
+    };
+    if possible_edges > 0.0 && edge_count / possible_edges >= DENSE_GRAPH_DENSITY {
+        Layout::Circular
+    } else {
+        Layout::ForceDirected
+    }
+}
+
+/// Returns the `layout`, with [`Layout::Auto`] replaced by the layout selected for the graph, see
+/// [`select_layout`].
+pub(crate) fn resolve_layout<G>(graph: G, layout: &Layout) -> Cow<'_, Layout>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+{
+    match layout {
+        Layout::Auto => Cow::Owned(select_layout(graph)),
+        layout => Cow::Borrowed(layout),
+    }
+}
+
+/// Returns whether the graph contains no cycles when ignoring the directions of edges.
+fn is_forest<G>(graph: G) -> bool
+where
+    G: IntoEdgeReferences + NodeIndexable,
+{
+    let mut components = UnionFind::new(graph.node_bound());
+    graph.edge_references().all(|edge| {
+        components.union(
+            NodeIndexable::to_index(&graph, edge.source()),
+            NodeIndexable::to_index(&graph, edge.target()),
+        )
+    })
+}
+
+/// Two-colors the graph using a breadth-first traversal, ignoring the directions of edges.
+///
+/// Returns the nodes of the first color, i.e. the roots of the traversal and every other level
+/// below them, or `None` if the graph is not bipartite.
+fn bipartition<G>(graph: G) -> Option<HashSet<NodeIndex>>
+where
+    G: IntoNodeReferences + NodeIndexable + IntoNeighborsDirected,
+{
+    let mut colors: Vec<Option<bool>> = vec![None; graph.node_bound()];
+    let mut queue = Vec::new();
+    for node_ref in graph.node_references() {
+        let start = NodeIndexable::to_index(&graph, node_ref.id());
+        if colors[start].is_some() {
+            continue;
+        }
+        colors[start] = Some(true);
+        queue.clear();
+        queue.push(start);
+        let mut next = 0;
+        while let Some(&node) = queue.get(next) {
+            next += 1;
+            let color = colors[node];
+            let node_id = NodeIndexable::from_index(&graph, node);
+            let neighbors = graph
+                .neighbors_directed(node_id, petgraph::Direction::Outgoing)
+                .chain(graph.neighbors_directed(node_id, petgraph::Direction::Incoming));
+            for neighbor in neighbors {
+                let neighbor = NodeIndexable::to_index(&graph, neighbor);
+                match colors[neighbor] {
+                    None => {
+                        colors[neighbor] = color.map(|color| !color);
+                        queue.push(neighbor);
+                    }
+                    neighbor_color if neighbor_color == color => return None,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Some(
+        colors
+            .iter()
+            .enumerate()
+            .filter(|(_, &color)| color == Some(true))
+            .map(|(index, _)| NodeIndex::new(index))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{DiGraph, UnGraph};
+
+    use super::select_layout;
+    use crate::layout::{hierarchical::Orientation, Layout};
+
+    #[test]
+    fn test_select_layout() {
+        // A tree, with its edges pointing towards the root
+        let mut tree = DiGraph::<(), ()>::new();
+        let nodes: Vec<_> = (0..7).map(|_| tree.add_node(())).collect();
+        for child in 1..7 {
+            tree.add_edge(nodes[child], nodes[(child - 1) / 2], ());
+        }
+        assert!(matches!(
+            select_layout(&tree),
+            Layout::Hierarchical(Orientation::TopToBottom)
+        ));
+
+        let mut complete = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|_| complete.add_node(())).collect();
+        for (i, &a) in nodes.iter().enumerate() {
+            for &b in &nodes[i + 1..] {
+                complete.add_edge(a, b, ());
+            }
+        }
+        assert!(matches!(select_layout(&complete), Layout::Circular));
+
+        // An even cycle is bipartite, with opposite nodes on the same side
+        let mut cycle = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|_| cycle.add_node(())).collect();
+        for i in 0..6 {
+            cycle.add_edge(nodes[i], nodes[(i + 1) % 6], ());
+        }
+        let Layout::Bipartite(Some(left_partition)) = select_layout(&cycle) else {
+            panic!("An even cycle should be laid out as bipartite graph");
+        };
+        assert_eq!(left_partition.len(), 3);
+        assert!(left_partition.contains(&nodes[0]) && left_partition.contains(&nodes[2]));
+
+        // A sparse graph with an odd cycle
+        cycle.add_edge(nodes[0], nodes[2], ());
+        let ring = (0..6).map(|_| cycle.add_node(())).collect::<Vec<_>>();
+        for i in 0..6 {
+            cycle.add_edge(ring[i], ring[(i + 1) % 6], ());
+        }
+        assert!(matches!(select_layout(&cycle), Layout::ForceDirected));
+    }
+}
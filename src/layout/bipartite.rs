@@ -8,9 +8,11 @@ use std::{collections::HashSet, hash::Hash};
 use fixedbitset::FixedBitSet;
 use petgraph::{
     graph::NodeIndex,
-    visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable},
+    visit::{IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable},
 };
 
+use crate::errors::NotBipartiteError;
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum NodePosition {
     Left,
@@ -24,7 +26,8 @@ enum NodePosition {
 ///
 /// If the `left` parameter is `None`, the function will attempt to determine the bipartition
 /// using a breadth-first traversal. If the graph is not bipartite, the layout will still assign
-/// nodes to left and right positions based on the traversal.
+/// nodes to left and right positions based on the traversal. Use [`try_bipartite_layout`] to
+/// detect this case instead.
 ///
 /// The returned position map is normalized to [0.0, 1.0].
 pub fn bipartite_layout<'a, G>(
@@ -32,58 +35,127 @@ pub fn bipartite_layout<'a, G>(
     left: Option<&HashSet<NodeIndex>>,
 ) -> impl Fn(G::NodeId) -> (f32, f32) + 'a
 where
-    G: IntoNodeIdentifiers + NodeIndexable + IntoNeighbors,
+    G: IntoNodeIdentifiers + NodeIndexable + IntoNeighborsDirected,
     G::NodeId: Hash + Eq,
 {
-    let mut visited: FixedBitSet = FixedBitSet::with_capacity(graph.node_bound());
-    let mut dfs_stack = Vec::new();
+    let partition = match left {
+        Some(left_nodes) => partition_from_left_nodes(graph, left_nodes),
+        None => partition_by_traversal(graph, false)
+            .expect("Traversal should not fail if conflicting edges are not reported."),
+    };
 
-    let (node_lr_positions, left_count, right_count) = if let Some(left_nodes) = left {
-        let mut node_lr_positions = vec![None; graph.node_bound()];
-        let left_count = left_nodes.len();
-        let right_count = graph.node_identifiers().count() - left_count;
-
-        for node_id in graph.node_identifiers() {
-            let node_index = graph.to_index(node_id);
-            if left_nodes.contains(&NodeIndex::new(node_index)) {
-                node_lr_positions[node_index] = Some(NodePosition::Left);
-            } else {
-                node_lr_positions[node_index] = Some(NodePosition::Right);
-            }
+    position_map_from_partition(graph, partition)
+}
+
+/// Same as [`bipartite_layout`], but returns a [`NotBipartiteError`] if `left` is `None` and the
+/// graph is not bipartite.
+///
+/// The error contains an edge whose endpoints were assigned to the same partition during the
+/// traversal, i.e. an edge closing a cycle of odd length.
+///
+/// If the `left` parameter is provided, it is used as is and no error is returned.
+pub fn try_bipartite_layout<'a, G>(
+    graph: &'a G,
+    left: Option<&HashSet<NodeIndex>>,
+) -> Result<impl Fn(G::NodeId) -> (f32, f32) + 'a, NotBipartiteError>
+where
+    G: IntoNodeIdentifiers + NodeIndexable + IntoNeighborsDirected,
+    G::NodeId: Hash + Eq,
+{
+    let partition = match left {
+        Some(left_nodes) => partition_from_left_nodes(graph, left_nodes),
+        None => partition_by_traversal(graph, true)?,
+    };
+
+    Ok(position_map_from_partition(graph, partition))
+}
+
+/// Assignment of node indices to the left or right side along with the sizes of both sides.
+type Partition = (Vec<Option<NodePosition>>, usize, usize);
+
+fn partition_from_left_nodes<G>(graph: &G, left_nodes: &HashSet<NodeIndex>) -> Partition
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut node_lr_positions = vec![None; graph.node_bound()];
+    let left_count = left_nodes.len();
+    let right_count = graph.node_identifiers().count() - left_count;
+
+    for node_id in graph.node_identifiers() {
+        let node_index = graph.to_index(node_id);
+        if left_nodes.contains(&NodeIndex::new(node_index)) {
+            node_lr_positions[node_index] = Some(NodePosition::Left);
+        } else {
+            node_lr_positions[node_index] = Some(NodePosition::Right);
         }
+    }
 
-        (node_lr_positions, left_count, right_count)
-    } else {
-        let mut node_lr_positions = vec![None; graph.node_bound()];
-        let mut left_count = 0;
-        let mut right_count = 0;
-        for outer_node_index in graph.node_identifiers().map(|id| graph.to_index(id)) {
-            if !visited.contains(outer_node_index) {
-                dfs_stack.push((outer_node_index, 0));
-                while let Some((current_node_index, layer)) = dfs_stack.pop() {
-                    if visited.contains(current_node_index) {
-                        continue;
-                    }
-                    visited.insert(current_node_index);
-                    if layer % 2 == 0 {
-                        node_lr_positions[current_node_index] = Some(NodePosition::Left);
-                        left_count += 1;
-                    } else {
-                        node_lr_positions[current_node_index] = Some(NodePosition::Right);
-                        right_count += 1;
-                    }
-                    for neighbor in graph.neighbors(graph.from_index(current_node_index)) {
-                        let neighbor_idx = graph.to_index(neighbor);
-                        if !visited.contains(neighbor_idx) {
-                            dfs_stack.push((neighbor_idx, layer + 1));
-                        }
+    (node_lr_positions, left_count, right_count)
+}
+
+/// Two-colors the graph using a depth-first traversal, ignoring the directions of edges. If
+/// `report_conflicts` is set, the first edge connecting two nodes of the same side is returned as
+/// an error.
+fn partition_by_traversal<G>(
+    graph: &G,
+    report_conflicts: bool,
+) -> Result<Partition, NotBipartiteError>
+where
+    G: IntoNodeIdentifiers + NodeIndexable + IntoNeighborsDirected,
+{
+    let mut visited: FixedBitSet = FixedBitSet::with_capacity(graph.node_bound());
+    let mut dfs_stack = Vec::new();
+
+    let mut node_lr_positions = vec![None; graph.node_bound()];
+    let mut left_count = 0;
+    let mut right_count = 0;
+    for outer_node_index in graph.node_identifiers().map(|id| graph.to_index(id)) {
+        if !visited.contains(outer_node_index) {
+            dfs_stack.push((outer_node_index, 0));
+            while let Some((current_node_index, layer)) = dfs_stack.pop() {
+                if visited.contains(current_node_index) {
+                    continue;
+                }
+                visited.insert(current_node_index);
+                let current_position = if layer % 2 == 0 {
+                    left_count += 1;
+                    NodePosition::Left
+                } else {
+                    right_count += 1;
+                    NodePosition::Right
+                };
+                node_lr_positions[current_node_index] = Some(current_position);
+                let current_node = graph.from_index(current_node_index);
+                let neighbors = graph
+                    .neighbors_directed(current_node, petgraph::Direction::Outgoing)
+                    .chain(graph.neighbors_directed(current_node, petgraph::Direction::Incoming));
+                for neighbor in neighbors {
+                    let neighbor_idx = graph.to_index(neighbor);
+                    if !visited.contains(neighbor_idx) {
+                        dfs_stack.push((neighbor_idx, layer + 1));
+                    } else if report_conflicts
+                        && node_lr_positions[neighbor_idx] == Some(current_position)
+                    {
+                        return Err(NotBipartiteError(
+                            NodeIndex::new(current_node_index),
+                            NodeIndex::new(neighbor_idx),
+                        ));
                     }
                 }
             }
         }
-        (node_lr_positions, left_count, right_count)
-    };
+    }
+
+    Ok((node_lr_positions, left_count, right_count))
+}
 
+fn position_map_from_partition<'a, G>(
+    graph: &'a G,
+    (node_lr_positions, left_count, right_count): Partition,
+) -> impl Fn(G::NodeId) -> (f32, f32) + 'a
+where
+    G: NodeIndexable,
+{
     let mut node_positions: Vec<(f32, f32)> = vec![(0.0, 0.0); graph.node_bound()];
 
     let left_spacing = if left_count > 1 {
@@ -122,3 +194,66 @@ where
         node_positions[index]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{DiGraph, UnGraph};
+
+    use super::try_bipartite_layout;
+
+    #[test]
+    fn test_try_bipartite_layout_on_triangle() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let node_a = graph.add_node(());
+        let node_b = graph.add_node(());
+        let node_c = graph.add_node(());
+        graph.add_edge(node_a, node_b, ());
+        graph.add_edge(node_b, node_c, ());
+        graph.add_edge(node_c, node_a, ());
+
+        let graph = &graph;
+        let error = try_bipartite_layout(&graph, None)
+            .err()
+            .expect("A triangle should not be bipartite.");
+        assert!(graph.contains_edge(error.0, error.1));
+    }
+
+    #[test]
+    fn test_try_bipartite_layout_on_directed_odd_cycle() {
+        // The edges form a triangle when ignoring their directions, although no node can reach
+        // itself
+        let mut graph = DiGraph::<(), ()>::new();
+        let node_a = graph.add_node(());
+        let node_b = graph.add_node(());
+        let node_c = graph.add_node(());
+        graph.add_edge(node_a, node_b, ());
+        graph.add_edge(node_a, node_c, ());
+        graph.add_edge(node_c, node_b, ());
+
+        let graph = &graph;
+        let error = try_bipartite_layout(&graph, None)
+            .err()
+            .expect("A directed triangle should not be bipartite.");
+        assert!(graph.contains_edge(error.0, error.1) || graph.contains_edge(error.1, error.0));
+    }
+
+    #[test]
+    fn test_try_bipartite_layout_on_square() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let node_a = graph.add_node(());
+        let node_b = graph.add_node(());
+        let node_c = graph.add_node(());
+        let node_d = graph.add_node(());
+        graph.add_edge(node_a, node_b, ());
+        graph.add_edge(node_b, node_c, ());
+        graph.add_edge(node_c, node_d, ());
+        graph.add_edge(node_d, node_a, ());
+
+        let graph = &graph;
+        let position_map =
+            try_bipartite_layout(&graph, None).expect("A square should be bipartite.");
+        assert_eq!(position_map(node_a).0, position_map(node_c).0);
+        assert_eq!(position_map(node_b).0, position_map(node_d).0);
+        assert_ne!(position_map(node_a).0, position_map(node_b).0);
+    }
+}
@@ -0,0 +1,67 @@
+//! Module containing functionality for caching computed layouts.
+//!
+//! The main type is [`LayoutCache`], which can be passed to
+//! [`SettingsBuilder::layout_cache`](crate::settings::SettingsBuilder::layout_cache) to reuse the
+//! positions of a layout when rendering the same graph several times, e.g. at different sizes or in
+//! different colors.
+
+use std::{collections::HashMap, sync::Mutex};
+
+/// Cache of the normalized positions computed by layout algorithms, keyed by caller-provided keys.
+///
+/// Since graphs can not be fingerprinted cheaply, the caller is responsible for choosing keys which
+/// identify the graph and the layout. Reusing a key for a different graph or layout results in the
+/// cached positions being used anyway, so the key should be changed whenever either of them
+/// changes. Nodes without a cached position are placed at the center (0.5, 0.5).
+///
+/// The cache can be shared between threads, e.g. by wrapping it into an
+/// [`Arc`](std::sync::Arc).
+#[derive(Debug, Default)]
+pub struct LayoutCache {
+    entries: Mutex<HashMap<String, Vec<(f32, f32)>>>,
+}
+
+impl LayoutCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached positions for the `key`, if any, indexed by the node index.
+    pub fn get(&self, key: &str) -> Option<Vec<(f32, f32)>> {
+        self.lock().get(key).cloned()
+    }
+
+    /// Caches the `positions` for the `key`, indexed by the node index, and returns the previously
+    /// cached positions, if any.
+    pub fn insert(
+        &self,
+        key: impl Into<String>,
+        positions: Vec<(f32, f32)>,
+    ) -> Option<Vec<(f32, f32)>> {
+        self.lock().insert(key.into(), positions)
+    }
+
+    /// Removes all cached positions.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns the number of cached layouts.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether no layouts are cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Locks the entries. A poisoned lock is recovered, since the entries are never left in an
+    /// inconsistent state.
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<(f32, f32)>>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
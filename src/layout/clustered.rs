@@ -0,0 +1,230 @@
+//! Module containing functionality for the clustered layout.
+//!
+//! The main function is [`clustered_layout`], which returns a position map function that groups
+//! the nodes of each community together.
+
+use std::collections::{HashMap, HashSet};
+
+use petgraph::{
+    graph::{NodeIndex, UnGraph},
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef},
+};
+
+use crate::layout::force_directed::{
+    force_directed_layout, DEFAULT_INITIAL_TEMPERATURE, DEFAULT_ITERATIONS,
+};
+
+/// Half the side length of the square around the position of a community in which its members are
+/// placed, relative to the smallest distance between two communities. Small enough that any two
+/// members of a community are closer to each other than to any node of another community.
+const COMMUNITY_AREA_FACTOR: f32 = 0.15;
+
+/// Returns a position map function that arranges nodes such that the nodes of each community are
+/// grouped together.
+///
+/// `communities` contains the community of each node, indexed by the node index. Nodes without an
+/// entry together form one additional community.
+///
+/// The layout is computed in two steps:
+/// - Each community is contracted into a single supernode, and two supernodes are connected if an
+///   edge runs between their communities. The supernodes are arranged using the
+///   [`force_directed_layout`].
+/// - The members of each community are arranged using the [`force_directed_layout`] on the edges
+///   within the community and placed in a square around the position of their supernode.
+///
+/// The returned position map is normalized to [0.0, 1.0]. The composite layout is scaled by the
+/// same factor along both axes, such that the distances between communities are preserved.
+pub fn clustered_layout<'a, G>(
+    graph: &'a G,
+    communities: &[usize],
+) -> impl Fn(G::NodeId) -> (f32, f32) + 'a
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+{
+    let mut positions = vec![(0.0, 0.0); graph.node_bound()];
+
+    // Number the communities consecutively in the order of their first member and collect the
+    // members of each community
+    let mut community_numbers = HashMap::new();
+    let mut members: Vec<Vec<usize>> = Vec::new();
+    for node_ref in graph.node_references() {
+        let idx = graph.to_index(node_ref.id());
+        let community = communities.get(idx).copied();
+        let number = *community_numbers.entry(community).or_insert_with(|| {
+            members.push(Vec::new());
+            members.len() - 1
+        });
+        members[number].push(idx);
+    }
+    let community_of = |idx: usize| community_numbers[&communities.get(idx).copied()];
+
+    // Build the graph of the supernodes and a graph of each community
+    let mut supernode_graph = UnGraph::<(), ()>::new_undirected();
+    for _ in &members {
+        supernode_graph.add_node(());
+    }
+    let mut community_graphs: Vec<UnGraph<(), ()>> = members
+        .iter()
+        .map(|community_members| {
+            let mut community_graph = UnGraph::new_undirected();
+            for _ in community_members {
+                community_graph.add_node(());
+            }
+            community_graph
+        })
+        .collect();
+    let local_indices: HashMap<usize, usize> = members
+        .iter()
+        .flat_map(|community_members| {
+            community_members
+                .iter()
+                .enumerate()
+                .map(|(local_idx, &idx)| (idx, local_idx))
+        })
+        .collect();
+
+    let mut supernode_edges = HashSet::new();
+    for edge in graph.edge_references() {
+        let source = graph.to_index(edge.source());
+        let target = graph.to_index(edge.target());
+        let (source_community, target_community) = (community_of(source), community_of(target));
+        if source_community == target_community {
+            community_graphs[source_community].add_edge(
+                NodeIndex::new(local_indices[&source]),
+                NodeIndex::new(local_indices[&target]),
+                (),
+            );
+        } else {
+            let key = (
+                source_community.min(target_community),
+                source_community.max(target_community),
+            );
+            if supernode_edges.insert(key) {
+                supernode_graph.add_edge(NodeIndex::new(key.0), NodeIndex::new(key.1), ());
+            }
+        }
+    }
+
+    let supernode_graph = &supernode_graph;
+    let supernode_position_map = force_directed_layout(
+        &supernode_graph,
+        DEFAULT_ITERATIONS,
+        DEFAULT_INITIAL_TEMPERATURE,
+    );
+    let supernode_positions: Vec<(f32, f32)> = (0..members.len())
+        .map(|number| supernode_position_map(NodeIndex::new(number)))
+        .collect();
+
+    // Half the side length of the square in which the members of a community are placed
+    let mut min_distance = f32::INFINITY;
+    for (i, &(x_i, y_i)) in supernode_positions.iter().enumerate() {
+        for &(x_j, y_j) in &supernode_positions[i + 1..] {
+            min_distance = min_distance.min(((x_i - x_j).powi(2) + (y_i - y_j).powi(2)).sqrt());
+        }
+    }
+    let half_size = if min_distance.is_finite() && min_distance > 0.0 {
+        COMMUNITY_AREA_FACTOR * min_distance
+    } else {
+        0.5
+    };
+
+    for ((community_members, community_graph), (center_x, center_y)) in members
+        .iter()
+        .zip(&community_graphs)
+        .zip(supernode_positions)
+    {
+        let local_position_map = force_directed_layout(
+            &community_graph,
+            DEFAULT_ITERATIONS,
+            DEFAULT_INITIAL_TEMPERATURE,
+        );
+        for (local_idx, &idx) in community_members.iter().enumerate() {
+            let (local_x, local_y) = local_position_map(NodeIndex::new(local_idx));
+            positions[idx] = (
+                center_x + (2.0 * local_x - 1.0) * half_size,
+                center_y + (2.0 * local_y - 1.0) * half_size,
+            );
+        }
+    }
+
+    normalize_positions(&mut positions, members.iter().flatten().copied());
+
+    move |node_id| positions[NodeIndexable::to_index(&graph, node_id)]
+}
+
+/// Rescales the positions of the given node indices by the same factor along both axes, such that
+/// their bounding box fits into [0.0, 1.0] x [0.0, 1.0], and centers them.
+fn normalize_positions(
+    positions: &mut [(f32, f32)],
+    node_indices: impl Iterator<Item = usize> + Clone,
+) {
+    let (mut min_x, mut max_x) = (f32::INFINITY, f32::NEG_INFINITY);
+    let (mut min_y, mut max_y) = (f32::INFINITY, f32::NEG_INFINITY);
+    for idx in node_indices.clone() {
+        let (x, y) = positions[idx];
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
+
+    let span = (max_x - min_x).max(max_y - min_y);
+    let scale = if span > 0.0 { 1.0 / span } else { 1.0 };
+    let (center_x, center_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    for idx in node_indices {
+        let (x, y) = positions[idx];
+        positions[idx] = (0.5 + (x - center_x) * scale, 0.5 + (y - center_y) * scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use super::clustered_layout;
+
+    #[test]
+    fn test_clustered_layout_groups_communities() {
+        // Two cliques of four nodes each, connected by a single edge
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..8).map(|_| graph.add_node(())).collect();
+        for clique in nodes.chunks(4) {
+            for (i, &node_i) in clique.iter().enumerate() {
+                for &node_j in &clique[i + 1..] {
+                    graph.add_edge(node_i, node_j, ());
+                }
+            }
+        }
+        graph.add_edge(nodes[0], nodes[4], ());
+        let communities = [0, 0, 0, 0, 1, 1, 1, 1];
+
+        let graph = &graph;
+        let position_map = clustered_layout(&graph, &communities);
+
+        let distance = |i: usize, j: usize| {
+            let (x_i, y_i) = position_map(nodes[i]);
+            let (x_j, y_j) = position_map(nodes[j]);
+            ((x_i - x_j).powi(2) + (y_i - y_j).powi(2)).sqrt()
+        };
+        let mut max_intra_distance = 0.0f32;
+        let mut min_inter_distance = f32::INFINITY;
+        for i in 0..nodes.len() {
+            for j in (i + 1)..nodes.len() {
+                if communities[i] == communities[j] {
+                    max_intra_distance = max_intra_distance.max(distance(i, j));
+                } else {
+                    min_inter_distance = min_inter_distance.min(distance(i, j));
+                }
+            }
+        }
+        assert!(
+            max_intra_distance < min_inter_distance,
+            "{max_intra_distance} >= {min_inter_distance}"
+        );
+
+        for node in nodes {
+            let (x, y) = position_map(node);
+            assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+        }
+    }
+}
@@ -3,19 +3,84 @@
 //! The main function is [`force_directed_layout`], which returns a position map function that
 //! arranges nodes in a force-directed layout.
 
-use petgraph::visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use fixedbitset::FixedBitSet;
+use petgraph::{
+    graph::NodeIndex,
+    unionfind::UnionFind,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef},
+};
 
 /// Default number of iterations for the [`force_directed_layout`] function.
 pub const DEFAULT_ITERATIONS: u32 = 1000;
 /// Default initial temperature for the [`force_directed_layout`] function.
 pub const DEFAULT_INITIAL_TEMPERATURE: f32 = 0.1;
 const CLIPPING_VALUE: f32 = 0.01;
+/// Gap between two connected components packed by [`layout_components`], relative to the typical
+/// distance between two nodes.
+const COMPONENT_GAP: f32 = 1.0;
+
+/// Parameters of the simulation run by [`force_directed_layout_with_params`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForceDirectedParams {
+    /// Number of iterations of the simulation. The default is [`DEFAULT_ITERATIONS`].
+    pub iterations: u32,
+    /// Maximum distance a node moves in the first iteration, which decreases with each iteration.
+    /// The default is [`DEFAULT_INITIAL_TEMPERATURE`].
+    pub initial_temperature: f32,
+    /// Normalized positions in [0.0, 1.0] of nodes which are kept fixed. By default, no nodes are
+    /// pinned.
+    pub pinned_positions: HashMap<NodeIndex, (f32, f32)>,
+    /// How edge weights influence the ideal length of an edge. The default is
+    /// [`EdgeWeightMode::Attract`].
+    pub edge_weight_mode: EdgeWeightMode,
+    /// Seed of the generator drawing the random initial positions. The default of `None` seeds the
+    /// generator randomly.
+    pub seed: Option<u64>,
+    /// Time after which the simulation stops, even if not all iterations have run. The default of
+    /// `None` runs all iterations.
+    pub time_budget: Option<Duration>,
+}
+
+impl Default for ForceDirectedParams {
+    fn default() -> Self {
+        ForceDirectedParams {
+            iterations: DEFAULT_ITERATIONS,
+            initial_temperature: DEFAULT_INITIAL_TEMPERATURE,
+            pinned_positions: HashMap::new(),
+            edge_weight_mode: EdgeWeightMode::default(),
+            seed: None,
+            time_budget: None,
+        }
+    }
+}
+
+/// Modes for how edge weights influence the [`force_directed_layout_with_params`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EdgeWeightMode {
+    /// The ideal length of an edge is divided by its weight, i.e. heavier edges pull their
+    /// endpoints closer together. This is the default.
+    #[default]
+    Attract,
+    /// The ideal length of an edge is multiplied by its weight, i.e. heavier edges push their
+    /// endpoints farther apart.
+    Repel,
+}
 
 /// Returns a position map function that arranges nodes using a force-directed layout. The specific
 /// algorithm used is the Fruchterman-Reingold algorithm, see [Reference](#reference).
 ///
 /// The returned position map is normalized to [0.0, 1.0].
 ///
+/// If the graph is disconnected, each connected component is laid out independently, which avoids
+/// the components drifting apart and is faster due to the quadratic running time of the
+/// simulation. The drawings of the components are then packed into the unit square, such that
+/// they do not overlap. With the `rayon` feature, the components are laid out in parallel.
+///
 /// # Reference
 ///
 /// This is an implementation of the Fruchterman-Reingold force-directed algorithm as presented in
@@ -31,133 +96,607 @@ pub fn force_directed_layout<G>(
 where
     G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
 {
-    let node_count = graph.node_references().count();
-    let mut positions = vec![(0.0f32, 0.0f32); graph.node_bound()];
+    let params = ForceDirectedParams {
+        iterations,
+        initial_temperature: inital_temperature,
+        ..ForceDirectedParams::default()
+    };
+    force_directed_layout_with_params(graph, &params, |_| 1.0)
+}
+
+/// Same as [`force_directed_layout`], but with the simulation configured by the provided
+/// [`ForceDirectedParams`] and the weight of each edge given by `edge_weight_fn`.
+///
+/// # Pinned nodes
+///
+/// The nodes in [`ForceDirectedParams::pinned_positions`] are kept fixed at the provided
+/// coordinates while the remaining nodes settle around them. If at least one node is pinned, the
+/// simulation takes place directly in the unit square: free nodes are clamped to [0.0, 1.0] after
+/// each iteration and no final rescaling is applied, so pinned nodes end up exactly at their
+/// provided coordinates. Entries that do not refer to a node of the graph are ignored.
+///
+/// With pinned nodes, disconnected graphs are laid out as a whole instead of per component, since
+/// the pinned nodes define the frame of the entire drawing.
+///
+/// # Edge weights
+///
+/// The ideal length of an edge, i.e. the distance at which attraction and repulsion of its
+/// endpoints cancel out, is scaled according to the weight returned by `edge_weight_fn` and the
+/// [`EdgeWeightMode`] of the parameters. Weights which are not strictly positive and finite are
+/// treated as 1.0. Pass `|_| 1.0` for the unweighted layout.
+///
+/// # Seed and time budget
+///
+/// The layout only depends on the random initial positions of the free nodes, so the same
+/// [`seed`](ForceDirectedParams::seed) always yields the same layout for the same graph, while
+/// different seeds yield alternative layouts to choose from.
+///
+/// If the [`time_budget`](ForceDirectedParams::time_budget) is exceeded, the simulation stops
+/// and the positions reached so far are normalized as usual, so the returned position map is
+/// valid, but the layout is less settled than with all iterations. The budget is checked once per
+/// iteration, so a single iteration of a huge graph can exceed it.
+pub fn force_directed_layout_with_params<'a, G, EdgeWeightFn>(
+    graph: &'a G,
+    params: &ForceDirectedParams,
+    edge_weight_fn: EdgeWeightFn,
+) -> impl Fn(G::NodeId) -> (f32, f32) + 'a
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable,
+    EdgeWeightFn: Fn(G::EdgeId) -> f32,
+{
+    let ForceDirectedParams {
+        iterations,
+        initial_temperature,
+        ref pinned_positions,
+        edge_weight_mode,
+        seed,
+        time_budget,
+    } = *params;
+    let deadline = time_budget.and_then(|time_budget| Instant::now().checked_add(time_budget));
 
-    if node_count > 0 {
-        // Initialize positions randomly
-        let mut rng = fastrand::Rng::new();
+    // The simulation works on the ranks of the nodes, such that graphs with holes in the index
+    // space, e.g. a `StableGraph` with removed nodes, do not waste space
+    let node_indices: Vec<usize> = graph
+        .node_references()
+        .map(|node_ref| graph.to_index(node_ref.id()))
+        .collect();
+    let mut ranks = vec![0; graph.node_bound()];
+    for (rank, &idx) in node_indices.iter().enumerate() {
+        ranks[idx] = rank;
+    }
 
-        for node_ref in graph.node_references() {
+    // Initialize positions randomly, except for pinned nodes
+    let mut rng = match seed {
+        Some(seed) => fastrand::Rng::with_seed(seed),
+        None => fastrand::Rng::new(),
+    };
+    let mut pinned = FixedBitSet::with_capacity(node_indices.len());
+    let mut local_positions = Vec::with_capacity(node_indices.len());
+    for (rank, &idx) in node_indices.iter().enumerate() {
+        if let Some(&pinned_position) = pinned_positions.get(&NodeIndex::new(idx)) {
+            local_positions.push(pinned_position);
+            pinned.insert(rank);
+        } else {
             let x = rng.f32();
             let y = rng.f32();
-            let idx = graph.to_index(node_ref.id());
-            positions[idx] = (x, y);
-        }
-
-        // Simulation parameters
-        let k = (1.0 / (node_count as f32)).sqrt();
-
-        let edges: Vec<(usize, usize)> = graph
-            .edge_references()
-            .map(|edge| (graph.to_index(edge.source()), graph.to_index(edge.target())))
-            .collect();
-
-        let node_indices: Vec<usize> = graph
-            .node_references()
-            .map(|node_ref| graph.to_index(node_ref.id()))
-            .collect();
-
-        for iteration in 0..iterations {
-            let mut displacements = vec![(0.0f32, 0.0f32); graph.node_bound()];
-
-            // Calculate repulsive forces between all pairs of nodes
-            for i in 0..node_indices.len() {
-                for j in (i + 1)..node_indices.len() {
-                    let idx_i = node_indices[i];
-                    let idx_j = node_indices[j];
-
-                    let delta_x = positions[idx_i].0 - positions[idx_j].0;
-                    let delta_y = positions[idx_i].1 - positions[idx_j].1;
-                    let distance = (delta_x * delta_x + delta_y * delta_y)
-                        .sqrt()
-                        .max(CLIPPING_VALUE);
-
-                    // Repulsive force: f_r = k^2 / d
-                    let repulsion = k * k / distance;
-                    let force_x = (delta_x / distance) * repulsion;
-                    let force_y = (delta_y / distance) * repulsion;
-
-                    displacements[idx_i].0 += force_x;
-                    displacements[idx_i].1 += force_y;
-                    displacements[idx_j].0 -= force_x;
-                    displacements[idx_j].1 -= force_y;
-                }
-            }
+            local_positions.push((x, y));
+        }
+    }
+    let has_pinned_nodes = !pinned.is_clear();
 
-            // Calculate attractive forces along edges
-            for &(source_idx, target_idx) in &edges {
-                let delta_x = positions[source_idx].0 - positions[target_idx].0;
-                let delta_y = positions[source_idx].1 - positions[target_idx].1;
+    // Each edge is stored along with the factor by which its ideal length differs from the ideal
+    // length of an edge of weight 1.0
+    let edges: Vec<(usize, usize, f32)> = graph
+        .edge_references()
+        .map(|edge| {
+            let weight = edge_weight_fn(edge.id());
+            let weight = if weight.is_finite() && weight > 0.0 {
+                weight
+            } else {
+                1.0
+            };
+            let length_factor = match edge_weight_mode {
+                EdgeWeightMode::Attract => 1.0 / weight,
+                EdgeWeightMode::Repel => weight,
+            };
+            (
+                ranks[graph.to_index(edge.source())],
+                ranks[graph.to_index(edge.target())],
+                length_factor,
+            )
+        })
+        .collect();
+
+    let components = connected_components(node_indices.len(), &edges);
+    if has_pinned_nodes || components.len() <= 1 {
+        simulate(
+            &mut local_positions,
+            &edges,
+            &pinned,
+            iterations,
+            initial_temperature,
+            deadline,
+        );
+
+        // Normalize positions to [0.0, 1.0]. With pinned nodes, positions already lie in the unit
+        // square and rescaling would move the pinned nodes away from their coordinates.
+        if !has_pinned_nodes {
+            normalize_axes(&mut local_positions);
+        }
+    } else {
+        layout_components(
+            &mut local_positions,
+            &edges,
+            &components,
+            iterations,
+            initial_temperature,
+            deadline,
+        );
+    }
+
+    let mut positions = vec![(0.0f32, 0.0f32); graph.node_bound()];
+    for (&idx, position) in node_indices.iter().zip(local_positions) {
+        positions[idx] = position;
+    }
+
+    move |node_id| positions[NodeIndexable::to_index(&graph, node_id)]
+}
+
+/// Runs the Fruchterman-Reingold simulation on the given `positions`, which are indexed by the
+/// ranks of the nodes, as are the endpoints of the `edges`. Nodes in `pinned` are not moved. If at
+/// least one node is pinned, free nodes are clamped to the unit square after each iteration. If
+/// the `deadline` has passed, the simulation stops before the next iteration.
+fn simulate(
+    positions: &mut [(f32, f32)],
+    edges: &[(usize, usize, f32)],
+    pinned: &FixedBitSet,
+    iterations: u32,
+    inital_temperature: f32,
+    deadline: Option<Instant>,
+) {
+    let node_count = positions.len();
+    if node_count == 0 {
+        return;
+    }
+    let has_pinned_nodes = !pinned.is_clear();
+
+    // Simulation parameters
+    let k = (1.0 / (node_count as f32)).sqrt();
+
+    for iteration in 0..iterations {
+        if matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+            break;
+        }
+        let mut displacements = vec![(0.0f32, 0.0f32); node_count];
+
+        // Calculate repulsive forces between all pairs of nodes
+        for i in 0..node_count {
+            for j in (i + 1)..node_count {
+                let delta_x = positions[i].0 - positions[j].0;
+                let delta_y = positions[i].1 - positions[j].1;
                 let distance = (delta_x * delta_x + delta_y * delta_y)
                     .sqrt()
                     .max(CLIPPING_VALUE);
 
-                let attraction = distance * distance / k;
-                let force_x = (delta_x / distance) * attraction;
-                let force_y = (delta_y / distance) * attraction;
+                // Repulsive force: f_r = k^2 / d
+                let repulsion = k * k / distance;
+                let force_x = (delta_x / distance) * repulsion;
+                let force_y = (delta_y / distance) * repulsion;
 
-                displacements[source_idx].0 -= force_x;
-                displacements[source_idx].1 -= force_y;
-                displacements[target_idx].0 += force_x;
-                displacements[target_idx].1 += force_y;
+                displacements[i].0 += force_x;
+                displacements[i].1 += force_y;
+                displacements[j].0 -= force_x;
+                displacements[j].1 -= force_y;
             }
+        }
 
-            // Apply displacements with cooling
-            let curr_temp =
-                inital_temperature - (0.1 * iteration as f32) / ((iterations + 1) as f32);
-            for &idx in &node_indices {
-                let disp_len = (displacements[idx].0 * displacements[idx].0
-                    + displacements[idx].1 * displacements[idx].1)
-                    .sqrt();
-
-                if disp_len > 0.0 {
-                    let limited_disp_len = disp_len.min(curr_temp);
-                    positions[idx].0 += (displacements[idx].0 / disp_len) * limited_disp_len;
-                    positions[idx].1 += (displacements[idx].1 / disp_len) * limited_disp_len;
-                }
-            }
+        // Calculate attractive forces along edges
+        for &(source, target, length_factor) in edges {
+            let delta_x = positions[source].0 - positions[target].0;
+            let delta_y = positions[source].1 - positions[target].1;
+            let distance = (delta_x * delta_x + delta_y * delta_y)
+                .sqrt()
+                .max(CLIPPING_VALUE);
+
+            // Attractive force: f_a = d^2 / k
+            let attraction = distance * distance / (k * length_factor);
+            let force_x = (delta_x / distance) * attraction;
+            let force_y = (delta_y / distance) * attraction;
+
+            displacements[source].0 -= force_x;
+            displacements[source].1 -= force_y;
+            displacements[target].0 += force_x;
+            displacements[target].1 += force_y;
         }
 
-        // Normalize positions to [0.0, 1.0]
-        if !positions.is_empty() {
-            let mut min_x = f32::INFINITY;
-            let mut max_x = f32::NEG_INFINITY;
-            let mut min_y = f32::INFINITY;
-            let mut max_y = f32::NEG_INFINITY;
+        // Apply displacements with cooling
+        let curr_temp = inital_temperature - (0.1 * iteration as f32) / ((iterations + 1) as f32);
+        for (idx, position) in positions.iter_mut().enumerate() {
+            if pinned.contains(idx) {
+                continue;
+            }
+            let (disp_x, disp_y) = displacements[idx];
+            let disp_len = (disp_x * disp_x + disp_y * disp_y).sqrt();
 
-            for &idx in &node_indices {
-                min_x = min_x.min(positions[idx].0);
-                max_x = max_x.max(positions[idx].0);
-                min_y = min_y.min(positions[idx].1);
-                max_y = max_y.max(positions[idx].1);
+            if disp_len > 0.0 {
+                let limited_disp_len = disp_len.min(curr_temp);
+                position.0 += (disp_x / disp_len) * limited_disp_len;
+                position.1 += (disp_y / disp_len) * limited_disp_len;
             }
 
-            let range_x = max_x - min_x;
-            let range_y = max_y - min_y;
-
-            if range_x > 0.0 && range_y > 0.0 {
-                for &idx in &node_indices {
-                    positions[idx].0 = (positions[idx].0 - min_x) / range_x;
-                    positions[idx].1 = (positions[idx].1 - min_y) / range_y;
-                }
-            } else if range_x > 0.0 {
-                for &idx in &node_indices {
-                    positions[idx].0 = (positions[idx].0 - min_x) / range_x;
-                    positions[idx].1 = 0.5;
-                }
-            } else if range_y > 0.0 {
-                for &idx in &node_indices {
-                    positions[idx].0 = 0.5;
-                    positions[idx].1 = (positions[idx].1 - min_y) / range_y;
-                }
-            } else {
-                for &idx in &node_indices {
-                    positions[idx] = (0.5, 0.5);
-                }
+            // Pinned nodes define the frame of the drawing, so keep free nodes inside of it
+            if has_pinned_nodes {
+                position.0 = position.0.clamp(0.0, 1.0);
+                position.1 = position.1.clamp(0.0, 1.0);
             }
         }
     }
+}
 
-    move |node_id| positions[NodeIndexable::to_index(&graph, node_id)]
+/// Rescales each axis of the `positions` independently to exactly fill [0.0, 1.0]. If all positions
+/// share a coordinate, that coordinate is set to 0.5.
+fn normalize_axes(positions: &mut [(f32, f32)]) {
+    let ((min_x, min_y), (max_x, max_y)) = bounds(positions);
+    let range_x = max_x - min_x;
+    let range_y = max_y - min_y;
+
+    for position in positions.iter_mut() {
+        position.0 = if range_x > 0.0 {
+            (position.0 - min_x) / range_x
+        } else {
+            0.5
+        };
+        position.1 = if range_y > 0.0 {
+            (position.1 - min_y) / range_y
+        } else {
+            0.5
+        };
+    }
+}
+
+/// Returns the smallest and largest coordinates of the `positions` along both axes.
+fn bounds(positions: &[(f32, f32)]) -> ((f32, f32), (f32, f32)) {
+    positions.iter().fold(
+        (
+            (f32::INFINITY, f32::INFINITY),
+            (f32::NEG_INFINITY, f32::NEG_INFINITY),
+        ),
+        |((min_x, min_y), (max_x, max_y)), &(x, y)| {
+            ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+        },
+    )
+}
+
+/// Returns the ranks of the nodes of each connected component, ignoring the direction of the
+/// `edges`. Components are ordered by their smallest rank.
+fn connected_components(node_count: usize, edges: &[(usize, usize, f32)]) -> Vec<Vec<usize>> {
+    let mut union_find = UnionFind::<usize>::new(node_count);
+    for &(source, target, _) in edges {
+        union_find.union(source, target);
+    }
+
+    let mut component_numbers = HashMap::new();
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    for rank in 0..node_count {
+        let number = *component_numbers
+            .entry(union_find.find(rank))
+            .or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+        components[number].push(rank);
+    }
+    components
+}
+
+/// Lays out each of the connected `components` independently and packs the resulting drawings
+/// into the unit square.
+///
+/// Each component is simulated on its own and scaled such that its area is proportional to its
+/// number of nodes, keeping its aspect ratio. The components are then placed in rows, largest
+/// first, with a gap of [`COMPONENT_GAP`] between them, and the whole packing is scaled uniformly
+/// and centered in [0.0, 1.0] x [0.0, 1.0]. With the `rayon` feature, the components are
+/// simulated in parallel. All simulations stop at the `deadline`.
+fn layout_components(
+    positions: &mut [(f32, f32)],
+    edges: &[(usize, usize, f32)],
+    components: &[Vec<usize>],
+    iterations: u32,
+    inital_temperature: f32,
+    deadline: Option<Instant>,
+) {
+    // Split the positions and edges into the components, using the index within the component
+    let mut component_of = vec![(0, 0); positions.len()];
+    for (number, component) in components.iter().enumerate() {
+        for (local_idx, &rank) in component.iter().enumerate() {
+            component_of[rank] = (number, local_idx);
+        }
+    }
+    let mut component_edges = vec![Vec::new(); components.len()];
+    for &(source, target, length_factor) in edges {
+        let (number, local_source) = component_of[source];
+        let (_, local_target) = component_of[target];
+        component_edges[number].push((local_source, local_target, length_factor));
+    }
+
+    let layout_component = |(component, edges): (&Vec<usize>, &Vec<(usize, usize, f32)>)| {
+        let mut component_positions: Vec<_> =
+            component.iter().map(|&rank| positions[rank]).collect();
+        simulate(
+            &mut component_positions,
+            edges,
+            &FixedBitSet::with_capacity(component.len()),
+            iterations,
+            inital_temperature,
+            deadline,
+        );
+
+        // Scale the drawing such that its larger side is the square root of the number of nodes
+        let ((min_x, min_y), (max_x, max_y)) = bounds(&component_positions);
+        let span = (max_x - min_x).max(max_y - min_y);
+        let scale = if span > 0.0 {
+            (component.len() as f32).sqrt() / span
+        } else {
+            0.0
+        };
+        for position in &mut component_positions {
+            *position = ((position.0 - min_x) * scale, (position.1 - min_y) * scale);
+        }
+        let size = ((max_x - min_x) * scale, (max_y - min_y) * scale);
+        (component_positions, size)
+    };
+
+    #[cfg(not(feature = "rayon"))]
+    let layouts: Vec<_> = components
+        .iter()
+        .zip(&component_edges)
+        .map(layout_component)
+        .collect();
+
+    #[cfg(feature = "rayon")]
+    let layouts: Vec<_> = {
+        use rayon::prelude::*;
+
+        components
+            .par_iter()
+            .zip(&component_edges)
+            .map(layout_component)
+            .collect()
+    };
+
+    // Place the components in rows of roughly the side length of a square of the same total area,
+    // starting with the tallest components
+    let cells: Vec<(f32, f32)> = layouts
+        .iter()
+        .map(|(_, (width, height))| (width + COMPONENT_GAP, height + COMPONENT_GAP))
+        .collect();
+    let total_area: f32 = cells.iter().map(|(width, height)| width * height).sum();
+    let row_width = cells
+        .iter()
+        .map(|&(width, _)| width)
+        .fold(total_area.sqrt(), f32::max);
+    let mut order: Vec<usize> = (0..cells.len()).collect();
+    order.sort_by(|&a, &b| cells[b].1.total_cmp(&cells[a].1));
+
+    let mut offsets = vec![(0.0, 0.0); cells.len()];
+    let (mut x, mut y, mut row_height) = (0.0f32, 0.0f32, 0.0f32);
+    for number in order {
+        let (width, height) = cells[number];
+        if x > 0.0 && x + width > row_width {
+            x = 0.0;
+            y += row_height;
+            row_height = 0.0;
+        }
+        offsets[number] = (x, y);
+        x += width;
+        row_height = row_height.max(height);
+    }
+
+    for ((component, (component_positions, _)), (offset_x, offset_y)) in
+        components.iter().zip(layouts).zip(offsets)
+    {
+        for (&rank, (x, y)) in component.iter().zip(component_positions) {
+            positions[rank] = (x + offset_x, y + offset_y);
+        }
+    }
+
+    // Scale the packing uniformly, such that the components keep their shapes
+    let ((min_x, min_y), (max_x, max_y)) = bounds(positions);
+    let span = (max_x - min_x).max(max_y - min_y);
+    let scale = if span > 0.0 { 1.0 / span } else { 1.0 };
+    let (center_x, center_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+    for position in positions.iter_mut() {
+        // Clamp to guard against rounding errors at the borders
+        *position = (
+            (0.5 + (position.0 - center_x) * scale).clamp(0.0, 1.0),
+            (0.5 + (position.1 - center_y) * scale).clamp(0.0, 1.0),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    use petgraph::graph::UnGraph;
+
+    use super::{
+        force_directed_layout, force_directed_layout_with_params, ForceDirectedParams,
+        DEFAULT_INITIAL_TEMPERATURE, DEFAULT_ITERATIONS,
+    };
+
+    #[test]
+    fn test_pinned_nodes_keep_their_positions() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        for (i, &node) in nodes.iter().enumerate() {
+            graph.add_edge(node, nodes[(i + 1) % nodes.len()], ());
+        }
+
+        let graph = &graph;
+        let params = ForceDirectedParams {
+            pinned_positions: HashMap::from([(nodes[0], (0.1, 0.2)), (nodes[3], (0.9, 0.7))]),
+            ..ForceDirectedParams::default()
+        };
+        let position_map = force_directed_layout_with_params(&graph, &params, |_| 1.0);
+
+        assert_eq!(position_map(nodes[0]), (0.1, 0.2));
+        assert_eq!(position_map(nodes[3]), (0.9, 0.7));
+        for node in nodes {
+            let (x, y) = position_map(node);
+            assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+        }
+    }
+
+    /// Builds two triangles connected by a bridge edge with the provided weight and returns the
+    /// distance between the endpoints of the bridge in the weighted layout. The initial positions
+    /// are seeded, such that the result is deterministic.
+    fn bridge_length(bridge_weight: f32) -> f32 {
+        let mut graph = UnGraph::<(), f32>::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        for triangle in nodes.chunks(3) {
+            graph.add_edge(triangle[0], triangle[1], 1.0);
+            graph.add_edge(triangle[1], triangle[2], 1.0);
+            graph.add_edge(triangle[2], triangle[0], 1.0);
+        }
+        graph.add_edge(nodes[0], nodes[3], bridge_weight);
+
+        let graph = &graph;
+        let params = ForceDirectedParams {
+            seed: Some(42),
+            ..ForceDirectedParams::default()
+        };
+        let position_map =
+            force_directed_layout_with_params(&graph, &params, |edge_id| graph[edge_id]);
+
+        let (x_0, y_0) = position_map(nodes[0]);
+        let (x_3, y_3) = position_map(nodes[3]);
+        ((x_0 - x_3).powi(2) + (y_0 - y_3).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_heavy_edges_are_shorter() {
+        let heavy_bridge_length = bridge_length(10.0);
+        let light_bridge_length = bridge_length(0.1);
+
+        assert!(
+            heavy_bridge_length < light_bridge_length,
+            "Heavy bridge: {heavy_bridge_length}, light bridge: {light_bridge_length}"
+        );
+    }
+
+    #[test]
+    fn test_disconnected_components_are_packed() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        let triangles: Vec<_> = nodes.chunks(3).collect();
+        for triangle in &triangles {
+            graph.add_edge(triangle[0], triangle[1], ());
+            graph.add_edge(triangle[1], triangle[2], ());
+            graph.add_edge(triangle[2], triangle[0], ());
+        }
+
+        let graph = &graph;
+        let position_map =
+            force_directed_layout(&graph, DEFAULT_ITERATIONS, DEFAULT_INITIAL_TEMPERATURE);
+        let distance = |a, b| {
+            let ((x_a, y_a), (x_b, y_b)) = (position_map(a), position_map(b));
+            ((x_a - x_b).powi(2) + (y_a - y_b).powi(2)).sqrt()
+        };
+
+        let mut bounding_boxes = Vec::new();
+        for triangle in &triangles {
+            // The triangles are roughly equilateral
+            let sides = [
+                distance(triangle[0], triangle[1]),
+                distance(triangle[1], triangle[2]),
+                distance(triangle[2], triangle[0]),
+            ];
+            let (shortest, longest) = (
+                sides.iter().copied().fold(f32::INFINITY, f32::min),
+                sides.iter().copied().fold(0.0, f32::max),
+            );
+            assert!(longest < 1.2 * shortest, "{sides:?}");
+
+            let xs = triangle.iter().map(|&node| position_map(node).0);
+            let ys = triangle.iter().map(|&node| position_map(node).1);
+            bounding_boxes.push((
+                (
+                    xs.clone().fold(f32::INFINITY, f32::min),
+                    xs.fold(f32::NEG_INFINITY, f32::max),
+                ),
+                (
+                    ys.clone().fold(f32::INFINITY, f32::min),
+                    ys.fold(f32::NEG_INFINITY, f32::max),
+                ),
+            ));
+        }
+
+        // The bounding boxes of the triangles are disjoint
+        let [((min_x_a, max_x_a), (min_y_a, max_y_a)), ((min_x_b, max_x_b), (min_y_b, max_y_b))] =
+            [bounding_boxes[0], bounding_boxes[1]];
+        assert!(max_x_a < min_x_b || max_x_b < min_x_a || max_y_a < min_y_b || max_y_b < min_y_a);
+
+        for node in nodes {
+            let (x, y) = position_map(node);
+            assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+        }
+    }
+
+    #[test]
+    fn test_time_budget_stops_simulation() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..300).map(|_| graph.add_node(())).collect();
+        for (i, &node) in nodes.iter().enumerate() {
+            graph.add_edge(node, nodes[(i + 1) % nodes.len()], ());
+        }
+
+        // Without a budget, this takes minutes
+        let graph = &graph;
+        let start = Instant::now();
+        let params = ForceDirectedParams {
+            iterations: 100_000,
+            time_budget: Some(Duration::from_millis(10)),
+            ..ForceDirectedParams::default()
+        };
+        let position_map = force_directed_layout_with_params(&graph, &params, |_| 1.0);
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "{:?}",
+            start.elapsed()
+        );
+
+        for node in nodes {
+            let (x, y) = position_map(node);
+            assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+        }
+    }
+
+    #[test]
+    fn test_seed_reproduces_layout() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        for (i, &node) in nodes.iter().enumerate() {
+            graph.add_edge(node, nodes[(i + 1) % nodes.len()], ());
+        }
+
+        let graph = &graph;
+        let positions = |seed| {
+            let params = ForceDirectedParams {
+                seed: Some(seed),
+                ..ForceDirectedParams::default()
+            };
+            let position_map = force_directed_layout_with_params(&graph, &params, |_| 1.0);
+            nodes
+                .iter()
+                .map(|&node| position_map(node))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(positions(42), positions(42));
+        assert_ne!(positions(42), positions(7));
+    }
 }
@@ -1,10 +1,13 @@
 //! Module containing functionality for the hierarchical layout.
 //!
 //! The main function is [`hierarchical_layout`], which returns a position map function that
-//! arranges nodes in a hierarchical layout.
+//! arranges nodes in a hierarchical layout. Use [`hierarchical_layout_with_params`] to configure
+//! the spacing between levels and siblings.
 
 use fixedbitset::FixedBitSet;
-use petgraph::visit::{IntoNeighborsDirected, IntoNodeReferences, NodeIndexable, NodeRef};
+use petgraph::visit::{
+    GraphProp, IntoNeighborsDirected, IntoNodeReferences, NodeIndexable, NodeRef,
+};
 
 #[derive(Debug, Clone, Copy, Default)]
 /// Orientation for hierarchical layout. Top to Bottom is the default.
@@ -20,6 +23,25 @@ pub enum Orientation {
     RightToLeft,
 }
 
+/// Number of sweeps of [`reduce_crossings`], each consisting of a downward and an upward pass.
+const CROSSING_REDUCTION_SWEEPS: usize = 4;
+
+/// Spacing of the nodes placed by [`hierarchical_layout_with_params`].
+///
+/// Both spacings are given in normalized coordinates along the respective axis, i.e. as a fraction
+/// of the width or height of the drawing area, and are independent of the orientation. If the
+/// nodes do not fit into [0.0, 1.0] with the given spacing, they are spaced evenly across the
+/// range instead. Otherwise, they are centered in the range.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HierarchicalParams {
+    /// Distance between two consecutive levels (rows). The default of `None` spreads the levels
+    /// evenly across [0.0, 1.0].
+    pub level_spacing: Option<f32>,
+    /// Distance between two adjacent siblings, i.e. neighboring columns. The default of `None`
+    /// spreads the columns evenly across [0.0, 1.0].
+    pub sibling_spacing: Option<f32>,
+}
+
 /// Returns a position map function that arranges nodes in a hierarchical layout.
 ///
 /// The returned position map is normalized to [0.0, 1.0]. The provided orientation determines the
@@ -28,51 +50,130 @@ pub enum Orientation {
 /// The basic structure of the algorithm is as follows:
 /// - Identify root nodes (nodes with no incoming edges). If none are found, use nodes with the
 ///   highest out-degree as starting points.
-/// - Perform a depth-first traversal from each root node, assigning levels (rows) to nodes based on
-///   their distance from the root.
-/// - Calculate the column positions for each node, centering parents above their children.
+/// - Compute a depth-first spanning forest starting from the root nodes. Only the edges of this
+///   forest (tree edges) are used to determine the hierarchy.
+/// - For undirected graphs, the above steps are replaced by a breadth-first spanning forest, see
+///   [Undirected graphs](#undirected-graphs).
+/// - Assign levels (rows) to nodes based on their depth in the spanning forest.
+/// - Calculate the column positions for each node, centering parents above their children. The
+///   subtrees of siblings, as well as the trees of the forest, are placed next to each other as
+///   closely as possible without overlapping on any level, such that narrow subtrees do not take
+///   up the width of wide ones.
+/// - Reorder the nodes within each level to reduce the number of crossings between edges of
+///   adjacent levels, see [Crossing reduction](#crossing-reduction).
 /// - Normalize the positions to fit within a unit square, adjusting based on the specified
 ///   orientation.
+///
+/// # Cycles
+///
+/// Graphs containing cycles do not have a natural hierarchy. Restricting the leveling to the tree
+/// edges of the spanning forest breaks all cycles: every edge closing a cycle (back edge) is
+/// ignored when assigning levels and is simply drawn between the already positioned nodes
+/// afterward. For example, the directed cycle `a -> b -> c -> a` is laid out as the chain
+/// `a -> b -> c` on three distinct levels, with the edge `c -> a` pointing back upward.
+///
+/// # Crossing reduction
+///
+/// Edges which are not part of the spanning forest may cross other edges. To reduce such crossings,
+/// the nodes of each level are repeatedly sorted by the median position of their neighbors on the
+/// previous level, sweeping down and up through the levels. The nodes keep the columns of their
+/// level, only their order changes. The new order is only used if it has fewer crossings between
+/// edges of adjacent levels than the original one, such that e.g. trees are laid out as before.
+///
+/// # Undirected graphs
+///
+/// Edges of undirected graphs have no direction to derive the hierarchy from. Instead, a
+/// pseudo-peripheral node of each connected component, i.e. a node which is about as far away from
+/// all other nodes as possible, is used as root, and each node is placed on the level given by its
+/// distance from the root. For example, a path is laid out as a chain from one of its ends to the
+/// other.
 pub fn hierarchical_layout<G>(
     graph: &G,
     orientation: Orientation,
 ) -> impl Fn(G::NodeId) -> (f32, f32) + '_
 where
-    G: IntoNodeReferences + NodeIndexable + IntoNeighborsDirected,
+    G: IntoNodeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+{
+    hierarchical_layout_with_params(graph, orientation, HierarchicalParams::default())
+}
+
+/// Same as [`hierarchical_layout`], but spaces levels and siblings as given by `params`.
+///
+/// For example, a small sibling spacing keeps narrow trees from being stretched across the whole
+/// width of the drawing area.
+pub fn hierarchical_layout_with_params<G>(
+    graph: &G,
+    orientation: Orientation,
+    params: HierarchicalParams,
+) -> impl Fn(G::NodeId) -> (f32, f32) + '_
+where
+    G: IntoNodeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
 {
-    // Use FixedBitSet and Vec with node bound for better performance
-    let mut visited = FixedBitSet::with_capacity(graph.node_bound());
     let mut positions = vec![(0.0, 0.0); graph.node_bound()];
+    let mut shifts = vec![0.0; graph.node_bound()];
+
+    let (roots, tree_children) = spanning_forest(graph);
+
+    // Assign levels starting from the roots of the spanning forest, placing the trees next to each
+    // other like the subtrees of siblings
+    let mut contour = Vec::new();
+    for &root in &roots {
+        let root_contour = assign_levels(&tree_children, &mut positions, &mut shifts, root, 0);
+        shifts[root] = pack_subtree(&mut contour, &root_contour);
+    }
+    let max_row = contour.len().saturating_sub(1);
+    let min_col = contour
+        .iter()
+        .map(|&(left, _)| left)
+        .fold(f32::INFINITY, f32::min);
+    let width = contour
+        .iter()
+        .map(|&(_, right)| right - min_col)
+        .fold(0.0, f32::max);
+    for &root in &roots {
+        apply_shifts(&tree_children, &mut positions, &shifts, root, -min_col);
+    }
 
-    let mut next_col = 0;
-    let roots = graph
+    let node_indices: Vec<usize> = graph
         .node_references()
-        .filter(|node_ref| {
-            graph
-                .neighbors_directed(node_ref.id(), petgraph::Direction::Incoming)
-                .next()
-                .is_none()
-        })
         .map(|node_ref| NodeIndexable::to_index(&graph, node_ref.id()))
-        .collect::<Vec<_>>();
-
-    let mut max_row = 0;
-    let mut max_col = 0;
+        .collect();
+    reduce_crossings(graph, &mut positions, &node_indices, max_row);
 
-    // Assign levels starting from root nodes
-    for root in roots {
-        if visited.contains(root) {
-            continue;
-        }
+    normalize_positions(&mut positions, width, max_row, orientation, params);
 
-        let (curr_max_col, curr_max_row) =
-            assign_levels(graph, &mut visited, &mut positions, root, next_col, 0);
+    move |node_id| positions[NodeIndexable::to_index(&graph, node_id)]
+}
 
-        max_row = max_row.max(curr_max_row);
-        max_col = max_col.max(curr_max_col);
-        next_col = curr_max_col + 1;
+/// Computes a depth-first spanning forest of the graph.
+///
+/// Returns the roots of the trees in the order they were started from and, for each node index,
+/// the children of that node along tree edges. Nodes with no incoming edges are used as roots
+/// first. Nodes which are not reachable from those (e.g. nodes on cycles) are covered by starting
+/// from the remaining nodes in descending order of their out-degree.
+///
+/// For undirected graphs, a breadth-first spanning forest is computed instead, see
+/// [`undirected_spanning_forest`].
+fn spanning_forest<G>(graph: &G) -> (Vec<usize>, Vec<Vec<usize>>)
+where
+    G: IntoNodeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+{
+    if !graph.is_directed() {
+        return undirected_spanning_forest(graph);
     }
 
+    // Use FixedBitSet and Vec with node bound for better performance
+    let mut visited = FixedBitSet::with_capacity(graph.node_bound());
+    let mut tree_children = vec![Vec::new(); graph.node_bound()];
+    let mut roots = Vec::new();
+
+    let nodes_without_incoming_edges = graph.node_references().filter(|node_ref| {
+        graph
+            .neighbors_directed(node_ref.id(), petgraph::Direction::Incoming)
+            .next()
+            .is_none()
+    });
+
     // We might not find any roots, especially in undirected graphs. This is the backup.
     let all_nodes_sorted_by_desc_deg = {
         let mut nodes: Vec<_> = graph.node_references().collect();
@@ -84,42 +185,104 @@ where
         nodes.reverse();
         nodes
     };
-    for root in all_nodes_sorted_by_desc_deg
-        .iter()
+
+    for root in nodes_without_incoming_edges
+        .chain(all_nodes_sorted_by_desc_deg)
         .map(|node_ref| NodeIndexable::to_index(&graph, node_ref.id()))
     {
         if visited.contains(root) {
             continue;
         }
 
-        let (curr_max_col, curr_max_row) =
-            assign_levels(graph, &mut visited, &mut positions, root, next_col, 0);
-
-        max_row = max_row.max(curr_max_row);
-        max_col = max_col.max(curr_max_col);
-        next_col = curr_max_col + 1;
+        build_tree(graph, &mut visited, &mut tree_children, root);
+        roots.push(root);
     }
 
-    normalize_positions(&mut positions, max_col, max_row, orientation);
+    (roots, tree_children)
+}
 
-    move |node_id| positions[NodeIndexable::to_index(&graph, node_id)]
+/// Computes a breadth-first spanning forest of an undirected graph with one tree per connected
+/// component, returned like by [`spanning_forest`].
+///
+/// Each tree is rooted at a pseudo-peripheral node, found by two sweeps of breadth-first search:
+/// starting from any node of the component, the last node reached is searched from again, and the
+/// last node reached by that second search is the root. This yields deep and narrow trees, such
+/// that e.g. paths are laid out as chains.
+fn undirected_spanning_forest<G>(graph: &G) -> (Vec<usize>, Vec<Vec<usize>>)
+where
+    G: IntoNodeReferences + NodeIndexable + IntoNeighborsDirected,
+{
+    let mut visited = FixedBitSet::with_capacity(graph.node_bound());
+    let mut sweep_visited = FixedBitSet::with_capacity(graph.node_bound());
+    let mut tree_children = vec![Vec::new(); graph.node_bound()];
+    let mut roots = Vec::new();
+
+    for node_ref in graph.node_references() {
+        let start = NodeIndexable::to_index(&graph, node_ref.id());
+        if visited.contains(start) {
+            continue;
+        }
+
+        let mut root = start;
+        for _ in 0..2 {
+            let order = breadth_first_tree(graph, root, &mut sweep_visited);
+            root = order.last().map_or(root, |&(node, _)| node);
+            // Only reset the nodes of this component, since the others are not reached anyway
+            for (node, _) in order {
+                sweep_visited.set(node, false);
+            }
+        }
+
+        for (node, parent) in breadth_first_tree(graph, root, &mut visited) {
+            if let Some(parent) = parent {
+                tree_children[parent].push(node);
+            }
+        }
+        roots.push(root);
+    }
+
+    (roots, tree_children)
 }
 
-fn assign_levels<G>(
+/// Visits the nodes reachable from `start` which are not yet `visited` in breadth-first order and
+/// marks them as visited.
+///
+/// Returns the node indices in the order they were visited, each with the index of the node it was
+/// reached from, i.e. its parent in the breadth-first tree. Only `start` has no parent.
+fn breadth_first_tree<G>(
     graph: &G,
+    start: usize,
     visited: &mut FixedBitSet,
-    positions: &mut Vec<(f32, f32)>,
-    node: usize,
-    start_col: usize,
-    row: usize,
-) -> (usize, usize)
+) -> Vec<(usize, Option<usize>)>
 where
     G: IntoNeighborsDirected + NodeIndexable,
 {
-    if visited.contains(node) {
-        return (start_col, row);
+    visited.insert(start);
+    let mut order = vec![(start, None)];
+    let mut next = 0;
+    while let Some(&(node, _)) = order.get(next) {
+        next += 1;
+        for neighbor in
+            graph.neighbors_directed(graph.from_index(node), petgraph::Direction::Outgoing)
+        {
+            let neighbor = graph.to_index(neighbor);
+            if !visited.put(neighbor) {
+                order.push((neighbor, Some(node)));
+            }
+        }
     }
 
+    order
+}
+
+fn build_tree<G>(
+    graph: &G,
+    visited: &mut FixedBitSet,
+    tree_children: &mut [Vec<usize>],
+    node: usize,
+) where
+    G: IntoNeighborsDirected + NodeIndexable,
+{
     visited.insert(node);
 
     let children: Vec<usize> = graph
@@ -127,76 +290,499 @@ where
         .map(|child| graph.to_index(child))
         .collect();
 
-    let mut child_positions = Vec::new();
-    let mut child_col = start_col;
-    let mut max_col = start_col;
-    let mut max_row = row;
-
     for child in children {
         if visited.contains(child) {
             continue;
         }
 
-        let (child_max_col, child_max_row) =
-            assign_levels(graph, visited, positions, child, child_col, row + 1);
+        tree_children[node].push(child);
+        build_tree(graph, visited, tree_children, child);
+    }
+}
 
-        child_positions.push(positions[child]);
+/// Assigns the row of each node in the subtree of `node` and lays out the columns of the subtree,
+/// centering each parent between its leftmost and rightmost child.
+///
+/// The columns are relative to the subtree: the column of `node` is stored in `positions`, while
+/// each child's subtree is laid out on its own and moved right next to the subtrees of its previous
+/// siblings by the shift stored in `shifts`, see [`pack_subtree`]. The shifts are resolved into
+/// absolute columns by [`apply_shifts`].
+///
+/// Returns the contour of the subtree, i.e. the leftmost and rightmost column on each level,
+/// starting with the level of `node`.
+fn assign_levels(
+    tree_children: &[Vec<usize>],
+    positions: &mut [(f32, f32)],
+    shifts: &mut [f32],
+    node: usize,
+    row: usize,
+) -> Vec<(f32, f32)> {
+    let mut contour = Vec::new();
+    let mut child_cols = Vec::new();
 
-        max_col = max_col.max(child_max_col);
-        max_row = max_row.max(child_max_row);
-        child_col = child_max_col + 1;
+    for &child in &tree_children[node] {
+        let child_contour = assign_levels(tree_children, positions, shifts, child, row + 1);
+        shifts[child] = pack_subtree(&mut contour, &child_contour);
+        child_cols.push(positions[child].0 + shifts[child]);
     }
 
-    let parent_col = if !child_positions.is_empty() {
-        let leftmost = child_positions.first().unwrap().0;
-        let rightmost = child_positions.last().unwrap().0;
-        (leftmost + rightmost) / 2.0
+    let parent_col = match (child_cols.first(), child_cols.last()) {
+        (Some(leftmost), Some(rightmost)) => (leftmost + rightmost) / 2.0,
+        _ => 0.0,
+    };
+    positions[node] = (parent_col, row as f32);
+
+    contour.insert(0, (parent_col, parent_col));
+    contour
+}
+
+/// Returns the shift which places a subtree with the given contour right of the subtrees of the
+/// `contour`, keeping a distance of at least one column on every level both have in common, and
+/// adds the shifted subtree to the `contour`. The first subtree is not shifted.
+fn pack_subtree(contour: &mut Vec<(f32, f32)>, subtree_contour: &[(f32, f32)]) -> f32 {
+    let shift = if contour.is_empty() {
+        0.0
     } else {
-        start_col as f32
+        contour
+            .iter()
+            .zip(subtree_contour)
+            .map(|(&(_, right), &(left, _))| right + 1.0 - left)
+            .fold(f32::NEG_INFINITY, f32::max)
     };
 
-    positions[node] = (parent_col, row as f32);
+    for (level, &(left, right)) in subtree_contour.iter().enumerate() {
+        match contour.get_mut(level) {
+            // The shifted subtree lies right of the previous ones on all common levels
+            Some((_, contour_right)) => *contour_right = right + shift,
+            None => contour.push((left + shift, right + shift)),
+        }
+    }
+
+    shift
+}
 
-    (max_col, max_row)
+/// Turns the relative columns computed by [`assign_levels`] into absolute columns by adding the
+/// shifts of each node and all of its ancestors, as well as `offset`.
+fn apply_shifts(
+    tree_children: &[Vec<usize>],
+    positions: &mut [(f32, f32)],
+    shifts: &[f32],
+    node: usize,
+    offset: f32,
+) {
+    let offset = offset + shifts[node];
+    positions[node].0 += offset;
+    for &child in &tree_children[node] {
+        apply_shifts(tree_children, positions, shifts, child, offset);
+    }
+}
+
+/// Reorders the nodes within each row to reduce the number of crossings between edges connecting
+/// adjacent rows, using the median heuristic.
+///
+/// The rows are swept downward and upward [`CROSSING_REDUCTION_SWEEPS`] times. In each pass, the
+/// nodes of a row are sorted by the median order of their neighbors in the previously visited row,
+/// keeping nodes without such neighbors at their current order. The order with the fewest crossings
+/// is kept if it improves on the original one, with each row reusing its original columns.
+fn reduce_crossings<G>(
+    graph: &G,
+    positions: &mut [(f32, f32)],
+    node_indices: &[usize],
+    max_row: usize,
+) where
+    G: IntoNeighborsDirected + NodeIndexable + GraphProp,
+{
+    let mut rows = vec![Vec::new(); max_row + 1];
+    for &idx in node_indices {
+        rows[positions[idx].1 as usize].push(idx);
+    }
+    for row in &mut rows {
+        row.sort_by(|&a, &b| positions[a].0.total_cmp(&positions[b].0));
+    }
+
+    // Edges between adjacent rows as (upper, lower) pairs, grouped by the row of the upper node.
+    // Undirected edges are reported from both of their nodes, so only keep them once.
+    let mut edges = vec![Vec::new(); max_row];
+    let mut upper_neighbors = vec![Vec::new(); positions.len()];
+    let mut lower_neighbors = vec![Vec::new(); positions.len()];
+    for &idx in node_indices {
+        let row = positions[idx].1;
+        for neighbor in
+            graph.neighbors_directed(graph.from_index(idx), petgraph::Direction::Outgoing)
+        {
+            let neighbor = graph.to_index(neighbor);
+            let neighbor_row = positions[neighbor].1;
+            let (upper, lower) = if neighbor_row == row + 1.0 {
+                (idx, neighbor)
+            } else if neighbor_row + 1.0 == row && graph.is_directed() {
+                (neighbor, idx)
+            } else {
+                continue;
+            };
+            edges[positions[upper].1 as usize].push((upper, lower));
+            upper_neighbors[lower].push(upper);
+            lower_neighbors[upper].push(lower);
+        }
+    }
+
+    let mut order = vec![0; positions.len()];
+    for row in &rows {
+        for (position, &idx) in row.iter().enumerate() {
+            order[idx] = position;
+        }
+    }
+
+    let initial_crossings = count_crossings(&edges, &order);
+    let mut best = (initial_crossings, rows.clone());
+    for _ in 0..CROSSING_REDUCTION_SWEEPS {
+        if best.0 == 0 {
+            break;
+        }
+        for row in rows.iter_mut().skip(1) {
+            sort_by_median(row, &upper_neighbors, &mut order);
+        }
+        for row in rows.iter_mut().rev().skip(1) {
+            sort_by_median(row, &lower_neighbors, &mut order);
+        }
+
+        let crossings = count_crossings(&edges, &order);
+        if crossings < best.0 {
+            best = (crossings, rows.clone());
+        }
+    }
+
+    if best.0 < initial_crossings {
+        for row in best.1 {
+            let mut columns: Vec<f32> = row.iter().map(|&idx| positions[idx].0).collect();
+            columns.sort_by(f32::total_cmp);
+            for (idx, col) in row.into_iter().zip(columns) {
+                positions[idx].0 = col;
+            }
+        }
+    }
+}
+
+/// Sorts the nodes of a row by the median of the `order` of their `neighbors` and updates the
+/// `order` accordingly. Nodes without neighbors keep their current order as sort key.
+fn sort_by_median(row: &mut [usize], neighbors: &[Vec<usize>], order: &mut [usize]) {
+    let mut keyed: Vec<(f32, usize)> = row
+        .iter()
+        .map(|&idx| {
+            let mut neighbor_order: Vec<usize> = neighbors[idx]
+                .iter()
+                .map(|&neighbor| order[neighbor])
+                .collect();
+            neighbor_order.sort_unstable();
+            let mid = neighbor_order.len() / 2;
+            let key = match neighbor_order.len() {
+                0 => order[idx] as f32,
+                len if len % 2 == 1 => neighbor_order[mid] as f32,
+                _ => (neighbor_order[mid - 1] + neighbor_order[mid]) as f32 / 2.0,
+            };
+            (key, idx)
+        })
+        .collect();
+    // The sort is stable, such that ties keep their current order
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    for (position, (slot, (_, idx))) in row.iter_mut().zip(keyed).enumerate() {
+        *slot = idx;
+        order[idx] = position;
+    }
+}
+
+/// Returns the number of pairs of edges between the same two adjacent rows which cross, given the
+/// `order` of the nodes within their rows.
+fn count_crossings(edges: &[Vec<(usize, usize)>], order: &[usize]) -> usize {
+    edges
+        .iter()
+        .map(|row_edges| {
+            row_edges
+                .iter()
+                .enumerate()
+                .map(|(i, &(upper_a, lower_a))| {
+                    row_edges[i + 1..]
+                        .iter()
+                        .filter(|&&(upper_b, lower_b)| {
+                            (order[upper_a] < order[upper_b] && order[lower_a] > order[lower_b])
+                                || (order[upper_a] > order[upper_b]
+                                    && order[lower_a] < order[lower_b])
+                        })
+                        .count()
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Returns the scale and offset which map the coordinates [0, `max`] along one axis into
+/// [0.0, 1.0] such that consecutive coordinates are `spacing` apart, if possible.
+fn axis_transform(max: f32, spacing: Option<f32>) -> (f32, f32) {
+    let fill_scale = if max > 0.0 { 1.0 / max } else { 1.0 };
+    match spacing {
+        None => (fill_scale, 0.0),
+        Some(spacing) => {
+            let scale = if max > 0.0 {
+                spacing.min(fill_scale)
+            } else {
+                spacing
+            };
+            (scale, (1.0 - max * scale) / 2.0)
+        }
+    }
 }
 
 fn normalize_positions(
     positions: &mut [(f32, f32)],
-    max_col: usize,
+    max_col: f32,
     max_row: usize,
     orientation: Orientation,
+    params: HierarchicalParams,
 ) {
-    let row_scale = if max_row > 0 {
-        1.0 / max_row as f32
-    } else {
-        1.0
-    };
-    let col_scale = if max_col > 0 {
-        1.0 / max_col as f32
-    } else {
-        1.0
-    };
+    let (row_scale, row_offset) = axis_transform(max_row as f32, params.level_spacing);
+    let (col_scale, col_offset) = axis_transform(max_col, params.sibling_spacing);
 
     for (col, row) in positions.iter_mut() {
-        match orientation {
-            Orientation::TopToBottom => {
-                *row *= row_scale;
-                *col *= col_scale;
-            }
-            Orientation::BottomToTop => {
-                *row = 1.0 - (*row * row_scale);
-                *col *= col_scale;
-            }
-            Orientation::LeftToRight => {
-                let temp = *row;
-                *row = *col * col_scale;
-                *col = temp * row_scale;
-            }
-            Orientation::RightToLeft => {
-                let temp = *row;
-                *row = *col * col_scale;
-                *col = 1.0 - (temp * row_scale);
+        // If all nodes lie in a single column, center them
+        let normalized_col = if max_col <= 0.0 {
+            0.5
+        } else {
+            col_offset + *col * col_scale
+        };
+        let normalized_row = row_offset + *row * row_scale;
+
+        (*col, *row) = match orientation {
+            Orientation::TopToBottom => (normalized_col, normalized_row),
+            Orientation::BottomToTop => (normalized_col, 1.0 - normalized_row),
+            Orientation::LeftToRight => (normalized_row, normalized_col),
+            Orientation::RightToLeft => (1.0 - normalized_row, normalized_col),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{DiGraph, NodeIndex, UnGraph};
+
+    use super::{
+        super::metrics::count_edge_crossings, hierarchical_layout, hierarchical_layout_with_params,
+        reduce_crossings, HierarchicalParams, Orientation,
+    };
+
+    #[test]
+    fn test_hierarchical_layout_on_cycle() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let node_a = graph.add_node(());
+        let node_b = graph.add_node(());
+        let node_c = graph.add_node(());
+        graph.add_edge(node_a, node_b, ());
+        graph.add_edge(node_b, node_c, ());
+        graph.add_edge(node_c, node_a, ());
+
+        let graph = &graph;
+        let position_map = hierarchical_layout(&graph, Orientation::TopToBottom);
+
+        let mut levels: Vec<f32> = [node_a, node_b, node_c]
+            .into_iter()
+            .map(|node| position_map(node).1)
+            .collect();
+        levels.sort_by(f32::total_cmp);
+        assert_eq!(levels, vec![0.0, 0.5, 1.0]);
+
+        // Following the cycle, each node is one level below its predecessor, except for the edge
+        // closing the cycle.
+        let (_, level_a) = position_map(node_a);
+        let (_, level_b) = position_map(node_b);
+        let (_, level_c) = position_map(node_c);
+        let steps = [level_b - level_a, level_c - level_b, level_a - level_c];
+        assert_eq!(steps.iter().filter(|&&step| step == 0.5).count(), 2);
+    }
+
+    #[test]
+    fn test_hierarchical_layout_on_undirected_path() {
+        // Path 0 - 1 - 2 - 3 - 4, with the nodes added in shuffled order along the path
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..5).map(|_| graph.add_node(())).collect();
+        for pair in [2, 0, 3, 1, 4].windows(2) {
+            graph.add_edge(nodes[pair[0]], nodes[pair[1]], ());
+        }
+
+        let graph = &graph;
+        let position_map = hierarchical_layout(&graph, Orientation::TopToBottom);
+
+        // The path is laid out as a vertical chain from one end to the other
+        let mut positions: Vec<_> = nodes.iter().map(|&node| position_map(node)).collect();
+        assert!(positions.iter().all(|&(x, _)| x == 0.5));
+        let ends = (positions[nodes[2].index()].1, positions[nodes[4].index()].1);
+        assert!(ends == (0.0, 1.0) || ends == (1.0, 0.0));
+        positions.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let steps: Vec<_> = positions.iter().map(|&(_, y)| y).collect();
+        assert_eq!(steps, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_reduce_crossings() {
+        // Three layers of three nodes, where each node is connected to the node in the mirrored
+        // column of the next layer
+        let mut graph = DiGraph::<(), ()>::new();
+        let nodes: Vec<_> = (0..9).map(|_| graph.add_node(())).collect();
+        for layer in 0..2 {
+            for col in 0..3 {
+                let lower = nodes[3 * (layer + 1) + 2 - col];
+                graph.add_edge(nodes[3 * layer + col], lower, ());
             }
         }
+        graph.add_edge(nodes[0], nodes[4], ());
+
+        let mut positions: Vec<(f32, f32)> = (0..9)
+            .map(|idx| ((idx % 3) as f32, (idx / 3) as f32))
+            .collect();
+        let crossings = |positions: &[(f32, f32)]| {
+            count_edge_crossings(&graph, &|node: NodeIndex| positions[node.index()])
+        };
+        let before = crossings(&positions);
+        let node_indices: Vec<usize> = (0..9).collect();
+        reduce_crossings(&&graph, &mut positions, &node_indices, 2);
+        let after = crossings(&positions);
+
+        assert_eq!(before, 7);
+        assert!(after < before);
+        // Nodes keep their rows and the columns of each row are reused
+        for (idx, &(col, row)) in positions.iter().enumerate() {
+            assert_eq!(row, (idx / 3) as f32);
+            assert!([0.0, 1.0, 2.0].contains(&col));
+        }
+    }
+
+    #[test]
+    fn test_hierarchical_layout_reduces_crossings() {
+        // The tree of `a` is laid out first, such that the edge from `b` to the first child of `a`
+        // crosses the edge to its second child, unless the children are swapped
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        let d = graph.add_node(());
+        graph.add_edge(a, c, ());
+        graph.add_edge(a, d, ());
+        graph.add_edge(b, c, ());
+
+        let graph = &graph;
+        let position_map = hierarchical_layout(&graph, Orientation::TopToBottom);
+
+        let order = |upper, lower| position_map(upper).0 < position_map(lower).0;
+        assert_eq!(order(a, b), order(d, c));
+    }
+
+    #[test]
+    fn test_hierarchical_layout_centers_rows() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let root = graph.add_node(());
+        let child = graph.add_node(());
+        let grandchildren: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(root, child, ());
+        for &grandchild in &grandchildren {
+            graph.add_edge(child, grandchild, ());
+        }
+
+        let graph = &graph;
+        let position_map = hierarchical_layout(&graph, Orientation::TopToBottom);
+
+        let mut grandchildren_x: Vec<f32> = grandchildren
+            .iter()
+            .map(|&node| position_map(node).0)
+            .collect();
+        grandchildren_x.sort_by(f32::total_cmp);
+        assert_eq!(grandchildren_x, vec![0.0, 0.5, 1.0]);
+        assert_eq!(position_map(root).0, 0.5);
+        assert_eq!(position_map(child).0, 0.5);
+    }
+
+    #[test]
+    fn test_hierarchical_layout_centers_parents_over_uneven_children() {
+        // The root has a leaf and an inner node with three children as its children, such that
+        // the subtrees of the two children have different widths
+        let mut graph = DiGraph::<(), ()>::new();
+        let root = graph.add_node(());
+        let leaf = graph.add_node(());
+        let inner = graph.add_node(());
+        graph.add_edge(root, leaf, ());
+        graph.add_edge(root, inner, ());
+        let grandchildren: Vec<_> = (0..3)
+            .map(|_| {
+                let grandchild = graph.add_node(());
+                graph.add_edge(inner, grandchild, ());
+                grandchild
+            })
+            .collect();
+
+        let graph = &graph;
+        let position_map = hierarchical_layout(&graph, Orientation::TopToBottom);
+        let x = |node| position_map(node).0;
+        let mean = |nodes: &[NodeIndex]| {
+            nodes.iter().map(|&node| x(node)).sum::<f32>() / nodes.len() as f32
+        };
+
+        // The widest level spans the full width
+        let mut grandchildren_x: Vec<f32> = grandchildren.iter().map(|&node| x(node)).collect();
+        grandchildren_x.sort_by(f32::total_cmp);
+        assert_eq!(grandchildren_x, vec![0.0, 0.5, 1.0]);
+
+        assert_eq!(x(inner), mean(&grandchildren));
+        assert_eq!(x(root), mean(&[leaf, inner]));
+        assert!((x(leaf) - x(inner)).abs() >= 0.5);
+    }
+
+    #[test]
+    fn test_hierarchical_layout_sibling_spacing() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let root = graph.add_node(());
+        let children: Vec<_> = (0..3).map(|_| graph.add_node(())).collect();
+        for &child in &children {
+            graph.add_edge(root, child, ());
+        }
+
+        let graph = &graph;
+        let horizontal_extent = |sibling_spacing| {
+            let params = HierarchicalParams {
+                sibling_spacing,
+                ..HierarchicalParams::default()
+            };
+            let position_map =
+                hierarchical_layout_with_params(&graph, Orientation::TopToBottom, params);
+            let xs: Vec<f32> = children.iter().map(|&node| position_map(node).0).collect();
+            let min_x = xs.iter().copied().fold(f32::INFINITY, f32::min);
+            let max_x = xs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            // Rows stay centered
+            assert!((position_map(root).0 - 0.5).abs() < 1e-6);
+            max_x - min_x
+        };
+
+        let narrow = horizontal_extent(Some(0.1));
+        let wide = horizontal_extent(Some(0.2));
+        assert!((narrow - 0.2).abs() < 1e-6);
+        assert!((wide - 0.4).abs() < 1e-6);
+        // Spacings which do not fit are spread evenly across the range instead
+        assert!((horizontal_extent(Some(1.0)) - 1.0).abs() < 1e-6);
+        assert!((horizontal_extent(None) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_hierarchical_layout_level_spacing() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let root = graph.add_node(());
+        let child = graph.add_node(());
+        graph.add_edge(root, child, ());
+
+        let graph = &graph;
+        let params = HierarchicalParams {
+            level_spacing: Some(0.25),
+            ..HierarchicalParams::default()
+        };
+        let position_map =
+            hierarchical_layout_with_params(&graph, Orientation::LeftToRight, params);
+
+        assert_eq!(position_map(root), (0.375, 0.5));
+        assert_eq!(position_map(child), (0.625, 0.5));
     }
 }
@@ -0,0 +1,124 @@
+//! Module containing quality metrics of layouts.
+//!
+//! The metrics can be used to compare layouts programmatically, e.g. to select the best out of
+//! several runs of the force-directed layout with different seeds. The main functions are
+//! [`count_edge_crossings`] and [`edge_length_variance`], which both take a graph and a position
+//! map, such as the ones returned by the layout functions of this crate.
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+/// Returns the number of pairs of edges which cross each other when drawn as straight lines
+/// between the positions of their endpoints. Fewer crossings usually make a drawing more readable.
+///
+/// Edges sharing an endpoint are never counted as crossing, neither are self-loops. Edges which
+/// merely touch or overlap collinearly are not counted either.
+///
+/// Note that every pair of edges is checked, so the running time is quadratic in the number of
+/// edges.
+pub fn count_edge_crossings<G>(graph: G, position_map: &impl Fn(G::NodeId) -> (f32, f32)) -> usize
+where
+    G: IntoEdgeReferences,
+{
+    let segments: Vec<_> = graph
+        .edge_references()
+        .filter(|edge| edge.source() != edge.target())
+        .map(|edge| {
+            (
+                edge.source(),
+                edge.target(),
+                position_map(edge.source()),
+                position_map(edge.target()),
+            )
+        })
+        .collect();
+
+    let mut crossings = 0;
+    for (i, &(source_a, target_a, start_a, end_a)) in segments.iter().enumerate() {
+        for &(source_b, target_b, start_b, end_b) in &segments[i + 1..] {
+            let share_endpoint = source_a == source_b
+                || source_a == target_b
+                || target_a == source_b
+                || target_a == target_b;
+            if !share_endpoint && segments_cross((start_a, end_a), (start_b, end_b)) {
+                crossings += 1;
+            }
+        }
+    }
+    crossings
+}
+
+/// Returns the variance of the lengths of all edges when drawn as straight lines between the
+/// positions of their endpoints. A low variance means the edges have similar lengths, which
+/// usually makes a drawing look more uniform.
+///
+/// Returns 0.0 for graphs without edges.
+pub fn edge_length_variance<G>(graph: G, position_map: &impl Fn(G::NodeId) -> (f32, f32)) -> f32
+where
+    G: IntoEdgeReferences,
+{
+    let lengths: Vec<f32> = graph
+        .edge_references()
+        .map(|edge| {
+            let (source_x, source_y) = position_map(edge.source());
+            let (target_x, target_y) = position_map(edge.target());
+            ((target_x - source_x).powi(2) + (target_y - source_y).powi(2)).sqrt()
+        })
+        .collect();
+    if lengths.is_empty() {
+        return 0.0;
+    }
+
+    let count = lengths.len() as f32;
+    let mean = lengths.iter().sum::<f32>() / count;
+    lengths
+        .iter()
+        .map(|length| (length - mean).powi(2))
+        .sum::<f32>()
+        / count
+}
+
+type Segment = ((f32, f32), (f32, f32));
+
+/// Returns whether the two segments properly cross, that is, the endpoints of each segment lie
+/// strictly on different sides of the other segment.
+fn segments_cross((start_a, end_a): Segment, (start_b, end_b): Segment) -> bool {
+    let orientation = |(ax, ay): (f32, f32), (bx, by): (f32, f32), (cx, cy): (f32, f32)| {
+        (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+    };
+
+    let side_start_b = orientation(start_a, end_a, start_b);
+    let side_end_b = orientation(start_a, end_a, end_b);
+    let side_start_a = orientation(start_b, end_b, start_a);
+    let side_end_a = orientation(start_b, end_b, end_a);
+
+    side_start_b * side_end_b < 0.0 && side_start_a * side_end_a < 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{NodeIndex, UnGraph};
+
+    use super::{count_edge_crossings, edge_length_variance};
+
+    #[test]
+    fn test_count_edge_crossings_on_k4_square() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                graph.add_edge(nodes[i], nodes[j], ());
+            }
+        }
+
+        // Nodes at the corners of a square, in order, such that only the diagonals cross
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let position_map = |node_id: NodeIndex| corners[node_id.index()];
+
+        assert_eq!(count_edge_crossings(&graph, &position_map), 1);
+        // Four sides of length 1 and two diagonals of length sqrt(2)
+        let mean = (4.0 + 2.0 * 2f32.sqrt()) / 6.0;
+        let expected_variance =
+            (4.0 * (1.0 - mean).powi(2) + 2.0 * (2f32.sqrt() - mean).powi(2)) / 6.0;
+        assert!((edge_length_variance(&graph, &position_map) - expected_variance).abs() < 1e-6);
+    }
+}
@@ -6,17 +6,45 @@
 //! The layout algorithms can also be called directly from their respective
 //! submodules of this module.
 
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
-use petgraph::graph::NodeIndex;
+use petgraph::{
+    graph::NodeIndex,
+    visit::{
+        GraphProp, IntoEdgeReferences, IntoNeighborsDirected, IntoNodeReferences, NodeIndexable,
+        NodeRef,
+    },
+};
 
-use crate::layout::hierarchical::Orientation;
+use crate::layout::{
+    auto::select_layout,
+    bipartite::bipartite_layout,
+    circular::circular_layout,
+    clustered::clustered_layout,
+    force_directed::{force_directed_layout, DEFAULT_INITIAL_TEMPERATURE, DEFAULT_ITERATIONS},
+    hex_grid::hex_grid_layout,
+    hierarchical::{hierarchical_layout, Orientation},
+    random::random_layout,
+    spiral::spiral_layout,
+};
 
 pub(crate) type DefaultPositionMapFn = fn(NodeIndex) -> (f32, f32);
 
+/// Maximum number of separation rounds in [`resolve_overlaps`].
+const MAX_OVERLAP_ITERATIONS: u32 = 100;
+/// Angle used to spread coincident nodes in different directions, see [`resolve_overlaps`].
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+pub mod auto;
 pub mod bipartite;
+pub mod cache;
+pub mod clustered;
 pub mod force_directed;
 pub mod hierarchical;
+pub mod metrics;
 
 /// Different layout algorithms for graph visualization.
 ///
@@ -31,6 +59,8 @@ pub mod hierarchical;
 #[derive(Debug, Clone)]
 pub enum Layout {
     /// Nodes are arranged in a [circular layout](https://en.wikipedia.org/wiki/Circular_layout).
+    /// On non-square canvases, the circle is fit into the shorter side of the drawing area, such
+    /// that it stays circular.
     ///
     /// See [`circular_layout`][crate::layout::circular::circular_layout] for more details or
     /// calling the layout function directly.
@@ -56,6 +86,33 @@ pub enum Layout {
     /// See [`random_layout`][crate::layout::random::random_layout] for more details or calling the
     /// layout function directly.
     Random,
+    /// Nodes are arranged along an [Archimedean spiral](https://en.wikipedia.org/wiki/Archimedean_spiral)
+    /// by index, starting at the center. Like for [`Layout::Circular`], the aspect ratio of the
+    /// spiral is preserved on non-square canvases.
+    ///
+    /// See [`spiral_layout`][crate::layout::spiral::spiral_layout] for more details or calling the
+    /// layout function directly.
+    Spiral,
+    /// Nodes are grouped by community.
+    ///
+    /// The provided `Vec` contains the community of each node, indexed by the node index. See
+    /// [`clustered_layout`][crate::layout::clustered::clustered_layout] for more details or calling
+    /// the layout function directly.
+    Clustered(Vec<usize>),
+    /// Nodes are packed on a hexagonal grid by index, in rows which are alternately shifted by half
+    /// a cell. Like for [`Layout::Circular`], the aspect ratio of the grid is preserved on
+    /// non-square canvases.
+    ///
+    /// See [`hex_grid_layout`][crate::layout::hex_grid::hex_grid_layout] for more details or
+    /// calling the layout function directly.
+    HexGrid,
+    /// One of the other layouts is selected based on the structure of the graph, e.g. the
+    /// hierarchical layout for trees and the circular layout for dense graphs. The selection is
+    /// deterministic.
+    ///
+    /// See [`select_layout`][crate::layout::auto::select_layout] for the exact rules or selecting
+    /// the layout directly.
+    Auto,
 }
 
 /// Enum to represent either a layout algorithm or a custom position map function. Only used for
@@ -68,6 +125,244 @@ pub enum LayoutOrPositionMap<PositionMapFn = DefaultPositionMapFn> {
     PositionMap(PositionMapFn),
 }
 
+/// Modes for bringing positions into the normalized range [0.0, 1.0] before they are scaled to the
+/// canvas. Only used for [`SettingsBuilder`][crate::settings::SettingsBuilder].
+///
+/// The built-in layouts already return normalized positions. This is mostly useful for custom
+/// position maps, which might return positions outside of [0.0, 1.0] and thus place nodes outside
+/// of the canvas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NormalizeMode {
+    /// Positions are used as is. This is the default.
+    #[default]
+    None,
+    /// Each coordinate is clamped to [0.0, 1.0]. Nodes outside of the range end up on the border of
+    /// the drawing area.
+    Clamp,
+    /// Positions are rescaled such that the bounding box of all positions exactly fills
+    /// [0.0, 1.0] x [0.0, 1.0]. If all nodes share a coordinate, that coordinate is set to 0.5.
+    Fit,
+}
+
+/// Directions of the y-axis of normalized positions. Only used for
+/// [`SettingsBuilder`][crate::settings::SettingsBuilder].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum YAxis {
+    /// The y-axis points down, i.e. a y of 0.0 is at the top of the drawing area, like in SVG. This
+    /// is the default.
+    #[default]
+    Down,
+    /// The y-axis points up, i.e. a y of 0.0 is at the bottom of the drawing area, like in most
+    /// mathematical plots.
+    Up,
+}
+
+/// Computes the positions of all nodes of the graph using the provided layout algorithm.
+///
+/// The returned positions are normalized to [0.0, 1.0], that is, they are the positions before
+/// being scaled to the canvas. This is useful to feed the coordinates into a custom renderer or to
+/// post-process them and pass them back as a position map.
+///
+/// The force-directed layout is computed with [`DEFAULT_ITERATIONS`] and
+/// [`DEFAULT_INITIAL_TEMPERATURE`] and without pinned positions or edge weights.
+pub fn compute_positions<G>(graph: G, layout: &Layout) -> HashMap<G::NodeId, (f32, f32)>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+    G::NodeId: Hash + Eq,
+{
+    collect_positions(graph, layout)
+}
+
+/// Result of [`compute_layout`]: The normalized positions of all nodes along with their bounding
+/// box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutResult<NodeId = NodeIndex> {
+    /// The position of each node, in the order of the node references of the graph.
+    pub positions: Vec<(NodeId, (f32, f32))>,
+    /// The bounding box of all positions as `(min_x, min_y, max_x, max_y)`. For graphs without
+    /// nodes, this is `(0.0, 0.0, 0.0, 0.0)`.
+    pub bbox: (f32, f32, f32, f32),
+}
+
+/// Computes the positions of all nodes of the graph using the provided layout algorithm, along with
+/// their bounding box.
+///
+/// Like [`compute_positions`], the positions are normalized to [0.0, 1.0]. The bounding box tells
+/// which part of that range is actually covered, e.g. to arrange the drawings of several graphs
+/// next to each other in one figure.
+pub fn compute_layout<G>(graph: G, layout: &Layout) -> LayoutResult<G::NodeId>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+    G::NodeId: Hash + Eq,
+{
+    let positions: Vec<_> = collect_positions(graph, layout);
+    let bbox = positions
+        .iter()
+        .map(|&(_, (x, y))| (x, y, x, y))
+        .reduce(|(min_x, min_y, max_x, max_y), (x, y, _, _)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        })
+        .unwrap_or((0.0, 0.0, 0.0, 0.0));
+
+    LayoutResult { positions, bbox }
+}
+
+/// Returns a position map which looks up the position of each node in `positions`, e.g. positions
+/// computed by [`compute_positions`] or loaded from a file.
+///
+/// The returned closure can be passed to
+/// [`SettingsBuilder::position_map`](crate::settings::SettingsBuilder::position_map), which
+/// decouples computing an expensive layout from rendering it. Like for any position map, the
+/// positions should be normalized to [0.0, 1.0]. Nodes without a position are placed at the center
+/// (0.5, 0.5).
+pub fn positions_from_map<N>(positions: HashMap<N, (f32, f32)>) -> impl Fn(N) -> (f32, f32) + Clone
+where
+    N: Hash + Eq + Clone,
+{
+    move |node_id| positions.get(&node_id).copied().unwrap_or((0.5, 0.5))
+}
+
+/// Computes the positions of all nodes of the graph using the provided layout algorithm and
+/// collects them in the order of the node references of the graph.
+fn collect_positions<G, C>(graph: G, layout: &Layout) -> C
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + IntoNeighborsDirected + GraphProp,
+    G::NodeId: Hash + Eq,
+    C: FromIterator<(G::NodeId, (f32, f32))>,
+{
+    fn collect<G, C>(graph: G, position_map: impl Fn(G::NodeId) -> (f32, f32)) -> C
+    where
+        G: IntoNodeReferences,
+        C: FromIterator<(G::NodeId, (f32, f32))>,
+    {
+        graph
+            .node_references()
+            .map(|node| (node.id(), position_map(node.id())))
+            .collect()
+    }
+
+    match layout {
+        Layout::Circular => collect(graph, circular_layout(&graph)),
+        Layout::Hierarchical(orientation) => {
+            collect(graph, hierarchical_layout(&graph, *orientation))
+        }
+        Layout::ForceDirected => collect(
+            graph,
+            force_directed_layout(&graph, DEFAULT_ITERATIONS, DEFAULT_INITIAL_TEMPERATURE),
+        ),
+        Layout::Bipartite(left_partition) => {
+            collect(graph, bipartite_layout(&graph, left_partition.as_ref()))
+        }
+        Layout::Random => collect(graph, random_layout(&graph)),
+        Layout::Spiral => collect(graph, spiral_layout(&graph)),
+        Layout::Clustered(communities) => collect(graph, clustered_layout(&graph, communities)),
+        Layout::HexGrid => collect(graph, hex_grid_layout(&graph)),
+        Layout::Auto => collect_positions(graph, &select_layout(graph)),
+    }
+}
+
+/// Nudges overlapping nodes apart until no two circles of the provided `radius` overlap, or a
+/// maximum number of rounds is reached.
+///
+/// The `positions` are normalized to [0.0, 1.0] and are mapped to a drawing area of size
+/// `canvas = (width, height)` in pixels, in which the `radius` is given. In each round, every pair
+/// of nodes closer than `2 * radius` is pushed apart symmetrically along the line through their
+/// centers. Nodes sharing the exact same position are pushed apart in a direction depending on
+/// their indices.
+///
+/// Afterwards, the positions are renormalized: Coordinates which left [0.0, 1.0] are shifted back
+/// into the range or, if the nodes span more than the range, scaled down to fit into it. Note that
+/// scaling down can reintroduce small overlaps for very crowded drawings.
+pub fn resolve_overlaps(positions: &mut [(f32, f32)], radius: f32, canvas: (f32, f32)) {
+    let (width, height) = canvas;
+    if positions.len() < 2 || radius <= 0.0 || width <= 0.0 || height <= 0.0 {
+        return;
+    }
+    let min_distance = 2.0 * radius;
+
+    let mut pixel_positions: Vec<_> = positions
+        .iter()
+        .map(|&(x, y)| (x * width, y * height))
+        .collect();
+
+    for _ in 0..MAX_OVERLAP_ITERATIONS {
+        let mut moved = false;
+        for i in 0..pixel_positions.len() {
+            for j in (i + 1)..pixel_positions.len() {
+                let (x_i, y_i) = pixel_positions[i];
+                let (x_j, y_j) = pixel_positions[j];
+                let (delta_x, delta_y) = (x_j - x_i, y_j - y_i);
+                let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+                if distance >= min_distance {
+                    continue;
+                }
+                moved = true;
+
+                let (unit_x, unit_y) = if distance < f32::EPSILON {
+                    let angle = (i + j) as f32 * GOLDEN_ANGLE;
+                    (angle.cos(), angle.sin())
+                } else {
+                    (delta_x / distance, delta_y / distance)
+                };
+                let push = (min_distance - distance) / 2.0;
+                pixel_positions[i] = (x_i - unit_x * push, y_i - unit_y * push);
+                pixel_positions[j] = (x_j + unit_x * push, y_j + unit_y * push);
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+
+    for (position, (pixel_x, pixel_y)) in positions.iter_mut().zip(pixel_positions) {
+        *position = (pixel_x / width, pixel_y / height);
+    }
+    renormalize_axis(positions, |position| &mut position.0);
+    renormalize_axis(positions, |position| &mut position.1);
+}
+
+/// Returns the rank of each node in the order of the node references of the graph, indexed by the
+/// index of the node.
+///
+/// For graphs with holes in the index space, e.g. a `StableGraph` with removed nodes, the ranks are
+/// consecutive, whereas the indices are not. Slots of removed nodes are set to 0.
+fn node_ranks<G>(graph: &G) -> Vec<usize>
+where
+    G: IntoNodeReferences + NodeIndexable,
+{
+    let mut ranks = vec![0; graph.node_bound()];
+    for (rank, node) in graph.node_references().enumerate() {
+        ranks[graph.to_index(node.id())] = rank;
+    }
+    ranks
+}
+
+/// Brings the coordinates selected by `axis` back into [0.0, 1.0] by shifting them or, if they span
+/// more than the range, by scaling them to exactly fill it.
+fn renormalize_axis(positions: &mut [(f32, f32)], axis: impl Fn(&mut (f32, f32)) -> &mut f32) {
+    let (mut min, mut max) = (f32::INFINITY, f32::NEG_INFINITY);
+    for position in positions.iter_mut() {
+        let coordinate = *axis(position);
+        min = min.min(coordinate);
+        max = max.max(coordinate);
+    }
+    if min >= 0.0 && max <= 1.0 {
+        return;
+    }
+
+    let span = max - min;
+    for position in positions.iter_mut() {
+        let coordinate = axis(position);
+        *coordinate = if span > 1.0 {
+            (*coordinate - min) / span
+        } else if min < 0.0 {
+            *coordinate - min
+        } else {
+            *coordinate - (max - 1.0)
+        };
+    }
+}
+
 pub mod circular {
     //! Module containing functionality for the circular layout.
     //!
@@ -75,6 +370,26 @@ pub mod circular {
     //! arranges nodes in a circular layout.
     use petgraph::visit::{IntoNodeReferences, NodeIndexable};
 
+    /// Parameters of the ring on which the [`circular_layout_with_params`] places the nodes.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct CircularParams {
+        /// Radius of the ring in normalized coordinates. The default of 0.5 lets the ring touch
+        /// the borders of the unit square. Smaller radii leave room around the ring, e.g. for
+        /// large nodes.
+        pub radius: f32,
+        /// Center of the ring in normalized coordinates. The default is (0.5, 0.5).
+        pub center: (f32, f32),
+    }
+
+    impl Default for CircularParams {
+        fn default() -> Self {
+            CircularParams {
+                radius: 0.5,
+                center: (0.5, 0.5),
+            }
+        }
+    }
+
     /// Returns a position map function that arranges nodes in a circular layout.
     ///
     /// The nodes are evenly distributed around a unit circle centered at (0.5, 0.5). That is
@@ -86,12 +401,32 @@ pub mod circular {
     where
         G: IntoNodeReferences + NodeIndexable,
     {
+        circular_layout_with_params(graph, CircularParams::default())
+    }
+
+    /// Same as [`circular_layout`], but places the nodes on the ring with the radius and center
+    /// given by `params`.
+    ///
+    /// The returned positions only lie in the range [0.0, 1.0] if the ring fits into the unit
+    /// square, that is, if the center is at least the radius away from each border.
+    pub fn circular_layout_with_params<G>(
+        graph: &G,
+        params: CircularParams,
+    ) -> impl Fn(G::NodeId) -> (f32, f32) + '_
+    where
+        G: IntoNodeReferences + NodeIndexable,
+    {
+        let ranks = super::node_ranks(graph);
         let node_count = graph.node_references().count() as f32;
+        let CircularParams {
+            radius,
+            center: (center_x, center_y),
+        } = params;
         move |node_id| {
-            let index = graph.to_index(node_id) as f32;
+            let index = ranks[graph.to_index(node_id)] as f32;
             let angle = index / node_count * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
-            let x = 0.5 + 0.5 * angle.cos();
-            let y = 0.5 + 0.5 * angle.sin();
+            let x = center_x + radius * angle.cos();
+            let y = center_y + radius * angle.sin();
             (x, y)
         }
     }
@@ -126,3 +461,374 @@ pub mod random {
         }
     }
 }
+
+pub mod spiral {
+    //! Module containing functionality for the spiral layout.
+    //!
+    //! The main function is [`spiral_layout`], which returns a position map function that arranges
+    //! nodes along an Archimedean spiral.
+    use petgraph::visit::{IntoNodeReferences, NodeIndexable};
+
+    /// Parameters of the spiral along which the [`spiral_layout_with_params`] places the nodes.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct SpiralParams {
+        /// Number of full turns of the spiral from the first to the last node. The default is 3.0.
+        pub turns: f32,
+        /// Radial distance between two consecutive turns in normalized coordinates. The default of
+        /// 1/6 lets the outermost turn touch the borders of the unit square for the default number of
+        /// turns. If the spiral does not fit into the unit square, it is scaled down to fit.
+        pub spacing: f32,
+    }
+
+    impl Default for SpiralParams {
+        fn default() -> Self {
+            SpiralParams {
+                turns: 3.0,
+                spacing: 1.0 / 6.0,
+            }
+        }
+    }
+
+    /// Returns a position map function that arranges nodes along an Archimedean spiral.
+    ///
+    /// The node with the first (usually index 0) index is placed at the center (0.5, 0.5) and
+    /// following nodes are placed at evenly increasing angles in a clockwise manner, such that their
+    /// distance from the center grows with their index. This is a compact, deterministic layout,
+    /// e.g. for ordered data such as timelines.
+    ///
+    /// The returned position map is normalized to [0.0, 1.0].
+    pub fn spiral_layout<G>(graph: &G) -> impl Fn(G::NodeId) -> (f32, f32) + '_
+    where
+        G: IntoNodeReferences + NodeIndexable,
+    {
+        spiral_layout_with_params(graph, SpiralParams::default())
+    }
+
+    /// Same as [`spiral_layout`], but uses the number of turns and the spacing between turns given
+    /// by `params`.
+    pub fn spiral_layout_with_params<G>(
+        graph: &G,
+        params: SpiralParams,
+    ) -> impl Fn(G::NodeId) -> (f32, f32) + '_
+    where
+        G: IntoNodeReferences + NodeIndexable,
+    {
+        let ranks = super::node_ranks(graph);
+        let last_index = graph.node_references().count().saturating_sub(1).max(1) as f32;
+        let SpiralParams { turns, spacing } = params;
+        // Scale the spiral down if its outermost turn leaves the unit square
+        let max_radius = (spacing * turns).abs();
+        let spacing = if max_radius > 0.5 {
+            spacing * 0.5 / max_radius
+        } else {
+            spacing
+        };
+        move |node_id| {
+            let progress = ranks[graph.to_index(node_id)] as f32 / last_index;
+            let angle = progress * turns * std::f32::consts::TAU;
+            let radius = spacing * progress * turns;
+            let x = 0.5 + radius * (angle - std::f32::consts::FRAC_PI_2).cos();
+            let y = 0.5 + radius * (angle - std::f32::consts::FRAC_PI_2).sin();
+            (x, y)
+        }
+    }
+}
+
+pub mod hex_grid {
+    //! Module containing functionality for the hexagonal grid layout.
+    //!
+    //! The main function is [`hex_grid_layout`], which returns a position map function that packs
+    //! nodes on a hexagonal grid.
+    use petgraph::visit::{IntoNodeReferences, NodeIndexable};
+
+    /// Returns a position map function that packs nodes on a hexagonal grid.
+    ///
+    /// The nodes are placed in rows from left to right and top to bottom by index, where every
+    /// second row is shifted to the right by half a cell. Consecutive rows are `sqrt(3) / 2` cells
+    /// apart, such that each node has the same distance to its up to six neighbors on the grid. The
+    /// number of nodes per row is chosen such that the grid is roughly square. Compared to a square
+    /// grid, this packs dense graphs more tightly.
+    ///
+    /// The grid is scaled uniformly to fit into [0.0, 1.0] and centered along its shorter side. A
+    /// single node is placed at the center (0.5, 0.5).
+    pub fn hex_grid_layout<G>(graph: &G) -> impl Fn(G::NodeId) -> (f32, f32) + '_
+    where
+        G: IntoNodeReferences + NodeIndexable,
+    {
+        let ranks = super::node_ranks(graph);
+        let node_count = graph.node_references().count();
+        let row_spacing = 3.0f32.sqrt() / 2.0;
+
+        // Choose the number of columns such that width and height of the grid roughly match
+        let columns = ((node_count as f32 * row_spacing).sqrt().round() as usize).max(1);
+        let rows = ((node_count + columns - 1) / columns).max(1);
+        let width = (columns - 1) as f32 + if rows > 1 { 0.5 } else { 0.0 };
+        let height = (rows - 1) as f32 * row_spacing;
+        let extent = width.max(height);
+        let scale = if extent > 0.0 { 1.0 / extent } else { 0.0 };
+
+        move |node_id| {
+            let rank = ranks[graph.to_index(node_id)];
+            let (row, column) = (rank / columns, rank % columns);
+            let shift = if row % 2 == 1 { 0.5 } else { 0.0 };
+            let x = 0.5 + (column as f32 + shift - width / 2.0) * scale;
+            let y = 0.5 + (row as f32 * row_spacing - height / 2.0) * scale;
+            (x, y)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::{
+        graph::{NodeIndex, UnGraph},
+        stable_graph::StableGraph,
+        Undirected,
+    };
+
+    use crate::{
+        layout::{
+            circular::{circular_layout, circular_layout_with_params, CircularParams},
+            compute_layout, compute_positions,
+            hex_grid::hex_grid_layout,
+            positions_from_map, resolve_overlaps,
+            spiral::{spiral_layout, spiral_layout_with_params, SpiralParams},
+            Layout,
+        },
+        Orientation,
+    };
+
+    /// All layouts, with the hierarchical layout in every orientation.
+    fn all_layouts() -> Vec<Layout> {
+        vec![
+            Layout::Circular,
+            Layout::Hierarchical(Orientation::TopToBottom),
+            Layout::Hierarchical(Orientation::BottomToTop),
+            Layout::Hierarchical(Orientation::LeftToRight),
+            Layout::Hierarchical(Orientation::RightToLeft),
+            Layout::ForceDirected,
+            Layout::Bipartite(None),
+            Layout::Random,
+            Layout::Spiral,
+            Layout::Clustered(vec![0, 0, 1, 1, 1, 1]),
+            Layout::HexGrid,
+            Layout::Auto,
+        ]
+    }
+
+    #[test]
+    fn test_compute_positions_circular() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        for i in 0..4 {
+            graph.add_edge(nodes[i], nodes[(i + 1) % 4], ());
+        }
+
+        let positions = compute_positions(&graph, &Layout::Circular);
+
+        let graph = &graph;
+        let position_map = circular_layout(&graph);
+        assert_eq!(positions.len(), 4);
+        for node in nodes {
+            assert_eq!(positions[&node], position_map(node));
+        }
+    }
+
+    #[test]
+    fn test_positions_from_map_with_other_node_ids() {
+        let position_map =
+            positions_from_map(std::collections::HashMap::from([("a", (0.25, 0.75))]));
+        assert_eq!(position_map("a"), (0.25, 0.75));
+        assert_eq!(position_map("b"), (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_compute_layout_bbox() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        for window in nodes.windows(2) {
+            graph.add_edge(window[0], window[1], ());
+        }
+
+        for layout in all_layouts() {
+            let result = compute_layout(&graph, &layout);
+            assert_eq!(result.positions.len(), nodes.len());
+            assert!(result
+                .positions
+                .iter()
+                .map(|&(node, _)| node)
+                .eq(nodes.iter().copied()));
+
+            // Every position lies within the bounding box and every side of the box is touched
+            let (min_x, min_y, max_x, max_y) = result.bbox;
+            for &(_, (x, y)) in &result.positions {
+                assert!((min_x..=max_x).contains(&x) && (min_y..=max_y).contains(&y));
+            }
+            for (side, coordinate) in [(min_x, 0), (max_x, 0), (min_y, 1), (max_y, 1)] {
+                assert!(result
+                    .positions
+                    .iter()
+                    .any(|&(_, (x, y))| { (if coordinate == 0 { x } else { y }) == side }));
+            }
+        }
+
+        let empty = UnGraph::<(), ()>::new_undirected();
+        assert_eq!(
+            compute_layout(&empty, &Layout::Circular).bbox,
+            (0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_hex_grid_layout_offsets_rows() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..12).map(|_| graph.add_node(())).collect();
+        let graph = &graph;
+        let position_map = hex_grid_layout(&graph);
+        let positions: Vec<_> = nodes.iter().map(|&node| position_map(node)).collect();
+
+        // 12 nodes are packed in rows of 3
+        let columns = positions
+            .iter()
+            .take_while(|&&(_, y)| y == positions[0].1)
+            .count();
+        assert_eq!(columns, 3);
+        let cell = positions[1].0 - positions[0].0;
+        assert!(cell > 0.0);
+        for (rank, &(x, y)) in positions.iter().enumerate() {
+            assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+            let (row, column) = (rank / columns, rank % columns);
+            // Odd rows are shifted to the right by half a cell
+            let shift = if row % 2 == 1 { 0.5 } else { 0.0 };
+            assert!((x - (positions[0].0 + (column as f32 + shift) * cell)).abs() < 1e-6);
+            assert!((y - (positions[0].1 + row as f32 * 3.0f32.sqrt() / 2.0 * cell)).abs() < 1e-6);
+        }
+
+        let mut single_node_graph = UnGraph::<(), ()>::new_undirected();
+        let node = single_node_graph.add_node(());
+        let single_node_graph = &single_node_graph;
+        assert_eq!(hex_grid_layout(&single_node_graph)(node), (0.5, 0.5));
+    }
+
+    #[test]
+    fn test_circular_layout_radius() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..12).map(|_| graph.add_node(())).collect();
+
+        let params = CircularParams {
+            radius: 0.4,
+            ..CircularParams::default()
+        };
+        let graph = &graph;
+        let position_map = circular_layout_with_params(&graph, params);
+
+        for node in nodes {
+            let (x, y) = position_map(node);
+            assert!((0.1 - 1e-6..=0.9 + 1e-6).contains(&x), "x = {x}");
+            assert!((0.1 - 1e-6..=0.9 + 1e-6).contains(&y), "y = {y}");
+        }
+        // The first node is placed at the top of the ring
+        let (x, y) = position_map(NodeIndex::new(0));
+        assert!((x - 0.5).abs() < 1e-6 && (y - 0.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_spiral_layout_radius_grows() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..20).map(|_| graph.add_node(())).collect();
+        let graph = &graph;
+
+        let distance_from_center =
+            |(x, y): (f32, f32)| ((x - 0.5).powi(2) + (y - 0.5).powi(2)).sqrt();
+        for params in [
+            SpiralParams::default(),
+            SpiralParams {
+                turns: 5.0,
+                spacing: 0.2,
+            },
+        ] {
+            let position_map = spiral_layout_with_params(&graph, params);
+            let distances: Vec<_> = nodes
+                .iter()
+                .map(|&node| distance_from_center(position_map(node)))
+                .collect();
+
+            assert!(distances[0] < 1e-6);
+            assert!(distances.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(distances.iter().all(|&distance| distance <= 0.5 + 1e-6));
+        }
+
+        let (x, y) = spiral_layout(&graph)(nodes[19]);
+        assert!((0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y));
+    }
+
+    #[test]
+    fn test_layouts_on_trivial_graphs() {
+        let empty_graph = UnGraph::<(), ()>::new_undirected();
+        let mut single_node_graph = UnGraph::<(), ()>::new_undirected();
+        let node = single_node_graph.add_node(());
+
+        for layout in all_layouts() {
+            assert!(compute_positions(&empty_graph, &layout).is_empty());
+
+            let positions = compute_positions(&single_node_graph, &layout);
+            let (x, y) = positions[&node];
+            assert!(
+                (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y),
+                "{layout:?} placed the single node at {:?}",
+                (x, y)
+            );
+        }
+    }
+
+    #[test]
+    fn test_layouts_on_stable_graph_with_holes() {
+        // A path 0 - 1 - 3 - 4 - 5 after removing the middle node 2
+        let mut graph = StableGraph::<(), (), Undirected>::with_capacity(6, 5);
+        let nodes: Vec<_> = (0..6).map(|_| graph.add_node(())).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+        graph.remove_node(nodes[2]);
+        graph.add_edge(nodes[1], nodes[3], ());
+
+        for layout in all_layouts() {
+            let positions = compute_positions(&graph, &layout);
+            assert_eq!(positions.len(), 5, "{layout:?}");
+            for (node, &(x, y)) in &positions {
+                assert!(
+                    (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y),
+                    "{layout:?} placed {node:?} at {:?}",
+                    (x, y)
+                );
+            }
+            if matches!(layout, Layout::Random) {
+                continue;
+            }
+            let positions: Vec<_> = positions.into_iter().collect();
+            for (i, &(node_i, (x_i, y_i))) in positions.iter().enumerate() {
+                for &(node_j, (x_j, y_j)) in &positions[i + 1..] {
+                    assert!(
+                        (x_i - x_j).abs() > 1e-4 || (y_i - y_j).abs() > 1e-4,
+                        "{layout:?} placed {node_i:?} and {node_j:?} at the same position"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_overlaps() {
+        let (radius, width) = (25.0, 500.0);
+        let mut positions = vec![(0.5, 0.5), (0.5, 0.5), (0.0, 0.0)];
+
+        resolve_overlaps(&mut positions, radius, (width, width));
+
+        let (x_0, y_0) = positions[0];
+        let (x_1, y_1) = positions[1];
+        let distance = ((x_1 - x_0).powi(2) + (y_1 - y_0).powi(2)).sqrt();
+        assert!(distance >= 2.0 * radius / width - 1e-4);
+        // Nodes which did not overlap are not moved
+        assert_eq!(positions[2], (0.0, 0.0));
+    }
+}
@@ -1,17 +1,27 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_debug_implementations, missing_docs)]
 
+pub mod color;
+pub mod coloring;
 mod errors;
+pub mod graph_to_html;
 #[cfg(feature = "img")]
 pub mod graph_to_img;
 pub mod graph_to_svg;
+pub mod interop;
+pub mod labels;
 pub mod layout;
+pub mod palette;
 pub mod settings;
 #[cfg(feature = "img")]
 pub mod svg_to_img;
+#[cfg(feature = "pdf")]
+pub mod svg_to_pdf;
+pub mod widths;
 
+pub use graph_to_html::graph_to_html;
 #[cfg(feature = "img")]
-pub use graph_to_img::graph_to_img;
+pub use graph_to_img::{graph_to_img, visualize};
 pub use graph_to_svg::graph_to_svg;
 pub use layout::{hierarchical::Orientation, Layout};
 
@@ -103,4 +113,29 @@ pub mod tests {
             .expect("Values should be valid.");
         (square_graph, settings)
     }
+
+    /// Create a grid graph with `width` times `height` nodes, where each node is connected to the
+    /// nodes above and to the left of it and all edges have weight 1.
+    ///
+    /// The same as in `benches/common.rs`
+    pub fn build_2d_grid(width: usize, height: usize) -> UnGraph<(usize, usize), usize> {
+        let mut graph = UnGraph::new_undirected();
+
+        for j in 0..height {
+            for i in 0..width {
+                let node = graph.add_node((i, j));
+
+                if j > 0 {
+                    let up = NodeIndex::new(node.index() - width);
+                    graph.add_edge(node, up, 1);
+                }
+                if i > 0 {
+                    let left = NodeIndex::new(node.index() - 1);
+                    graph.add_edge(node, left, 1);
+                }
+            }
+        }
+
+        graph
+    }
 }
@@ -0,0 +1,100 @@
+//! Built-in categorical color palettes.
+//!
+//! Palettes are lists of distinct colors for coloring nodes or edges by category, e.g. by community
+//! or by layer. The main function is [`categorical`], which returns the color of a category index
+//! and can be used within [`node_coloring_fn`](crate::settings::SettingsBuilder::node_coloring_fn)
+//! or [`edge_coloring_fn`](crate::settings::SettingsBuilder::edge_coloring_fn):
+//!
+//! ```
+//! use petgraph::graph::NodeIndex;
+//! use visgraph::{
+//!     palette::{categorical, Palette},
+//!     settings::SettingsBuilder,
+//! };
+//!
+//! let settings = SettingsBuilder::new()
+//!     .node_coloring_fn(|node_id: NodeIndex| categorical(Palette::Tableau10, node_id.index()))
+//!     .build()
+//!     .expect("Values should be valid.");
+//! ```
+
+/// Colors of the Tableau 10 palette.
+const TABLEAU10: [&str; 10] = [
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948", "#b07aa1", "#ff9da7",
+    "#9c755f", "#bab0ac",
+];
+
+/// Colors of the Set3 palette from Color Brewer.
+const SET3: [&str; 12] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+    "#d9d9d9", "#bc80bd", "#ccebc5", "#ffed6f",
+];
+
+/// Colors of the rainbow.
+const RAINBOW: [&str; 7] = [
+    "red", "orange", "yellow", "green", "blue", "indigo", "violet",
+];
+
+/// Named categorical color palettes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Palette {
+    /// The ten colors of the Tableau 10 palette. This is the default.
+    #[default]
+    Tableau10,
+    /// The twelve pastel colors of the Set3 palette from Color Brewer.
+    Set3,
+    /// The seven colors of the rainbow, from `red` to `violet`.
+    Rainbow,
+}
+
+impl Palette {
+    /// Returns the colors of the palette as SVG color values.
+    pub fn colors(self) -> &'static [&'static str] {
+        match self {
+            Palette::Tableau10 => &TABLEAU10,
+            Palette::Set3 => &SET3,
+            Palette::Rainbow => &RAINBOW,
+        }
+    }
+}
+
+/// Returns the color of the category with the given `index` in `palette`.
+///
+/// Indices wrap around the length of the palette, such that any index is valid. Note that
+/// categories whose indices differ by a multiple of the palette length thus get the same color.
+pub fn categorical(palette: Palette, index: usize) -> &'static str {
+    let colors = palette.colors();
+    colors[index % colors.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{categorical, Palette};
+    use crate::color::is_valid_svg_color;
+
+    #[test]
+    fn test_palette_colors_are_valid() {
+        for palette in [Palette::Tableau10, Palette::Set3, Palette::Rainbow] {
+            for color in palette.colors() {
+                assert!(is_valid_svg_color(color), "{color} should be valid");
+            }
+        }
+    }
+
+    #[test]
+    fn test_categorical_wraps_around() {
+        for palette in [Palette::Tableau10, Palette::Set3, Palette::Rainbow] {
+            let colors = palette.colors();
+            for index in 0..colors.len() {
+                assert_eq!(categorical(palette, index), colors[index]);
+                assert_eq!(categorical(palette, index + colors.len()), colors[index]);
+                assert_eq!(
+                    categorical(palette, index + 3 * colors.len()),
+                    colors[index]
+                );
+            }
+        }
+        assert_eq!(categorical(Palette::Rainbow, 7), "red");
+        assert_eq!(categorical(Palette::Tableau10, 12), "#e15759");
+    }
+}
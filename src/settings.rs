@@ -6,9 +6,29 @@
 //! customize specific settings. The latter will validate the provided values upon calling
 //! `build()`.
 
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    sync::Arc,
+    time::Duration,
+};
+
+use petgraph::{
+    data::DataMap,
+    graph::NodeIndex,
+    visit::{EdgeRef, GraphBase, IntoEdgeReferences, IntoEdges},
+};
+
 use crate::{
-    errors::InvalidSettingsError,
-    layout::{DefaultPositionMapFn, LayoutOrPositionMap},
+    errors::{InvalidSettingsError, NotConnectedError},
+    graph_to_svg::title_height,
+    labels,
+    layout::{
+        cache::LayoutCache, circular::CircularParams, force_directed::EdgeWeightMode,
+        hierarchical::HierarchicalParams, spiral::SpiralParams, DefaultPositionMapFn,
+        LayoutOrPositionMap, NormalizeMode, YAxis,
+    },
     Layout,
 };
 
@@ -28,6 +48,71 @@ pub const DEFAULT_MARGIN: f32 = 0.05;
 /// Default layout algorithm for graph visualization.
 pub const DEFAULT_LAYOUT_OR_POS_MAP: LayoutOrPositionMap<DefaultPositionMapFn> =
     LayoutOrPositionMap::Layout(Layout::Circular);
+/// Default mode for normalizing positions. Positions are used as is.
+pub const DEFAULT_NORMALIZE_POSITIONS: NormalizeMode = NormalizeMode::None;
+/// Default direction of the y-axis. The y-axis points down, like in SVG.
+pub const DEFAULT_Y_AXIS: YAxis = YAxis::Down;
+/// Default number of grid divisions to snap positions to. Positions are not snapped.
+pub const DEFAULT_SNAP_TO_GRID: Option<usize> = None;
+/// Default for whether overlapping nodes are nudged apart. Positions are used as is.
+pub const DEFAULT_AVOID_OVERLAPS: bool = false;
+/// Default for whether the node radius is chosen automatically. The configured radius is used.
+pub const DEFAULT_AUTO_NODE_RADIUS: bool = false;
+/// Default corner in which the legend is drawn.
+pub const DEFAULT_LEGEND_CORNER: Corner = Corner::TopRight;
+/// Default for whether node labels are drawn. Node labels are drawn.
+pub const DEFAULT_DRAW_NODE_LABELS: bool = true;
+/// Default for whether node labels are interpreted as rich labels. Labels are drawn as is.
+pub const DEFAULT_RICH_LABELS: bool = false;
+/// Default maximum number of characters of labels. Labels are not truncated.
+pub const DEFAULT_LABEL_MAX_CHARS: Option<usize> = None;
+/// Default for whether the index of each node is drawn as a badge. No badges are drawn.
+pub const DEFAULT_SHOW_INDEX_BADGE: bool = false;
+/// Default style of the node outlines. Outlines are solid.
+pub const DEFAULT_NODE_STROKE_STYLE: EdgeStyle = EdgeStyle::Solid;
+/// Default for whether nodes cast a drop shadow. Nodes have no shadow.
+pub const DEFAULT_NODE_SHADOW: bool = false;
+/// Default for whether edge labels are drawn. Edge labels are drawn.
+pub const DEFAULT_DRAW_EDGE_LABELS: bool = true;
+/// Default position of edge labels as fraction along the edge. Labels are placed at the midpoint.
+pub const DEFAULT_EDGE_LABEL_POSITION: f32 = 0.5;
+/// Default for whether edge labels follow the direction of their edge. Labels are horizontal.
+pub const DEFAULT_ROTATE_EDGE_LABELS: bool = false;
+/// Default for whether parallel edges are bundled. Every edge is drawn separately.
+pub const DEFAULT_BUNDLE_PARALLEL_EDGES: bool = false;
+/// Default for whether reciprocal directed edges are drawn separately. They are drawn on top of
+/// each other.
+pub const DEFAULT_SEPARATE_RECIPROCAL_EDGES: bool = false;
+/// Default shape of arrowheads. Arrowheads are filled triangles.
+pub const DEFAULT_ARROW_STYLE: ArrowStyle = ArrowStyle::FilledTriangle;
+/// Default ends of the edges at which arrowheads are drawn. No arrowheads are drawn.
+pub const DEFAULT_EDGE_ARROW_MODE: ArrowMode = ArrowMode::None;
+/// Default minimum distance in pixels between two nodes for the edge between them to be drawn.
+pub const DEFAULT_MIN_EDGE_LENGTH: f32 = 0.001;
+/// Default for whether edges of the hierarchical layout are curved. Edges are straight lines.
+pub const DEFAULT_SMOOTH_TREE_EDGES: bool = false;
+/// Default routing of edges. Edges are straight lines.
+pub const DEFAULT_EDGE_ROUTING: EdgeRouting = EdgeRouting::Straight;
+/// Default for whether the SVG is responsive. The SVG has a fixed width and height.
+pub const DEFAULT_RESPONSIVE: bool = false;
+/// Default units of the canvas. The width and height are given in pixels.
+pub const DEFAULT_UNITS: Units = Units::Px;
+/// Default for whether a debug grid is drawn. No grid is drawn.
+pub const DEFAULT_DRAW_DEBUG_GRID: bool = false;
+/// Default for whether the SVG data starts with an XML declaration. No declaration is emitted.
+pub const DEFAULT_XML_DECLARATION: bool = false;
+/// Default for whether nodes and edges are given `id` attributes. No ids are emitted.
+pub const DEFAULT_EMIT_ELEMENT_IDS: bool = false;
+/// Default for whether nodes and edges are given CSS classes. No classes are emitted.
+pub const DEFAULT_EMIT_CSS_CLASSES: bool = false;
+/// Default for whether a metadata comment is embedded. No metadata is embedded.
+pub const DEFAULT_EMBED_METADATA: bool = false;
+/// Default color of highlighted nodes and edges.
+pub const DEFAULT_HIGHLIGHT_COLOR: &str = "red";
+/// Default stroke width of highlighted edges in pixels.
+pub const DEFAULT_HIGHLIGHT_STROKE_WIDTH: f32 = 8.0;
+/// Default for whether colors are validated before drawing. Colors are not validated.
+pub const DEFAULT_VALIDATE_COLORS: bool = false;
 /// Default function to generate node labels. Uses node indexes as labels.
 pub const DEFAULT_NODE_LABEL_FN: DefaultNodeLabelFn = |node_id| format!("{}", node_id.index());
 /// Default function to generate edge labels. No (empty) edge labels are drawn.
@@ -36,11 +121,349 @@ pub const DEFAULT_EDGE_LABEL_FN: DefaultEdgeLabelFn = |_| "".to_string();
 pub const DEFAULT_NODE_COLORING_FN: DefaultNodeColoringFn = |_| "white".to_string();
 /// Default function to generate edge colors. All edges are colored black.
 pub const DEFAULT_EDGE_COLORING_FN: DefaultEdgeColoringFn = |_| "black".to_string();
+/// Default function to generate edge weights for the force-directed layout. All edges have the
+/// same weight of 1.0.
+pub const DEFAULT_EDGE_WEIGHT_FN: DefaultEdgeWeightFn = |_| 1.0;
+/// Default mode for how edge weights influence the force-directed layout.
+pub const DEFAULT_EDGE_WEIGHT_MODE: EdgeWeightMode = EdgeWeightMode::Attract;
+/// Default function to generate node opacities. All nodes are fully opaque.
+pub const DEFAULT_NODE_OPACITY_FN: DefaultNodeOpacityFn = |_| 1.0;
+/// Default function to generate additional CSS classes of nodes. No additional classes are
+/// emitted.
+pub const DEFAULT_NODE_CLASS_FN: DefaultNodeClassFn = |_| "".to_string();
+/// Default function to generate additional CSS classes of edges. No additional classes are
+/// emitted.
+pub const DEFAULT_EDGE_CLASS_FN: DefaultEdgeClassFn = |_| "".to_string();
+/// Default function to generate node images. All nodes are drawn as circles.
+pub const DEFAULT_NODE_IMAGE_FN: DefaultNodeImageFn = |_| None;
+/// Default function to generate the curvature of edges. All edges are straight.
+pub const DEFAULT_EDGE_CURVATURE_FN: DefaultEdgeCurvatureFn = |_| 0.0;
+/// Default function to generate the z-order of nodes. All nodes have the same z-order.
+pub const DEFAULT_NODE_Z_FN: DefaultNodeZFn = |_| 0;
+
+/// Stroke width for edges in pixels of [`SettingsBuilder::publication_preset`].
+const PUBLICATION_STROKE_WIDTH: f32 = 1.0;
+/// Font size for labels in pixels of [`SettingsBuilder::publication_preset`].
+const PUBLICATION_FONT_SIZE: f32 = 10.0;
+/// Margin as a fraction of the width/height of [`SettingsBuilder::publication_preset`].
+const PUBLICATION_MARGIN: f32 = 0.02;
+
+/// Corners of the canvas, e.g. used to position the legend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Corner {
+    /// Top left corner.
+    TopLeft,
+    /// Top right corner. This is the default.
+    #[default]
+    TopRight,
+    /// Bottom left corner.
+    BottomLeft,
+    /// Bottom right corner.
+    BottomRight,
+}
+
+/// Routing of the edges between their nodes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EdgeRouting {
+    /// Edges are drawn as straight lines between the nodes. This is the default.
+    #[default]
+    Straight,
+    /// Edges are drawn as paths of alternating vertical and horizontal segments, which leave and
+    /// enter the nodes along the same axis and bend halfway between them. For the
+    /// [`Hierarchical`](crate::Layout::Hierarchical) layout, this axis is the direction of the
+    /// hierarchy. Otherwise, it is the axis along which the nodes are further apart.
+    Orthogonal,
+}
+
+/// Shapes of the arrowheads of directed edges. Arrowheads are painted in the color of their edge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrowStyle {
+    /// Filled triangle pointing at the target. This is the default.
+    #[default]
+    FilledTriangle,
+    /// Outline of a triangle pointing at the target.
+    OpenTriangle,
+    /// Filled diamond.
+    Diamond,
+    /// Filled circle.
+    Circle,
+}
+
+/// Ends of an edge at which arrowheads are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArrowMode {
+    /// No arrowheads are drawn. This is the default.
+    #[default]
+    None,
+    /// An arrowhead is drawn at the target of each edge.
+    End,
+    /// An arrowhead is drawn at the source of each edge, pointing at the source.
+    Start,
+    /// Arrowheads are drawn at both ends of each edge.
+    Both,
+}
+
+/// Units of the width and height of the canvas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Units {
+    /// Pixels. This is the default.
+    #[default]
+    Px,
+    /// Millimeters, e.g. for figures composed for print.
+    Mm,
+    /// Points, i.e. 1/72 of an inch.
+    Pt,
+}
+
+impl Units {
+    /// Returns the suffix of lengths given in these units in SVG data. Pixels have no suffix, since
+    /// they are the default units of SVG.
+    pub(crate) fn suffix(self) -> &'static str {
+        match self {
+            Units::Px => "",
+            Units::Mm => "mm",
+            Units::Pt => "pt",
+        }
+    }
+
+    /// Converts the `value` given in these units to pixels at a resolution of `dpi` pixels per inch.
+    pub fn to_pixels(self, value: f32, dpi: f32) -> f32 {
+        match self {
+            Units::Px => value,
+            Units::Mm => value * dpi / 25.4,
+            Units::Pt => value * dpi / 72.0,
+        }
+    }
+}
+
+/// Styles of drawn lines, e.g. of the outlines of nodes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EdgeStyle {
+    /// Continuous line. This is the default.
+    #[default]
+    Solid,
+    /// Line of dashes which are three times as long as the gaps between them.
+    Dashed,
+    /// Line of dots.
+    Dotted,
+}
+
+impl EdgeStyle {
+    /// Returns the value of the `stroke-dasharray` attribute for lines of this style and the given
+    /// `stroke_width`, or `None` for solid lines.
+    pub(crate) fn dash_array(self, stroke_width: f32) -> Option<String> {
+        let (dash, gap) = match self {
+            EdgeStyle::Solid => return None,
+            EdgeStyle::Dashed => (3.0 * stroke_width, stroke_width),
+            EdgeStyle::Dotted => (stroke_width, stroke_width),
+        };
+        let mut dash_buffer = ryu::Buffer::new();
+        let mut gap_buffer = ryu::Buffer::new();
+        Some(format!(
+            "{} {}",
+            dash_buffer.format(dash),
+            gap_buffer.format(gap)
+        ))
+    }
+}
+
+/// An edge of a graph together with its endpoints and weight, passed to the functions of
+/// [`SettingsBuilder::edge_coloring_fn_with_context`] and
+/// [`SettingsBuilder::edge_label_fn_with_context`].
+#[derive(Debug)]
+pub struct EdgeContext<'a, W> {
+    /// Index of the edge.
+    pub id: petgraph::prelude::EdgeIndex,
+    /// Index of the source node of the edge.
+    pub source: NodeIndex,
+    /// Index of the target node of the edge.
+    pub target: NodeIndex,
+    /// Weight of the edge.
+    pub weight: &'a W,
+}
+
+// Implemented manually, since deriving would require `W: Clone` and `W: Copy`
+impl<W> Clone for EdgeContext<'_, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<W> Copy for EdgeContext<'_, W> {}
+
+/// Returns a function which looks up the [`EdgeContext`] of each edge of the `graph`.
+///
+/// The endpoints of all edges are collected once when calling this function. The returned function
+/// panics if called with an edge which is not contained in the graph.
+fn edge_contexts<'a, G>(
+    graph: &'a G,
+) -> impl Fn(petgraph::prelude::EdgeIndex) -> EdgeContext<'a, G::EdgeWeight> + 'a
+where
+    G: DataMap + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+    &'a G:
+        IntoEdgeReferences + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+{
+    let endpoints: HashMap<petgraph::prelude::EdgeIndex, (NodeIndex, NodeIndex)> = graph
+        .edge_references()
+        .map(|edge| (edge.id(), (edge.source(), edge.target())))
+        .collect();
+
+    move |id| {
+        let (source, target) = endpoints[&id];
+        let weight = graph
+            .edge_weight(id)
+            .expect("Edges with endpoints should have a weight.");
+        EdgeContext {
+            id,
+            source,
+            target,
+            weight,
+        }
+    }
+}
+
+/// Fill of a node, returned by the node coloring function.
+///
+/// Coloring functions returning a `String` or `&str` are supported as well, since these convert into
+/// [`NodeFill::Solid`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum NodeFill {
+    /// The node is filled with a single color.
+    Solid(String),
+    /// The node is filled with a radial gradient from the `inner` color at its center to the
+    /// `outer` color at its border.
+    RadialGradient {
+        /// Color at the center of the node.
+        inner: String,
+        /// Color at the border of the node.
+        outer: String,
+    },
+}
+
+impl From<String> for NodeFill {
+    fn from(color: String) -> Self {
+        NodeFill::Solid(color)
+    }
+}
+
+impl From<&str> for NodeFill {
+    fn from(color: &str) -> Self {
+        NodeFill::Solid(color.to_owned())
+    }
+}
+
+/// Color of an edge, returned by the edge coloring function.
+///
+/// Coloring functions returning a `String` or `&str` are supported as well, since these convert into
+/// [`Color::Named`]. Note that no validation happens on conversion, the string is used as is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// Any SVG color value, e.g. a color name like `"red"` or a hex color like `"#ff0000"`.
+    Named(Cow<'static, str>),
+    /// Color given by its red, green and blue components.
+    Rgb(u8, u8, u8),
+    /// Color given by its red, green, blue and alpha components.
+    Rgba(u8, u8, u8, u8),
+}
+
+impl Color {
+    /// Creates a color from its red, green and blue components.
+    pub const fn rgb(red: u8, green: u8, blue: u8) -> Self {
+        Color::Rgb(red, green, blue)
+    }
+
+    /// Creates a color from its red, green, blue and alpha components.
+    pub const fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Color::Rgba(red, green, blue, alpha)
+    }
+
+    /// Parses a hex color of the form `#RGB`, `#RRGGBB` or `#RRGGBBAA`.
+    ///
+    /// Returns `None` if `hex` is not of one of these forms.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#')?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let component = |i: usize| u8::from_str_radix(&digits[2 * i..2 * i + 2], 16).ok();
+
+        match digits.len() {
+            3 => {
+                let short = |i: usize| {
+                    u8::from_str_radix(&digits[i..i + 1], 16)
+                        .ok()
+                        .map(|c| c * 17)
+                };
+                Some(Color::Rgb(short(0)?, short(1)?, short(2)?))
+            }
+            6 => Some(Color::Rgb(component(0)?, component(1)?, component(2)?)),
+            8 => Some(Color::Rgba(
+                component(0)?,
+                component(1)?,
+                component(2)?,
+                component(3)?,
+            )),
+            _ => None,
+        }
+    }
 
-pub(crate) type DefaultNodeLabelFn = fn(petgraph::prelude::NodeIndex) -> String;
+    /// Returns the color as an SVG color value.
+    ///
+    /// [`Color::Rgb`] is written as a hex color, e.g. `#ff0000`, and [`Color::Rgba`] using the
+    /// `rgba()` notation, since not all SVG renderers support hex colors with an alpha component.
+    pub fn to_svg(&self) -> Cow<'_, str> {
+        match self {
+            Color::Named(name) => Cow::Borrowed(name),
+            Color::Rgb(red, green, blue) => Cow::Owned(format!("#{red:02x}{green:02x}{blue:02x}")),
+            Color::Rgba(red, green, blue, alpha) => Cow::Owned(format!(
+                "rgba({red}, {green}, {blue}, {})",
+                ryu::Buffer::new().format(f32::from(*alpha) / 255.0)
+            )),
+        }
+    }
+}
+
+impl From<&'static str> for Color {
+    fn from(color: &'static str) -> Self {
+        Color::Named(Cow::Borrowed(color))
+    }
+}
+
+impl From<String> for Color {
+    fn from(color: String) -> Self {
+        Color::Named(Cow::Owned(color))
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((red, green, blue): (u8, u8, u8)) -> Self {
+        Color::Rgb(red, green, blue)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Color {
+    fn from((red, green, blue, alpha): (u8, u8, u8, u8)) -> Self {
+        Color::Rgba(red, green, blue, alpha)
+    }
+}
+
+impl From<Color> for NodeFill {
+    fn from(color: Color) -> Self {
+        NodeFill::Solid(color.to_svg().into_owned())
+    }
+}
+
+pub(crate) type DefaultNodeLabelFn = fn(NodeIndex) -> String;
 pub(crate) type DefaultEdgeLabelFn = fn(petgraph::prelude::EdgeIndex) -> String;
-pub(crate) type DefaultNodeColoringFn = fn(petgraph::prelude::NodeIndex) -> String;
+pub(crate) type DefaultNodeColoringFn = fn(NodeIndex) -> String;
 pub(crate) type DefaultEdgeColoringFn = fn(petgraph::prelude::EdgeIndex) -> String;
+pub(crate) type DefaultEdgeWeightFn = fn(petgraph::prelude::EdgeIndex) -> f32;
+pub(crate) type DefaultNodeOpacityFn = fn(NodeIndex) -> f32;
+pub(crate) type DefaultNodeClassFn = fn(NodeIndex) -> String;
+pub(crate) type DefaultEdgeClassFn = fn(petgraph::prelude::EdgeIndex) -> String;
+pub(crate) type DefaultNodeImageFn = fn(NodeIndex) -> Option<String>;
+pub(crate) type DefaultEdgeCurvatureFn = fn(petgraph::prelude::EdgeIndex) -> f32;
+pub(crate) type DefaultNodeZFn = fn(NodeIndex) -> i32;
 
 /// Settings for SVG graph rendering.
 ///
@@ -70,6 +493,13 @@ pub struct Settings<
     EdgeLabelFn = DefaultEdgeLabelFn,
     NodeColoringFn = DefaultNodeColoringFn,
     EdgeColoringFn = DefaultEdgeColoringFn,
+    EdgeWeightFn = DefaultEdgeWeightFn,
+    NodeOpacityFn = DefaultNodeOpacityFn,
+    NodeClassFn = DefaultNodeClassFn,
+    EdgeClassFn = DefaultEdgeClassFn,
+    NodeImageFn = DefaultNodeImageFn,
+    EdgeCurvatureFn = DefaultEdgeCurvatureFn,
+    NodeZFn = DefaultNodeZFn,
 > {
     pub(crate) width: f32,
     pub(crate) height: f32,
@@ -79,10 +509,64 @@ pub struct Settings<
     pub(crate) margin_x: f32,
     pub(crate) margin_y: f32,
     pub(crate) layout_or_pos_map: LayoutOrPositionMap<PositionMapFn>,
+    pub(crate) pinned_positions: HashMap<NodeIndex, (f32, f32)>,
+    pub(crate) circular_params: CircularParams,
+    pub(crate) spiral_params: SpiralParams,
+    pub(crate) hierarchical_params: HierarchicalParams,
+    pub(crate) normalize_positions: NormalizeMode,
+    pub(crate) y_axis: YAxis,
+    pub(crate) snap_to_grid: Option<usize>,
+    pub(crate) avoid_overlaps: bool,
+    pub(crate) auto_node_radius: bool,
+    pub(crate) legend: Vec<(String, String)>,
+    pub(crate) legend_corner: Corner,
+    pub(crate) title: Option<String>,
+    pub(crate) draw_node_labels: bool,
+    pub(crate) rich_labels: bool,
+    pub(crate) label_max_chars: Option<usize>,
+    pub(crate) show_index_badge: bool,
+    pub(crate) node_stroke_style: EdgeStyle,
+    pub(crate) node_shadow: bool,
+    pub(crate) draw_edge_labels: bool,
+    pub(crate) edge_label_position: f32,
+    pub(crate) rotate_edge_labels: bool,
+    pub(crate) bundle_parallel_edges: bool,
+    pub(crate) separate_reciprocal_edges: bool,
+    pub(crate) arrow_style: ArrowStyle,
+    pub(crate) edge_arrow_mode: ArrowMode,
+    pub(crate) min_edge_length: f32,
+    pub(crate) smooth_tree_edges: bool,
+    pub(crate) edge_routing: EdgeRouting,
+    pub(crate) responsive: bool,
+    pub(crate) units: Units,
+    pub(crate) background_color: Option<String>,
+    pub(crate) draw_debug_grid: bool,
+    pub(crate) xml_declaration: bool,
+    pub(crate) extra_defs: String,
+    pub(crate) emit_element_ids: bool,
+    pub(crate) emit_css_classes: bool,
+    pub(crate) embed_metadata: bool,
+    pub(crate) highlighted_nodes: HashSet<NodeIndex>,
+    pub(crate) highlighted_edges: HashSet<petgraph::prelude::EdgeIndex>,
+    pub(crate) node_draw_order: Option<Vec<NodeIndex>>,
+    pub(crate) highlight_color: String,
+    pub(crate) highlight_stroke_width: f32,
+    pub(crate) validate_colors: bool,
     pub(crate) node_label_fn: NodeLabelFn,
     pub(crate) edge_label_fn: EdgeLabelFn,
     pub(crate) node_coloring_fn: NodeColoringFn,
     pub(crate) edge_coloring_fn: EdgeColoringFn,
+    pub(crate) edge_weight_fn: EdgeWeightFn,
+    pub(crate) edge_weight_mode: EdgeWeightMode,
+    pub(crate) seed: Option<u64>,
+    pub(crate) layout_time_budget: Option<Duration>,
+    pub(crate) layout_cache: Option<(Arc<LayoutCache>, String)>,
+    pub(crate) node_opacity_fn: NodeOpacityFn,
+    pub(crate) node_class_fn: NodeClassFn,
+    pub(crate) edge_class_fn: EdgeClassFn,
+    pub(crate) node_image_fn: NodeImageFn,
+    pub(crate) edge_curvature_fn: EdgeCurvatureFn,
+    pub(crate) node_z_fn: NodeZFn,
 }
 
 impl Default for Settings<DefaultPositionMapFn, DefaultNodeLabelFn, DefaultEdgeLabelFn> {
@@ -99,10 +583,64 @@ impl Default for Settings<DefaultPositionMapFn, DefaultNodeLabelFn, DefaultEdgeL
             margin_x: DEFAULT_MARGIN,
             margin_y: DEFAULT_MARGIN,
             layout_or_pos_map: DEFAULT_LAYOUT_OR_POS_MAP,
+            pinned_positions: HashMap::new(),
+            circular_params: CircularParams::default(),
+            spiral_params: SpiralParams::default(),
+            hierarchical_params: HierarchicalParams::default(),
+            normalize_positions: DEFAULT_NORMALIZE_POSITIONS,
+            y_axis: DEFAULT_Y_AXIS,
+            snap_to_grid: DEFAULT_SNAP_TO_GRID,
+            avoid_overlaps: DEFAULT_AVOID_OVERLAPS,
+            auto_node_radius: DEFAULT_AUTO_NODE_RADIUS,
+            legend: Vec::new(),
+            legend_corner: DEFAULT_LEGEND_CORNER,
+            title: None,
+            draw_node_labels: DEFAULT_DRAW_NODE_LABELS,
+            rich_labels: DEFAULT_RICH_LABELS,
+            label_max_chars: DEFAULT_LABEL_MAX_CHARS,
+            show_index_badge: DEFAULT_SHOW_INDEX_BADGE,
+            node_stroke_style: DEFAULT_NODE_STROKE_STYLE,
+            node_shadow: DEFAULT_NODE_SHADOW,
+            draw_edge_labels: DEFAULT_DRAW_EDGE_LABELS,
+            edge_label_position: DEFAULT_EDGE_LABEL_POSITION,
+            rotate_edge_labels: DEFAULT_ROTATE_EDGE_LABELS,
+            bundle_parallel_edges: DEFAULT_BUNDLE_PARALLEL_EDGES,
+            separate_reciprocal_edges: DEFAULT_SEPARATE_RECIPROCAL_EDGES,
+            arrow_style: DEFAULT_ARROW_STYLE,
+            edge_arrow_mode: DEFAULT_EDGE_ARROW_MODE,
+            min_edge_length: DEFAULT_MIN_EDGE_LENGTH,
+            smooth_tree_edges: DEFAULT_SMOOTH_TREE_EDGES,
+            edge_routing: DEFAULT_EDGE_ROUTING,
+            responsive: DEFAULT_RESPONSIVE,
+            units: DEFAULT_UNITS,
+            background_color: None,
+            draw_debug_grid: DEFAULT_DRAW_DEBUG_GRID,
+            xml_declaration: DEFAULT_XML_DECLARATION,
+            extra_defs: String::new(),
+            emit_element_ids: DEFAULT_EMIT_ELEMENT_IDS,
+            emit_css_classes: DEFAULT_EMIT_CSS_CLASSES,
+            embed_metadata: DEFAULT_EMBED_METADATA,
+            highlighted_nodes: HashSet::new(),
+            highlighted_edges: HashSet::new(),
+            node_draw_order: None,
+            highlight_color: DEFAULT_HIGHLIGHT_COLOR.to_string(),
+            highlight_stroke_width: DEFAULT_HIGHLIGHT_STROKE_WIDTH,
+            validate_colors: DEFAULT_VALIDATE_COLORS,
             node_label_fn: DEFAULT_NODE_LABEL_FN,
             edge_label_fn: DEFAULT_EDGE_LABEL_FN,
             node_coloring_fn: DEFAULT_NODE_COLORING_FN,
             edge_coloring_fn: DEFAULT_EDGE_COLORING_FN,
+            edge_weight_fn: DEFAULT_EDGE_WEIGHT_FN,
+            edge_weight_mode: DEFAULT_EDGE_WEIGHT_MODE,
+            seed: None,
+            layout_time_budget: None,
+            layout_cache: None,
+            node_opacity_fn: DEFAULT_NODE_OPACITY_FN,
+            node_class_fn: DEFAULT_NODE_CLASS_FN,
+            edge_class_fn: DEFAULT_EDGE_CLASS_FN,
+            node_image_fn: DEFAULT_NODE_IMAGE_FN,
+            edge_curvature_fn: DEFAULT_EDGE_CURVATURE_FN,
+            node_z_fn: DEFAULT_NODE_Z_FN,
         }
     }
 }
@@ -118,6 +656,116 @@ impl Settings<DefaultPositionMapFn, DefaultNodeLabelFn, DefaultEdgeLabelFn> {
     }
 }
 
+impl<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    Settings<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+{
+    /// Returns the width of the SVG canvas and output image in pixels.
+    ///
+    /// See [`SettingsBuilder::width`].
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Returns the height of the SVG canvas and output image in pixels.
+    ///
+    /// See [`SettingsBuilder::height`].
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Returns the radius of the nodes in pixels.
+    ///
+    /// See [`SettingsBuilder::node_radius`].
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns the font size of the labels in pixels.
+    ///
+    /// See [`SettingsBuilder::font_size`].
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+
+    /// Returns the stroke width of the edges in pixels.
+    ///
+    /// See [`SettingsBuilder::stroke_width`].
+    pub fn stroke_width(&self) -> f32 {
+        self.stroke_width
+    }
+
+    /// Returns the horizontal margin as a fraction of the width.
+    ///
+    /// See [`SettingsBuilder::margin_x`].
+    pub fn margin_x(&self) -> f32 {
+        self.margin_x
+    }
+
+    /// Returns the vertical margin as a fraction of the height.
+    ///
+    /// See [`SettingsBuilder::margin_y`].
+    pub fn margin_y(&self) -> f32 {
+        self.margin_y
+    }
+
+    /// Returns the layout algorithm, or `None` if a custom position map is used.
+    ///
+    /// See [`SettingsBuilder::layout`] and [`SettingsBuilder::position_map`].
+    pub fn layout(&self) -> Option<&Layout> {
+        match &self.layout_or_pos_map {
+            LayoutOrPositionMap::Layout(layout) => Some(layout),
+            LayoutOrPositionMap::PositionMap(_) => None,
+        }
+    }
+
+    /// Returns the title drawn at the top of the canvas, if any.
+    ///
+    /// See [`SettingsBuilder::title`].
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the seed of the force-directed layout, if any.
+    ///
+    /// See [`SettingsBuilder::seed`].
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Returns whether the SVG is responsive.
+    ///
+    /// See [`SettingsBuilder::responsive`].
+    pub fn responsive(&self) -> bool {
+        self.responsive
+    }
+}
+
 /// Builder for creating a [`Settings`] instance with customized values.
 ///
 /// For details on the different settings, see the fields of this struct.
@@ -137,31 +785,43 @@ impl Settings<DefaultPositionMapFn, DefaultNodeLabelFn, DefaultEdgeLabelFn> {
 ///     .expect("Provided values should be valid.");
 /// ```
 #[derive(Debug)]
-pub struct SettingsBuilder<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
-{
+pub struct SettingsBuilder<
+    PositionMapFn,
+    NodeLabelFn,
+    EdgeLabelFn,
+    NodeColoringFn,
+    EdgeColoringFn,
+    EdgeWeightFn,
+    NodeOpacityFn,
+    NodeClassFn,
+    EdgeClassFn,
+    NodeImageFn,
+    EdgeCurvatureFn,
+    NodeZFn,
+> {
     /// Width of the SVG and output image in pixels.
     ///
-    /// **Valid values**: strictly positive f32
+    /// **Valid values**: strictly positive, finite f32
     pub width: f32,
 
     /// Height of the SVG and output image in pixels.
     ///
-    /// **Valid values**: strictly positive f32
+    /// **Valid values**: strictly positive, finite f32
     pub height: f32,
 
     /// Radius of the nodes in pixels.
     ///
-    /// **Valid values**: strictly positive f32
+    /// **Valid values**: strictly positive, finite f32
     pub node_radius: f32,
 
     /// Font size for labels in pixels.
     ///
-    /// **Valid values**: strictly positive f32
+    /// **Valid values**: strictly positive, finite f32
     pub font_size: f32,
 
     /// Stroke width for edges in pixels.
     ///
-    /// **Valid values**: strictly positive f32
+    /// **Valid values**: strictly positive, finite f32
     pub stroke_width: f32,
 
     /// Horizontal margin as a fraction of the width.
@@ -184,6 +844,334 @@ pub struct SettingsBuilder<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoring
     /// positions in the range [0.0, 1.0].
     pub layout_or_pos_map: LayoutOrPositionMap<PositionMapFn>,
 
+    /// Positions of nodes which are kept fixed by the
+    /// [`ForceDirected`](crate::Layout::ForceDirected) layout. The remaining nodes settle around
+    /// them. Has no effect on other layouts or custom position maps.
+    ///
+    /// **Valid values**: Normalized positions in the range [0.0, 1.0].
+    pub pinned_positions: HashMap<NodeIndex, (f32, f32)>,
+
+    /// Radius and center of the ring on which the [`Circular`](crate::Layout::Circular) layout places
+    /// the nodes, see [`CircularParams`]. Has no effect on other layouts or custom position maps.
+    ///
+    /// **Valid values**: [`CircularParams`] with a strictly positive, finite radius and a finite
+    /// center. Note that nodes are placed outside of the drawing area if the ring does not fit into
+    /// the unit square.
+    pub circular_params: CircularParams,
+
+    /// Number of turns and spacing between turns of the spiral along which the
+    /// [`Spiral`](crate::Layout::Spiral) layout places the nodes, see [`SpiralParams`]. Has no effect
+    /// on other layouts or custom position maps.
+    ///
+    /// **Valid values**: Any [`SpiralParams`]. Spirals which do not fit into the drawing area are scaled
+    /// down to fit.
+    pub spiral_params: SpiralParams,
+
+    /// Spacing between levels and siblings of the
+    /// [`Hierarchical`](crate::Layout::Hierarchical) layout, see [`HierarchicalParams`]. Has no effect on
+    /// other layouts or custom position maps.
+    ///
+    /// **Valid values**: Spacings must be strictly positive and finite, if given.
+    pub hierarchical_params: HierarchicalParams,
+
+    /// How positions returned by the layout or position map are brought into the range
+    /// [0.0, 1.0] before drawing. See [`NormalizeMode`] for the available modes.
+    ///
+    /// **Valid values**: Any [`NormalizeMode`].
+    pub normalize_positions: NormalizeMode,
+
+    /// Direction of the y-axis of the normalized positions. With [`YAxis::Up`], a normalized y of 0.0
+    /// is drawn at the bottom of the drawing area instead of the top, as is common in mathematics.
+    /// Applies to all layouts and custom position maps.
+    ///
+    /// **Valid values**: Any [`YAxis`].
+    pub y_axis: YAxis,
+
+    /// Number of grid divisions to snap the normalized positions to. With `Some(n)`, each normalized
+    /// coordinate is rounded to the nearest multiple of `1/n`, e.g. to tidy up the jitter of the
+    /// force-directed layout. Snapping happens after normalization and before overlaps are resolved, see
+    /// [`avoid_overlaps`](SettingsBuilder::avoid_overlaps).
+    ///
+    /// **Valid values**: `None` or `Some(n)` with `n` at least 1.
+    pub snap_to_grid: Option<usize>,
+
+    /// Whether overlapping nodes are nudged apart after the positions are computed, see
+    /// [`resolve_overlaps`](crate::layout::resolve_overlaps). This applies to both layouts and
+    /// custom position maps.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub avoid_overlaps: bool,
+
+    /// Whether the radius of the nodes is chosen automatically such that no two nodes overlap. If
+    /// `true`, the radius is set to 40% of the smallest distance between two nodes on the canvas, but
+    /// at most the configured [`node_radius`](Self::node_radius). Nodes at the same position are not
+    /// taken into account. If `false`, the configured radius is used as is.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub auto_node_radius: bool,
+
+    /// Entries of the legend as `(color, label)` pairs. If non-empty, a legend box with a colored
+    /// swatch and the label for each entry is drawn on top of the graph. If empty, no legend is
+    /// drawn.
+    ///
+    /// **Valid values**: The colors should be valid SVG colors (e.g., "red", "#ff0000",
+    /// "rgb(255,0,0)"), see the node coloring function.
+    pub legend: Vec<(String, String)>,
+
+    /// Corner of the canvas in which the legend is drawn.
+    ///
+    /// **Valid values**: Any [`Corner`].
+    pub legend_corner: Corner,
+
+    /// Title drawn centered at the top of the canvas using a larger font. If set, space for the
+    /// title is reserved at the top and the drawing area of the graph shrinks accordingly. If
+    /// `None`, no title is drawn.
+    ///
+    /// **Valid values**: Any `Option<String>`, as long as the space reserved for the title, three
+    /// times the font size, is smaller than the height of the canvas.
+    pub title: Option<String>,
+
+    /// Whether node labels are drawn. If `false`, no <text> elements are emitted for nodes and
+    /// the node label function is not called, which shrinks the SVG for large graphs.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub draw_node_labels: bool,
+
+    /// Whether node labels are interpreted as rich labels with a tiny markup for subscripts and
+    /// superscripts. If `true`, `_` starts a subscript and `^` a superscript, e.g. `x_1` or `x^2`. The
+    /// script consists of the following alphanumeric characters or, if enclosed in braces, of
+    /// everything up to the closing brace, e.g. `x_{i+1}`. Scripts are drawn using <tspan> elements.
+    /// If `false`, node labels are drawn as is.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub rich_labels: bool,
+
+    /// Maximum number of characters of node and edge labels. With `Some(n)`, labels longer than `n`
+    /// characters are truncated to their first `n - 1` characters followed by an ellipsis `…`, such that
+    /// they keep fitting their node. Unlike wrapping, this keeps the layout tidy at the cost of hiding
+    /// the end of long labels. Labels of edge bundles, i.e. the number of parallel edges, are not truncated.
+    ///
+    /// **Valid values**: `None` or `Some(n)` with `n` at least 1.
+    pub label_max_chars: Option<usize>,
+
+    /// Whether the index of each node is drawn as a small badge at the top right of the node, in
+    /// addition to the node label. This is meant as a debugging aid, e.g. to relate nodes with custom
+    /// labels to their petgraph indices.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub show_index_badge: bool,
+
+    /// Style of the outline of the node circles, see [`EdgeStyle`]. Non-solid outlines can mark special
+    /// nodes, e.g. virtual ones. Has no effect on nodes drawn as images, see
+    /// [`node_image_fn`](SettingsBuilder::node_image_fn).
+    ///
+    /// **Valid values**: Any [`EdgeStyle`].
+    pub node_stroke_style: EdgeStyle,
+
+    /// Whether nodes cast a subtle drop shadow to the bottom right, which improves depth perception in
+    /// dense graphs. The shadow is a blurred and offset copy of the node circle, defined as a <filter>
+    /// with the id `node-shadow`, and is rendered by [`graph_to_img`](crate::graph_to_img) as well. Has
+    /// no effect on nodes drawn as images, see [`node_image_fn`](SettingsBuilder::node_image_fn).
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub node_shadow: bool,
+
+    /// Whether edge labels are drawn. If `false`, no <text> elements are emitted for edges and
+    /// the edge label function is not called.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub draw_edge_labels: bool,
+
+    /// Position of edge labels as fraction along the edge, from the point where the edge leaves
+    /// the source node (0.0) to the point where it reaches the target node (1.0).
+    ///
+    /// **Valid values**: f32 in the range [0.0, 1.0]
+    pub edge_label_position: f32,
+
+    /// Whether edge labels are rotated to follow the direction of their edge. Labels are flipped such
+    /// that they are never drawn upside down. If `false`, edge labels are drawn horizontally.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub rotate_edge_labels: bool,
+
+    /// Whether parallel edges are bundled. If `true`, all edges between the same pair of nodes are
+    /// drawn as a single line, which is labeled with the number of bundled edges instead of the edge
+    /// label and is thicker the more edges it bundles. In directed graphs, only edges in the same
+    /// direction are bundled. The color and highlighting of a bundle are those of its first edge. This
+    /// declutters drawings of dense multigraphs.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub bundle_parallel_edges: bool,
+
+    /// Whether reciprocal edges of directed graphs are drawn separately. If `true`, an edge from `a` to
+    /// `b` in a graph which also contains an edge from `b` to `a` is shifted sideways, such that both
+    /// directions are drawn as two parallel lines, and ends in an arrowhead at its target. Only applies to
+    /// straight edges, that is, not to [`smooth_tree_edges`](SettingsBuilder::smooth_tree_edges) or
+    /// [`EdgeRouting::Orthogonal`], and has no effect on undirected graphs.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub separate_reciprocal_edges: bool,
+
+    /// Shape of the arrowheads of edges, see [`ArrowStyle`]. Arrowheads are drawn according to
+    /// [`edge_arrow_mode`](SettingsBuilder::edge_arrow_mode) and for reciprocal edges, see
+    /// [`separate_reciprocal_edges`](SettingsBuilder::separate_reciprocal_edges).
+    ///
+    /// **Valid values**: Any [`ArrowStyle`].
+    pub arrow_style: ArrowStyle,
+
+    /// Ends of the edges at which arrowheads are drawn, see [`ArrowMode`]. The arrowheads have the shape
+    /// given by [`arrow_style`](SettingsBuilder::arrow_style). This also applies to undirected graphs,
+    /// where the start of an edge is the node it was added from. If set to [`ArrowMode::None`], reciprocal
+    /// edges drawn separately still end in an arrowhead, see
+    /// [`separate_reciprocal_edges`](SettingsBuilder::separate_reciprocal_edges).
+    ///
+    /// **Valid values**: Any [`ArrowMode`].
+    pub edge_arrow_mode: ArrowMode,
+
+    /// Minimum distance in pixels between the centers of two nodes for the edge between them to be
+    /// drawn. Edges between nodes which are closer are skipped, since their direction is not well
+    /// defined. Lower this for small canvases or tiny radii, if short edges are missing.
+    ///
+    /// **Valid values**: Strictly positive, finite values.
+    pub min_edge_length: f32,
+
+    /// Whether edges are drawn as smooth curves, which leave and enter nodes along the direction of the
+    /// hierarchy, like in typical org charts. Only has an effect on the
+    /// [`Hierarchical`](crate::Layout::Hierarchical) layout, other layouts always use straight edges.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub smooth_tree_edges: bool,
+
+    /// How edges are routed between their nodes. If [`EdgeRouting::Orthogonal`], edges are drawn as
+    /// paths of horizontal and vertical segments, e.g. for flowchart-style diagrams with the
+    /// [`Hierarchical`](crate::Layout::Hierarchical) layout. This takes precedence over
+    /// [`smooth_tree_edges`](SettingsBuilder::smooth_tree_edges).
+    ///
+    /// **Valid values**: Any [`EdgeRouting`].
+    pub edge_routing: EdgeRouting,
+
+    /// Whether the SVG is responsive. If `true`, the SVG spans `100%` of the width and height of
+    /// its container and uses a `viewBox` of the configured width and height, such that it scales
+    /// when embedded in HTML. If `false`, the SVG has the configured width and height in pixels.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub responsive: bool,
+
+    /// Units of the width and height of the canvas, see [`Units`]. Other than pixels, the opening <svg>
+    /// tag carries the units as suffix of its width and height, e.g. `width="210.0mm"`, along with a
+    /// `viewBox` of the same numeric size. Thus, all other lengths, e.g. the node radius, the font size
+    /// and the stroke width, are given in the same units. Has no effect on responsive SVGs, see
+    /// [`responsive`](SettingsBuilder::responsive). [`graph_to_img`](crate::graph_to_img) converts the
+    /// size to pixels at 96 DPI.
+    ///
+    /// **Valid values**: Any [`Units`].
+    pub units: Units,
+
+    /// Color of the background of the canvas. If `None`, the background is transparent, so PNG and
+    /// similar outputs show the default background of the renderer.
+    ///
+    /// **Valid values**: `None` or `Some` valid SVG color, see the node coloring function.
+    pub background_color: Option<String>,
+
+    /// Whether a debug grid is drawn beneath the graph. If `true`, light gray gridlines are drawn at every
+    /// 0.1 of the normalized coordinates returned by the position map and labeled with their coordinate,
+    /// which helps verifying custom position maps. Note that radial layouts are corrected for the aspect
+    /// ratio of the canvas after normalization, such that the grid does not match their positions exactly.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub draw_debug_grid: bool,
+
+    /// Whether the SVG data starts with an XML declaration, i.e. `<?xml version="1.0" encoding="UTF-8"?>`.
+    /// Some strict XML consumers reject SVG files without it. The declaration is omitted from the SVG
+    /// embedded by [`graph_to_html`](crate::graph_to_html::graph_to_html), since it is not valid inside
+    /// of HTML documents.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub xml_declaration: bool,
+
+    /// Custom definitions inserted into a <defs> tag after the opening <svg> tag, e.g. gradients,
+    /// filters or patterns. Elements with an `id` can then be referenced by the coloring functions, e.g.
+    /// a node coloring function returning `url(#my-pattern)` fills nodes with the <pattern> of that id.
+    /// If empty, no <defs> tag is added.
+    ///
+    /// **Valid values**: Any `String`. The content is inserted as is, so it has to be valid SVG markup.
+    pub extra_defs: String,
+
+    /// Whether each node and edge is given an `id` attribute derived from its index, i.e. `node-3` for
+    /// the node with index 3 and `edge-7` for the edge with index 7. This allows addressing individual
+    /// elements, e.g. from JavaScript when the SVG is embedded in HTML.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub emit_element_ids: bool,
+
+    /// Whether nodes and edges are given CSS classes, such that they can be styled by an external
+    /// stylesheet when the SVG is embedded in HTML. If `true`, all nodes are given the class `node` and
+    /// all edges the class `edge`, followed by the classes returned by the
+    /// [`node_class_fn`](SettingsBuilder::node_class_fn) and
+    /// [`edge_class_fn`](SettingsBuilder::edge_class_fn), respectively.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub emit_css_classes: bool,
+
+    /// Whether a comment with metadata about the drawing is placed after the opening <svg> tag. If
+    /// `true`, the comment contains the name and version of this crate, the layout (the selected one for
+    /// [`Layout::Auto`]), the number of nodes and edges and the time of rendering, e.g. to trace back how
+    /// an SVG file was generated. Since the timestamp changes on every render, this is disabled by
+    /// default to keep the output reproducible.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub embed_metadata: bool,
+
+    /// Nodes which are highlighted. Highlighted nodes are filled with the
+    /// [`highlight_color`](Self::highlight_color) instead of the color returned by the node coloring
+    /// function.
+    ///
+    /// **Valid values**: Any `HashSet<NodeIndex>`. Node indices that do not refer to a node of the
+    /// graph are ignored.
+    pub highlighted_nodes: HashSet<NodeIndex>,
+
+    /// Edges which are highlighted. Highlighted edges are drawn using the
+    /// [`highlight_color`](Self::highlight_color) and
+    /// [`highlight_stroke_width`](Self::highlight_stroke_width) instead of the color returned by the
+    /// edge coloring function and the regular stroke width.
+    ///
+    /// **Valid values**: Any `HashSet<EdgeIndex>`. Edge indices that do not refer to an edge of the
+    /// graph are ignored.
+    pub highlighted_edges: HashSet<petgraph::prelude::EdgeIndex>,
+
+    /// Order in which the nodes are drawn. Nodes drawn later are drawn on top of nodes drawn earlier, so
+    /// this controls which node is visible where nodes overlap. If `Some`, the listed nodes are drawn
+    /// first in the given order, followed by all other nodes in index order. If `None`, all nodes are
+    /// drawn in index order. Either way, the nodes are then sorted by their z-order, see
+    /// [`node_z_fn`](SettingsBuilder::node_z_fn).
+    ///
+    /// **Valid values**: Any `Option<Vec<NodeIndex>>`. Node indices that do not refer to a node of the
+    /// graph and repeated indices are ignored.
+    pub node_draw_order: Option<Vec<NodeIndex>>,
+
+    /// Color of highlighted nodes and edges.
+    ///
+    /// **Valid values**: The string should be a valid SVG color, see the node coloring function.
+    pub highlight_color: String,
+
+    /// Stroke width of highlighted edges in pixels.
+    ///
+    /// **Valid values**: strictly positive, finite f32
+    pub highlight_stroke_width: f32,
+
+    /// Whether the colors returned by the node and edge coloring functions are validated while
+    /// drawing. If `true`, [`graph_to_svg`](crate::graph_to_svg()),
+    /// [`try_graph_to_svg_string`](crate::graph_to_svg::try_graph_to_svg_string),
+    /// [`graph_to_svg_stream`](crate::graph_to_svg::graph_to_svg_stream) and `graph_to_img` return an
+    /// error for the first color that is not a recognized SVG color (a color keyword, a hex color, an
+    /// `rgb()`/`rgba()`/`hsl()`/`hsla()` value or a `url()` reference), instead of the renderer
+    /// silently falling back to black. The functions which cannot fail, such as
+    /// [`graph_to_svg_string`](crate::graph_to_svg::graph_to_svg_string), skip the validation, see
+    /// [`validate_colors`](crate::graph_to_svg::validate_colors) for validating colors there.
+    ///
+    /// **Valid values**: `true` or `false`.
+    pub validate_colors: bool,
+
     /// Function to generate node labels. If none is provided, node indexes will be used as labels.
     ///
     /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> String`.
@@ -196,19 +1184,118 @@ pub struct SettingsBuilder<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoring
 
     /// Function to generate node colors. If none is provided, all nodes will be colored black.
     ///
-    /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> String`.
-    /// The returned string should be a valid SVG color (e.g., "red", "#ff0000", "rgb(255,0,0)").
+    /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> impl Into<NodeFill>`,
+    /// e.g. `impl Fn(G::NodeId) -> String`. The returned colors should be valid SVG colors (e.g.,
+    /// "red", "#ff0000", "rgb(255,0,0)").
     /// See [https://graphviz.org/doc/info/colors.html#svg](https://graphviz.org/doc/info/colors.html#svg)
     /// for a list of valid SVG color names.
     pub node_coloring_fn: NodeColoringFn,
 
     /// Function to generate edge colors. If none is provided, all edges will be colored black.
     ///
-    /// **Valid values**: Functions that implement `impl Fn(G::EdgeId) -> String`.
-    /// The returned string should be a valid SVG color (e.g., "red", "#ff0000", "rgb(255,0,0)").
+    /// **Valid values**: Functions that implement `impl Fn(G::EdgeId) -> impl Into<Color>`, e.g.
+    /// returning a [`Color`] or a `String`. The returned string should be a valid SVG color
+    /// (e.g., "red", "#ff0000", "rgb(255,0,0)").
     /// See [https://graphviz.org/doc/info/colors.html#svg](https://graphviz.org/doc/info/colors.html#svg)
     /// for a list of valid SVG color names.
     pub edge_coloring_fn: EdgeColoringFn,
+
+    /// Function to generate edge weights for the [`ForceDirected`](crate::Layout::ForceDirected)
+    /// layout. The ideal length of each edge is scaled according to its weight and the
+    /// [`edge_weight_mode`](Self::edge_weight_mode). If none is provided, all edges have the same
+    /// weight. Has no effect on other layouts or custom position maps.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::EdgeId) -> f32`. The returned weights
+    /// should be strictly positive and finite. Other weights are treated as 1.0.
+    pub edge_weight_fn: EdgeWeightFn,
+
+    /// Whether heavier edges pull their endpoints closer together or push them farther apart in
+    /// the force-directed layout.
+    ///
+    /// **Valid values**: Any [`EdgeWeightMode`].
+    pub edge_weight_mode: EdgeWeightMode,
+
+    /// Seed for the random initial positions of the [`ForceDirected`](crate::Layout::ForceDirected)
+    /// layout. The same seed always yields the same layout for the same graph, so different seeds can
+    /// be tried to pick the best out of several alternative layouts. If `None`, the layout is seeded
+    /// randomly and differs between runs. Has no effect on other layouts or custom position maps.
+    ///
+    /// **Valid values**: Any `Option<u64>`.
+    pub seed: Option<u64>,
+
+    /// Maximum duration of the simulation of the [`ForceDirected`](crate::Layout::ForceDirected)
+    /// layout. If the budget is exceeded, the simulation stops early and the positions reached so far
+    /// are used, which prevents long hangs for large graphs at the cost of a less settled layout. If
+    /// `None`, all iterations are run. Has no effect on other layouts or custom position maps.
+    ///
+    /// **Valid values**: Any `Option<Duration>`.
+    pub layout_time_budget: Option<Duration>,
+
+    /// Cache of layouts along with the key under which the positions of the layout are cached, see
+    /// [`LayoutCache`]. If the cache contains positions for the key, they are used instead of computing
+    /// the layout. Otherwise, the layout is computed and its positions are cached. Has no effect on
+    /// custom position maps.
+    ///
+    /// **Valid values**: Any `Option<(Arc<LayoutCache>, String)>`.
+    pub layout_cache: Option<(Arc<LayoutCache>, String)>,
+
+    /// Function to generate the fill opacity of nodes. Nodes with an opacity below 1.0 are drawn
+    /// with a `fill-opacity` attribute, which is useful to de-emphasize certain nodes. If none is
+    /// provided, all nodes are fully opaque.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> f32`. The returned
+    /// opacities should be in the range [0.0, 1.0]. Other values are clamped to this range.
+    pub node_opacity_fn: NodeOpacityFn,
+
+    /// Function to generate additional CSS classes of nodes. All nodes are given the class `node`. If
+    /// the returned string is non-empty, it is appended to the `class` attribute, such that nodes can be
+    /// styled individually by an external stylesheet. If none is provided, no additional classes are
+    /// emitted.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> String`. The returned string
+    /// may contain several classes separated by whitespace.
+    pub node_class_fn: NodeClassFn,
+
+    /// Function to generate additional CSS classes of edges. Works like
+    /// [`node_class_fn`](SettingsBuilder::node_class_fn), with edges being given the class `edge`.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::EdgeId) -> String`. The returned string
+    /// may contain several classes separated by whitespace.
+    pub edge_class_fn: EdgeClassFn,
+
+    /// Function to generate images of nodes. If it returns `Some(url)` for a node, the node is drawn
+    /// as an <image> of size `2 * radius` centered at its position instead of a circle, e.g. to show
+    /// icons in dashboards. The url is used as `href` and can be a data URI or a link to an image
+    /// file. If it returns `None`, the node is drawn as a circle. If none is provided, all nodes are
+    /// drawn as circles.
+    ///
+    /// Note that [`graph_to_img`](crate::graph_to_img) can only render images which resvg is able to
+    /// resolve, e.g. data URIs or paths to local files.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> Option<String>`. The
+    /// returned urls are escaped for use in an attribute.
+    pub node_image_fn: NodeImageFn,
+
+    /// Function to generate the curvature of edges, e.g. to bend an edge around a node it would
+    /// otherwise cross. If it returns a non-zero value for an edge, the edge is drawn as a quadratic
+    /// Bézier curve whose control point is shifted from the midpoint between the nodes by the
+    /// value times their distance, to the right of the direction from source to target for positive
+    /// values and to the left for negative values. Only applies to straight edges, that is, not to
+    /// [`smooth_tree_edges`](SettingsBuilder::smooth_tree_edges) or [`EdgeRouting::Orthogonal`]. If
+    /// none is provided, all edges are straight.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::EdgeId) -> f32`. Zero and non-finite
+    /// values result in straight edges.
+    pub edge_curvature_fn: EdgeCurvatureFn,
+
+    /// Function to generate the z-order of nodes, which determines which of two overlapping nodes is
+    /// drawn on top. Nodes are drawn in ascending order of their z-order, such that nodes with a higher
+    /// z-order are drawn later, i.e. on top. Nodes with the same z-order keep their order, see
+    /// [`node_draw_order`](SettingsBuilder::node_draw_order). If none is provided, all nodes have the
+    /// z-order 0.
+    ///
+    /// **Valid values**: Functions that implement `impl Fn(G::NodeId) -> i32`.
+    pub node_z_fn: NodeZFn,
 }
 
 impl Default
@@ -218,6 +1305,13 @@ impl Default
         DefaultEdgeLabelFn,
         DefaultNodeColoringFn,
         DefaultEdgeColoringFn,
+        DefaultEdgeWeightFn,
+        DefaultNodeOpacityFn,
+        DefaultNodeClassFn,
+        DefaultEdgeClassFn,
+        DefaultNodeImageFn,
+        DefaultEdgeCurvatureFn,
+        DefaultNodeZFn,
     >
 {
     /// Creates a new `SettingsBuilder` instance with default values.
@@ -233,10 +1327,64 @@ impl Default
             margin_x: DEFAULT_MARGIN,
             margin_y: DEFAULT_MARGIN,
             layout_or_pos_map: DEFAULT_LAYOUT_OR_POS_MAP,
+            pinned_positions: HashMap::new(),
+            circular_params: CircularParams::default(),
+            spiral_params: SpiralParams::default(),
+            hierarchical_params: HierarchicalParams::default(),
+            normalize_positions: DEFAULT_NORMALIZE_POSITIONS,
+            y_axis: DEFAULT_Y_AXIS,
+            snap_to_grid: DEFAULT_SNAP_TO_GRID,
+            avoid_overlaps: DEFAULT_AVOID_OVERLAPS,
+            auto_node_radius: DEFAULT_AUTO_NODE_RADIUS,
+            legend: Vec::new(),
+            legend_corner: DEFAULT_LEGEND_CORNER,
+            title: None,
+            draw_node_labels: DEFAULT_DRAW_NODE_LABELS,
+            rich_labels: DEFAULT_RICH_LABELS,
+            label_max_chars: DEFAULT_LABEL_MAX_CHARS,
+            show_index_badge: DEFAULT_SHOW_INDEX_BADGE,
+            node_stroke_style: DEFAULT_NODE_STROKE_STYLE,
+            node_shadow: DEFAULT_NODE_SHADOW,
+            draw_edge_labels: DEFAULT_DRAW_EDGE_LABELS,
+            edge_label_position: DEFAULT_EDGE_LABEL_POSITION,
+            rotate_edge_labels: DEFAULT_ROTATE_EDGE_LABELS,
+            bundle_parallel_edges: DEFAULT_BUNDLE_PARALLEL_EDGES,
+            separate_reciprocal_edges: DEFAULT_SEPARATE_RECIPROCAL_EDGES,
+            arrow_style: DEFAULT_ARROW_STYLE,
+            edge_arrow_mode: DEFAULT_EDGE_ARROW_MODE,
+            min_edge_length: DEFAULT_MIN_EDGE_LENGTH,
+            smooth_tree_edges: DEFAULT_SMOOTH_TREE_EDGES,
+            edge_routing: DEFAULT_EDGE_ROUTING,
+            responsive: DEFAULT_RESPONSIVE,
+            units: DEFAULT_UNITS,
+            background_color: None,
+            draw_debug_grid: DEFAULT_DRAW_DEBUG_GRID,
+            xml_declaration: DEFAULT_XML_DECLARATION,
+            extra_defs: String::new(),
+            emit_element_ids: DEFAULT_EMIT_ELEMENT_IDS,
+            emit_css_classes: DEFAULT_EMIT_CSS_CLASSES,
+            embed_metadata: DEFAULT_EMBED_METADATA,
+            highlighted_nodes: HashSet::new(),
+            highlighted_edges: HashSet::new(),
+            node_draw_order: None,
+            highlight_color: DEFAULT_HIGHLIGHT_COLOR.to_string(),
+            highlight_stroke_width: DEFAULT_HIGHLIGHT_STROKE_WIDTH,
+            validate_colors: DEFAULT_VALIDATE_COLORS,
             node_label_fn: DEFAULT_NODE_LABEL_FN,
             edge_label_fn: DEFAULT_EDGE_LABEL_FN,
             node_coloring_fn: DEFAULT_NODE_COLORING_FN,
             edge_coloring_fn: DEFAULT_EDGE_COLORING_FN,
+            edge_weight_fn: DEFAULT_EDGE_WEIGHT_FN,
+            edge_weight_mode: DEFAULT_EDGE_WEIGHT_MODE,
+            seed: None,
+            layout_time_budget: None,
+            layout_cache: None,
+            node_opacity_fn: DEFAULT_NODE_OPACITY_FN,
+            node_class_fn: DEFAULT_NODE_CLASS_FN,
+            edge_class_fn: DEFAULT_EDGE_CLASS_FN,
+            node_image_fn: DEFAULT_NODE_IMAGE_FN,
+            edge_curvature_fn: DEFAULT_EDGE_CURVATURE_FN,
+            node_z_fn: DEFAULT_NODE_Z_FN,
         }
     }
 }
@@ -248,6 +1396,13 @@ impl
         DefaultEdgeLabelFn,
         DefaultNodeColoringFn,
         DefaultEdgeColoringFn,
+        DefaultEdgeWeightFn,
+        DefaultNodeOpacityFn,
+        DefaultNodeClassFn,
+        DefaultEdgeClassFn,
+        DefaultNodeImageFn,
+        DefaultEdgeCurvatureFn,
+        DefaultNodeZFn,
     >
 {
     /// Creates a new `SettingsBuilder` instance with default values.
@@ -256,24 +1411,75 @@ impl
     pub fn new() -> Self {
         SettingsBuilder::default()
     }
-}
 
-impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
-    SettingsBuilder<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
-{
-    /// Sets the width of the SVG canvas and returns the modified [`SettingsBuilder`].
-    ///
-    /// For valid values, see the field documentation.
+    /// Creates a new `SettingsBuilder` instance configured for black-and-white figures in
+    /// publications, as a starting point for further customization.
     ///
-    /// The default width is [`DEFAULT_WIDTH`].
-    pub fn width(mut self, width: f32) -> Self {
-        self.width = width;
-        self
+    /// Compared to [`SettingsBuilder::new`], the background is white, edges are thin and black,
+    /// labels are small and the margins are narrower. All other values are the defaults.
+    pub fn publication_preset() -> Self {
+        SettingsBuilder::new()
+            .background_color(Some("white".to_string()))
+            .stroke_width(PUBLICATION_STROKE_WIDTH)
+            .font_size(PUBLICATION_FONT_SIZE)
+            .margin_x(PUBLICATION_MARGIN)
+            .margin_y(PUBLICATION_MARGIN)
+            .highlight_color("black".to_string())
+            .highlight_stroke_width(2.0 * PUBLICATION_STROKE_WIDTH)
     }
+}
 
-    /// Sets the height of the SVG canvas and returns the modified [`SettingsBuilder`].
-    ///
-    /// For valid values, see the field documentation.
+impl<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+{
+    /// Sets the width of the SVG canvas and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default width is [`DEFAULT_WIDTH`].
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::width`], but validates the width immediately instead of upon
+    /// calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::Dimensions` if the width is invalid.
+    pub fn try_width(self, width: f32) -> Result<Self, InvalidSettingsError> {
+        validate_dimensions(width, self.height)?;
+        Ok(self.width(width))
+    }
+
+    /// Sets the height of the SVG canvas and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
     ///
     /// The default height is [`DEFAULT_HEIGHT`].
     pub fn height(mut self, height: f32) -> Self {
@@ -281,74 +1487,1459 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
         self
     }
 
-    /// Sets the radius of the nodes in pixels and returns the modified [`SettingsBuilder`].
-    ///
-    /// For valid values, see the field documentation.
+    /// Same as [`SettingsBuilder::height`], but validates the height immediately instead of upon
+    /// calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::Dimensions` if the height is invalid.
+    pub fn try_height(self, height: f32) -> Result<Self, InvalidSettingsError> {
+        validate_dimensions(self.width, height)?;
+        Ok(self.height(height))
+    }
+
+    /// Sets the radius of the nodes in pixels and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default radius is [`DEFAULT_RADIUS`].
+    pub fn node_radius(mut self, radius: f32) -> Self {
+        self.node_radius = radius;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::node_radius`], but validates the radius immediately instead of
+    /// upon calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::Radius` if the radius is invalid.
+    pub fn try_node_radius(self, radius: f32) -> Result<Self, InvalidSettingsError> {
+        validate_radius(radius)?;
+        Ok(self.node_radius(radius))
+    }
+
+    /// Sets the font size for labels in pixels and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default font size is [`DEFAULT_FONT_SIZE`].
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::font_size`], but validates the font size immediately instead of
+    /// upon calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::FontSize` if the font size is invalid.
+    pub fn try_font_size(self, font_size: f32) -> Result<Self, InvalidSettingsError> {
+        validate_font_size(font_size)?;
+        Ok(self.font_size(font_size))
+    }
+
+    /// Sets the stroke width for edges in pixels and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default stroke width is [`DEFAULT_STROKE_WIDTH`].
+    pub fn stroke_width(mut self, stroke_width: f32) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::stroke_width`], but validates the stroke width immediately
+    /// instead of upon calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::StrokeWidth` if the stroke width is invalid.
+    pub fn try_stroke_width(self, stroke_width: f32) -> Result<Self, InvalidSettingsError> {
+        validate_stroke_width(stroke_width)?;
+        Ok(self.stroke_width(stroke_width))
+    }
+
+    /// Sets the horizontal margin as a fraction of the width and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default margin is [`DEFAULT_MARGIN`].
+    pub fn margin_x(mut self, margin_x: f32) -> Self {
+        self.margin_x = margin_x;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::margin_x`], but validates the margin immediately instead of upon
+    /// calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::Margin` if the margin is invalid.
+    pub fn try_margin_x(self, margin_x: f32) -> Result<Self, InvalidSettingsError> {
+        validate_margins(margin_x, self.margin_y)?;
+        Ok(self.margin_x(margin_x))
+    }
+
+    /// Sets the vertical margin as a fraction of the height and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default margin is [`DEFAULT_MARGIN`].
+    pub fn margin_y(mut self, margin_y: f32) -> Self {
+        self.margin_y = margin_y;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::margin_y`], but validates the margin immediately instead of upon
+    /// calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::Margin` if the margin is invalid.
+    pub fn try_margin_y(self, margin_y: f32) -> Result<Self, InvalidSettingsError> {
+        validate_margins(self.margin_x, margin_y)?;
+        Ok(self.margin_y(margin_y))
+    }
+
+    /// Sets the layout algorithm and returns the modified [`SettingsBuilder`].
+    ///
+    /// Note that this overrides any position map previously set using the
+    /// [`SettingsBuilder::position_map`] method.
+    ///
+    /// To provide a custom position map, use the [`SettingsBuilder::position_map`] method instead.
+    pub fn layout(
+        self,
+        layout: Layout,
+    ) -> SettingsBuilder<
+        DefaultPositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    > {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: LayoutOrPositionMap::Layout(layout),
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
+            edge_label_fn: self.edge_label_fn,
+            node_coloring_fn: self.node_coloring_fn,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets the custom position map function and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid position map functions, see the field documentation.
+    ///
+    /// Note that this overrides any layout algorithm previously set using the
+    /// [`SettingsBuilder::layout`] method.
+    ///
+    /// To use a predefined layout algorithm, use the [`SettingsBuilder::layout`] method instead.
+    pub fn position_map<NewPositionMapFn>(
+        self,
+        position_map: NewPositionMapFn,
+    ) -> SettingsBuilder<
+        NewPositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewPositionMapFn: Fn(NodeIndex) -> (f32, f32),
+    {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: LayoutOrPositionMap::PositionMap(position_map),
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
+            edge_label_fn: self.edge_label_fn,
+            node_coloring_fn: self.node_coloring_fn,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets the positions of nodes which stay fixed in the force-directed layout and returns the
+    /// modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, no nodes are pinned.
+    pub fn pinned_positions(mut self, pinned_positions: HashMap<NodeIndex, (f32, f32)>) -> Self {
+        self.pinned_positions = pinned_positions;
+        self
+    }
+
+    /// Sets the radius and center of the ring of the circular layout and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, the ring has a radius of 0.5 and is centered at (0.5, 0.5).
+    pub fn circular_params(mut self, circular_params: CircularParams) -> Self {
+        self.circular_params = circular_params;
+        self
+    }
+
+    /// Sets the number of turns and the spacing between turns of the spiral layout and returns
+    /// the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, the spiral has 3 turns which are 1/6 apart, such that it fills the drawing
+    /// area.
+    pub fn spiral_params(mut self, spiral_params: SpiralParams) -> Self {
+        self.spiral_params = spiral_params;
+        self
+    }
+
+    /// Sets the spacing between levels and siblings of the hierarchical layout and returns the
+    /// modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, levels and siblings are spread evenly across the drawing area.
+    pub fn hierarchical_params(mut self, hierarchical_params: HierarchicalParams) -> Self {
+        self.hierarchical_params = hierarchical_params;
+        self
+    }
+
+    /// Sets the mode for normalizing positions and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default mode is [`DEFAULT_NORMALIZE_POSITIONS`].
+    pub fn normalize_positions(mut self, normalize_positions: NormalizeMode) -> Self {
+        self.normalize_positions = normalize_positions;
+        self
+    }
+
+    /// Sets the direction of the y-axis and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default direction is [`DEFAULT_Y_AXIS`].
+    pub fn y_axis(mut self, y_axis: YAxis) -> Self {
+        self.y_axis = y_axis;
+        self
+    }
+
+    /// Sets the number of grid divisions to snap positions to and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default is [`DEFAULT_SNAP_TO_GRID`].
+    pub fn snap_to_grid(mut self, snap_to_grid: Option<usize>) -> Self {
+        self.snap_to_grid = snap_to_grid;
+        self
+    }
+
+    /// Sets whether overlapping nodes are nudged apart and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_AVOID_OVERLAPS`].
+    pub fn avoid_overlaps(mut self, avoid_overlaps: bool) -> Self {
+        self.avoid_overlaps = avoid_overlaps;
+        self
+    }
+
+    /// Sets whether the node radius is chosen automatically such that no two nodes overlap and
+    /// returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_AUTO_NODE_RADIUS`].
+    pub fn auto_node_radius(mut self, auto_node_radius: bool) -> Self {
+        self.auto_node_radius = auto_node_radius;
+        self
+    }
+
+    /// Sets the entries of the legend as `(color, label)` pairs and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, the legend is empty and thus not drawn.
+    pub fn legend(mut self, legend: Vec<(String, String)>) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Sets the corner in which the legend is drawn and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default corner is [`DEFAULT_LEGEND_CORNER`].
+    pub fn legend_corner(mut self, legend_corner: Corner) -> Self {
+        self.legend_corner = legend_corner;
+        self
+    }
+
+    /// Sets the title of the graph and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, no title is drawn.
+    pub fn title(mut self, title: Option<String>) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Sets whether node labels are drawn and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_DRAW_NODE_LABELS`].
+    pub fn draw_node_labels(mut self, draw_node_labels: bool) -> Self {
+        self.draw_node_labels = draw_node_labels;
+        self
+    }
+
+    /// Sets whether node labels are interpreted as rich labels with subscripts and superscripts
+    /// and returns the modified [`SettingsBuilder`].
+    ///
+    /// For the markup, see the field documentation.
+    ///
+    /// The default is [`DEFAULT_RICH_LABELS`].
+    pub fn rich_labels(mut self, rich_labels: bool) -> Self {
+        self.rich_labels = rich_labels;
+        self
+    }
+
+    /// Sets the maximum number of characters of labels, beyond which they are truncated with an
+    /// ellipsis, and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default is [`DEFAULT_LABEL_MAX_CHARS`].
+    pub fn label_max_chars(mut self, label_max_chars: Option<usize>) -> Self {
+        self.label_max_chars = label_max_chars;
+        self
+    }
+
+    /// Sets whether the index of each node is drawn as a small badge next to the node and returns
+    /// the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_SHOW_INDEX_BADGE`].
+    pub fn show_index_badge(mut self, show_index_badge: bool) -> Self {
+        self.show_index_badge = show_index_badge;
+        self
+    }
+
+    /// Sets the style of the node outlines and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_NODE_STROKE_STYLE`].
+    pub fn node_stroke_style(mut self, node_stroke_style: EdgeStyle) -> Self {
+        self.node_stroke_style = node_stroke_style;
+        self
+    }
+
+    /// Sets whether nodes cast a drop shadow and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_NODE_SHADOW`].
+    pub fn node_shadow(mut self, node_shadow: bool) -> Self {
+        self.node_shadow = node_shadow;
+        self
+    }
+
+    /// Sets whether edge labels are drawn and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_DRAW_EDGE_LABELS`].
+    pub fn draw_edge_labels(mut self, draw_edge_labels: bool) -> Self {
+        self.draw_edge_labels = draw_edge_labels;
+        self
+    }
+
+    /// Sets the position of edge labels as fraction along the edge and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default position is [`DEFAULT_EDGE_LABEL_POSITION`].
+    pub fn edge_label_position(mut self, edge_label_position: f32) -> Self {
+        self.edge_label_position = edge_label_position;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::edge_label_position`], but validates the position immediately
+    /// instead of upon calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::EdgeLabelPosition` if the position is invalid.
+    pub fn try_edge_label_position(
+        self,
+        edge_label_position: f32,
+    ) -> Result<Self, InvalidSettingsError> {
+        validate_edge_label_position(edge_label_position)?;
+        Ok(self.edge_label_position(edge_label_position))
+    }
+
+    /// Sets whether edge labels are rotated to follow the direction of their edge and returns the
+    /// modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_ROTATE_EDGE_LABELS`].
+    pub fn rotate_edge_labels(mut self, rotate_edge_labels: bool) -> Self {
+        self.rotate_edge_labels = rotate_edge_labels;
+        self
+    }
+
+    /// Sets whether parallel edges are bundled into a single line and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_BUNDLE_PARALLEL_EDGES`].
+    pub fn bundle_parallel_edges(mut self, bundle_parallel_edges: bool) -> Self {
+        self.bundle_parallel_edges = bundle_parallel_edges;
+        self
+    }
+
+    /// Sets whether reciprocal directed edges are drawn as two parallel lines with arrowheads and
+    /// returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_SEPARATE_RECIPROCAL_EDGES`].
+    pub fn separate_reciprocal_edges(mut self, separate_reciprocal_edges: bool) -> Self {
+        self.separate_reciprocal_edges = separate_reciprocal_edges;
+        self
+    }
+
+    /// Sets the shape of the arrowheads of directed edges and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_ARROW_STYLE`].
+    pub fn arrow_style(mut self, arrow_style: ArrowStyle) -> Self {
+        self.arrow_style = arrow_style;
+        self
+    }
+
+    /// Sets the ends of the edges at which arrowheads are drawn and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_EDGE_ARROW_MODE`].
+    pub fn edge_arrow_mode(mut self, edge_arrow_mode: ArrowMode) -> Self {
+        self.edge_arrow_mode = edge_arrow_mode;
+        self
+    }
+
+    /// Sets the minimum distance between two nodes for the edge between them to be drawn and
+    /// returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default is [`DEFAULT_MIN_EDGE_LENGTH`].
+    pub fn min_edge_length(mut self, min_edge_length: f32) -> Self {
+        self.min_edge_length = min_edge_length;
+        self
+    }
+
+    /// Sets whether edges of the hierarchical layout are drawn as smooth curves and returns the
+    /// modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_SMOOTH_TREE_EDGES`].
+    pub fn smooth_tree_edges(mut self, smooth_tree_edges: bool) -> Self {
+        self.smooth_tree_edges = smooth_tree_edges;
+        self
+    }
+
+    /// Sets how edges are routed between their nodes and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_EDGE_ROUTING`].
+    pub fn edge_routing(mut self, edge_routing: EdgeRouting) -> Self {
+        self.edge_routing = edge_routing;
+        self
+    }
+
+    /// Sets whether the SVG is responsive and returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_RESPONSIVE`].
+    pub fn responsive(mut self, responsive: bool) -> Self {
+        self.responsive = responsive;
+        self
+    }
+
+    /// Sets the units of the width and height of the canvas and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_UNITS`].
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets the background color of the canvas and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, the background is transparent.
+    pub fn background_color(mut self, background_color: Option<String>) -> Self {
+        self.background_color = background_color;
+        self
+    }
+
+    /// Sets whether a grid of the normalized coordinates is drawn beneath the graph and returns the
+    /// modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_DRAW_DEBUG_GRID`].
+    pub fn draw_debug_grid(mut self, draw_debug_grid: bool) -> Self {
+        self.draw_debug_grid = draw_debug_grid;
+        self
+    }
+
+    /// Sets whether the SVG data starts with an XML declaration and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_XML_DECLARATION`].
+    pub fn xml_declaration(mut self, xml_declaration: bool) -> Self {
+        self.xml_declaration = xml_declaration;
+        self
+    }
+
+    /// Sets the custom definitions inserted into a <defs> tag and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, no custom definitions are added.
+    pub fn extra_defs(mut self, extra_defs: String) -> Self {
+        self.extra_defs = extra_defs;
+        self
+    }
+
+    /// Sets whether nodes and edges are given `id` attributes derived from their index and returns
+    /// the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_EMIT_ELEMENT_IDS`].
+    pub fn emit_element_ids(mut self, emit_element_ids: bool) -> Self {
+        self.emit_element_ids = emit_element_ids;
+        self
+    }
+
+    /// Sets whether nodes and edges are given CSS classes and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_EMIT_CSS_CLASSES`].
+    pub fn emit_css_classes(mut self, emit_css_classes: bool) -> Self {
+        self.emit_css_classes = emit_css_classes;
+        self
+    }
+
+    /// Sets whether a metadata comment is embedded into the SVG and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_EMBED_METADATA`].
+    pub fn embed_metadata(mut self, embed_metadata: bool) -> Self {
+        self.embed_metadata = embed_metadata;
+        self
+    }
+
+    /// Sets the highlighted nodes and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, no nodes are highlighted.
+    pub fn highlighted_nodes(mut self, highlighted_nodes: HashSet<NodeIndex>) -> Self {
+        self.highlighted_nodes = highlighted_nodes;
+        self
+    }
+
+    /// Sets the highlighted edges and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, no edges are highlighted.
+    pub fn highlighted_edges(
+        mut self,
+        highlighted_edges: HashSet<petgraph::prelude::EdgeIndex>,
+    ) -> Self {
+        self.highlighted_edges = highlighted_edges;
+        self
+    }
+
+    /// Sets the order in which the nodes are drawn and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// By default, nodes are drawn in index order.
+    pub fn node_draw_order(mut self, node_draw_order: Option<Vec<NodeIndex>>) -> Self {
+        self.node_draw_order = node_draw_order;
+        self
+    }
+
+    /// Highlights the edges along the path given by the sequence of `nodes` in the `graph` and
+    /// returns the modified [`SettingsBuilder`].
+    ///
+    /// For each pair of consecutive nodes, an edge from the first to the second node is added to
+    /// the [`highlighted_edges`](Self::highlighted_edges). Edges which are already highlighted stay
+    /// highlighted. For directed graphs, the edges have to point along the path.
+    ///
+    /// Returns a [`NotConnectedError`] with the first pair of consecutive nodes that is not
+    /// connected by an edge.
+    pub fn highlight_path<G>(
+        mut self,
+        graph: G,
+        nodes: &[NodeIndex],
+    ) -> Result<Self, NotConnectedError>
+    where
+        G: IntoEdges<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+    {
+        for pair in nodes.windows(2) {
+            let (source, target) = (pair[0], pair[1]);
+            let edge = graph
+                .edges(source)
+                .find(|edge| edge.target() == target)
+                .ok_or(NotConnectedError(source, target))?;
+            self.highlighted_edges.insert(edge.id());
+        }
+        Ok(self)
+    }
+
+    /// Sets the color of highlighted nodes and edges and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default color is [`DEFAULT_HIGHLIGHT_COLOR`].
+    pub fn highlight_color(mut self, highlight_color: String) -> Self {
+        self.highlight_color = highlight_color;
+        self
+    }
+
+    /// Sets the stroke width of highlighted edges and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid values, see the field documentation.
+    ///
+    /// The default stroke width is [`DEFAULT_HIGHLIGHT_STROKE_WIDTH`].
+    pub fn highlight_stroke_width(mut self, highlight_stroke_width: f32) -> Self {
+        self.highlight_stroke_width = highlight_stroke_width;
+        self
+    }
+
+    /// Same as [`SettingsBuilder::highlight_stroke_width`], but validates the stroke width
+    /// immediately instead of upon calling `build()`.
+    ///
+    /// Returns an `InvalidSettingsError::StrokeWidth` if the stroke width is invalid.
+    pub fn try_highlight_stroke_width(
+        self,
+        highlight_stroke_width: f32,
+    ) -> Result<Self, InvalidSettingsError> {
+        validate_stroke_width(highlight_stroke_width)?;
+        Ok(self.highlight_stroke_width(highlight_stroke_width))
+    }
+
+    /// Sets whether the colors returned by the coloring functions are validated before drawing and
+    /// returns the modified [`SettingsBuilder`].
+    ///
+    /// The default is [`DEFAULT_VALIDATE_COLORS`].
+    pub fn validate_colors(mut self, validate_colors: bool) -> Self {
+        self.validate_colors = validate_colors;
+        self
+    }
+
+    /// Sets the node label function and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid node label functions, see the field documentation.
+    pub fn node_label_fn<NewNodeLabelFn>(
+        self,
+        node_label: NewNodeLabelFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NewNodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewNodeLabelFn: Fn(NodeIndex) -> String,
+    {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: node_label,
+            edge_label_fn: self.edge_label_fn,
+            node_coloring_fn: self.node_coloring_fn,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets the edge label function and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid edge label functions, see the field documentation.
+    pub fn edge_label_fn<NewEdgeLabelFn>(
+        self,
+        edge_label: NewEdgeLabelFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        NewEdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewEdgeLabelFn: Fn(petgraph::prelude::EdgeIndex) -> String,
+    {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
+            edge_label_fn: edge_label,
+            node_coloring_fn: self.node_coloring_fn,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets the node label function to label each node with its weight and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The weights are formatted using their [`Display`] implementation, see
+    /// [`labels::node_weights`]. The label function borrows the `graph`, such that it does not
+    /// need to be cloned.
+    pub fn node_labels_from_weights<'a, G>(
+        self,
+        graph: &'a G,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        impl Fn(NodeIndex) -> String + 'a,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        G: DataMap + GraphBase<NodeId = NodeIndex>,
+        G::NodeWeight: Display,
+    {
+        self.node_label_fn(labels::node_weights(graph))
+    }
+
+    /// Sets the edge label function to label each edge with its weight and returns the modified
+    /// [`SettingsBuilder`].
+    ///
+    /// The weights are formatted using their [`Display`] implementation, see
+    /// [`labels::edge_weights`]. The label function borrows the `graph`, such that it does not
+    /// need to be cloned.
+    pub fn edge_labels_from_weights<'a, G>(
+        self,
+        graph: &'a G,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        impl Fn(petgraph::prelude::EdgeIndex) -> String + 'a,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        G: DataMap + GraphBase<EdgeId = petgraph::prelude::EdgeIndex>,
+        G::EdgeWeight: Display,
+    {
+        self.edge_label_fn(labels::edge_weights(graph))
+    }
+
+    /// Sets both the node and the edge label function to label each node and edge with its weight
+    /// and returns the modified [`SettingsBuilder`].
+    ///
+    /// Combines [`node_labels_from_weights`](SettingsBuilder::node_labels_from_weights) and
+    /// [`edge_labels_from_weights`](SettingsBuilder::edge_labels_from_weights).
+    pub fn labels_from_weights<'a, G>(
+        self,
+        graph: &'a G,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        impl Fn(NodeIndex) -> String + 'a,
+        impl Fn(petgraph::prelude::EdgeIndex) -> String + 'a,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        G: DataMap + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+        G::NodeWeight: Display,
+        G::EdgeWeight: Display,
+    {
+        self.node_labels_from_weights(graph)
+            .edge_labels_from_weights(graph)
+    }
+
+    /// Sets the edge label function to a function of the [`EdgeContext`] of each edge, that is,
+    /// its endpoints and weight in the `graph`, and returns the modified [`SettingsBuilder`].
+    ///
+    /// Unlike [`edge_label_fn`](SettingsBuilder::edge_label_fn), the function does not need to
+    /// look up the edge in the graph itself. The resulting label function panics if called with an
+    /// edge which is not contained in the `graph`, so the settings should only be used to draw it.
+    pub fn edge_label_fn_with_context<'a, G, F>(
+        self,
+        graph: &'a G,
+        edge_label: F,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        impl Fn(petgraph::prelude::EdgeIndex) -> String + 'a,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        G: DataMap + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+        &'a G: IntoEdgeReferences
+            + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+        F: Fn(EdgeContext<'a, G::EdgeWeight>) -> String + 'a,
+    {
+        let context = edge_contexts(graph);
+        self.edge_label_fn(move |id| edge_label(context(id)))
+    }
+
+    /// Sets the node coloring function and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid node coloring functions, see the field documentation.
+    pub fn node_coloring_fn<NewNodeColoringFn, NodeColor>(
+        self,
+        node_coloring: NewNodeColoringFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NewNodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewNodeColoringFn: Fn(NodeIndex) -> NodeColor,
+        NodeColor: Into<NodeFill>,
+    {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
+            edge_label_fn: self.edge_label_fn,
+            node_coloring_fn: node_coloring,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets the edge coloring function and returns the modified [`SettingsBuilder`].
+    ///
+    /// For valid edge coloring functions, see the field documentation.
+    pub fn edge_coloring_fn<NewEdgeColoringFn, EdgeColor>(
+        self,
+        edge_coloring: NewEdgeColoringFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        NewEdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewEdgeColoringFn: Fn(petgraph::prelude::EdgeIndex) -> EdgeColor,
+        EdgeColor: Into<Color>,
+    {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
+            edge_label_fn: self.edge_label_fn,
+            node_coloring_fn: self.node_coloring_fn,
+            edge_coloring_fn: edge_coloring,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets the edge coloring function to a function of the [`EdgeContext`] of each edge, that
+    /// is, its endpoints and weight in the `graph`, and returns the modified [`SettingsBuilder`].
     ///
-    /// The default radius is [`DEFAULT_RADIUS`].
-    pub fn node_radius(mut self, radius: f32) -> Self {
-        self.node_radius = radius;
-        self
+    /// Unlike [`edge_coloring_fn`](SettingsBuilder::edge_coloring_fn), the function does not need
+    /// to look up the edge in the graph itself. The resulting coloring function panics if called
+    /// with an edge which is not contained in the `graph`, so the settings should only be used to
+    /// draw it.
+    pub fn edge_coloring_fn_with_context<'a, G, F, EdgeColor>(
+        self,
+        graph: &'a G,
+        edge_coloring: F,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        impl Fn(petgraph::prelude::EdgeIndex) -> EdgeColor + 'a,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        G: DataMap + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+        &'a G: IntoEdgeReferences
+            + GraphBase<NodeId = NodeIndex, EdgeId = petgraph::prelude::EdgeIndex>,
+        F: Fn(EdgeContext<'a, G::EdgeWeight>) -> EdgeColor + 'a,
+        EdgeColor: Into<Color>,
+    {
+        let context = edge_contexts(graph);
+        self.edge_coloring_fn(move |id| edge_coloring(context(id)))
     }
 
-    /// Sets the font size for labels in pixels and returns the modified [`SettingsBuilder`].
+    /// Sets the edge weight function and returns the modified [`SettingsBuilder`].
     ///
-    /// For valid values, see the field documentation.
+    /// For valid edge weight functions, see the field documentation.
+    pub fn edge_weight_fn<NewEdgeWeightFn>(
+        self,
+        edge_weight: NewEdgeWeightFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        NewEdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewEdgeWeightFn: Fn(petgraph::prelude::EdgeIndex) -> f32,
+    {
+        SettingsBuilder {
+            width: self.width,
+            height: self.height,
+            node_radius: self.node_radius,
+            font_size: self.font_size,
+            stroke_width: self.stroke_width,
+            margin_x: self.margin_x,
+            margin_y: self.margin_y,
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
+            edge_label_fn: self.edge_label_fn,
+            node_coloring_fn: self.node_coloring_fn,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: edge_weight,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
+        }
+    }
+
+    /// Sets whether heavier edges pull their endpoints closer together or push them farther apart
+    /// in the force-directed layout and returns the modified [`SettingsBuilder`].
     ///
-    /// The default font size is [`DEFAULT_FONT_SIZE`].
-    pub fn font_size(mut self, font_size: f32) -> Self {
-        self.font_size = font_size;
+    /// The default mode is [`DEFAULT_EDGE_WEIGHT_MODE`].
+    pub fn edge_weight_mode(mut self, edge_weight_mode: EdgeWeightMode) -> Self {
+        self.edge_weight_mode = edge_weight_mode;
         self
     }
 
-    /// Sets the stroke width for edges in pixels and returns the modified [`SettingsBuilder`].
+    /// Sets the seed for the initial positions of the force-directed layout and returns the
+    /// modified [`SettingsBuilder`].
     ///
     /// For valid values, see the field documentation.
     ///
-    /// The default stroke width is [`DEFAULT_STROKE_WIDTH`].
-    pub fn stroke_width(mut self, stroke_width: f32) -> Self {
-        self.stroke_width = stroke_width;
+    /// By default, no seed is set and the layout differs between runs.
+    pub fn seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
         self
     }
 
-    /// Sets the horizontal margin as a fraction of the width and returns the modified
-    /// [`SettingsBuilder`].
+    /// Sets the maximum duration of the simulation of the force-directed layout and returns the
+    /// modified [`SettingsBuilder`].
     ///
     /// For valid values, see the field documentation.
     ///
-    /// The default margin is [`DEFAULT_MARGIN`].
-    pub fn margin_x(mut self, margin_x: f32) -> Self {
-        self.margin_x = margin_x;
+    /// By default, no time budget is set and all iterations are run.
+    pub fn layout_time_budget(mut self, layout_time_budget: Option<Duration>) -> Self {
+        self.layout_time_budget = layout_time_budget;
         self
     }
 
-    /// Sets the vertical margin as a fraction of the height and returns the modified
-    /// [`SettingsBuilder`].
-    ///
-    /// For valid values, see the field documentation.
+    /// Sets the cache of layouts and the key under which the positions of the layout are cached
+    /// and returns the modified [`SettingsBuilder`].
     ///
-    /// The default margin is [`DEFAULT_MARGIN`].
-    pub fn margin_y(mut self, margin_y: f32) -> Self {
-        self.margin_y = margin_y;
+    /// By default, no cache is set and the layout is computed on every render.
+    pub fn layout_cache(mut self, cache: Arc<LayoutCache>, key: impl Into<String>) -> Self {
+        self.layout_cache = Some((cache, key.into()));
         self
     }
 
-    /// Sets the layout algorithm and returns the modified [`SettingsBuilder`].
-    ///
-    /// Note that this overrides any position map previously set using the
-    /// [`SettingsBuilder::position_map`] method.
+    /// Sets the node opacity function and returns the modified [`SettingsBuilder`].
     ///
-    /// To provide a custom position map, use the [`SettingsBuilder::position_map`] method instead.
-    pub fn layout(
+    /// For valid node opacity functions, see the field documentation.
+    pub fn node_opacity_fn<NewNodeOpacityFn>(
         self,
-        layout: Layout,
+        node_opacity: NewNodeOpacityFn,
     ) -> SettingsBuilder<
-        DefaultPositionMapFn,
+        PositionMapFn,
         NodeLabelFn,
         EdgeLabelFn,
         NodeColoringFn,
         EdgeColoringFn,
-    > {
+        EdgeWeightFn,
+        NewNodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
+    where
+        NewNodeOpacityFn: Fn(NodeIndex) -> f32,
+    {
         SettingsBuilder {
             width: self.width,
             height: self.height,
@@ -357,28 +2948,90 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             stroke_width: self.stroke_width,
             margin_x: self.margin_x,
             margin_y: self.margin_y,
-            layout_or_pos_map: LayoutOrPositionMap::Layout(layout),
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
             node_label_fn: self.node_label_fn,
             edge_label_fn: self.edge_label_fn,
             node_coloring_fn: self.node_coloring_fn,
             edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: node_opacity,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
         }
     }
 
-    /// Sets the custom position map function and returns the modified [`SettingsBuilder`].
-    ///
-    /// For valid position map functions, see the field documentation.
-    ///
-    /// Note that this overrides any layout algorithm previously set using the
-    /// [`SettingsBuilder::layout`] method.
+    /// Sets the node class function and returns the modified [`SettingsBuilder`].
     ///
-    /// To use a predefined layout algorithm, use the [`SettingsBuilder::layout`] method instead.
-    pub fn position_map<NewPositionMapFn>(
+    /// For valid node class functions, see the field documentation.
+    pub fn node_class_fn<NewNodeClassFn>(
         self,
-        position_map: NewPositionMapFn,
-    ) -> SettingsBuilder<NewPositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
+        node_class: NewNodeClassFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NewNodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
     where
-        NewPositionMapFn: Fn(petgraph::prelude::NodeIndex) -> (f32, f32),
+        NewNodeClassFn: Fn(NodeIndex) -> String,
     {
         SettingsBuilder {
             width: self.width,
@@ -388,23 +3041,90 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             stroke_width: self.stroke_width,
             margin_x: self.margin_x,
             margin_y: self.margin_y,
-            layout_or_pos_map: LayoutOrPositionMap::PositionMap(position_map),
+            layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
             node_label_fn: self.node_label_fn,
             edge_label_fn: self.edge_label_fn,
             node_coloring_fn: self.node_coloring_fn,
             edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: node_class,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
         }
     }
 
-    /// Sets the node label function and returns the modified [`SettingsBuilder`].
+    /// Sets the edge class function and returns the modified [`SettingsBuilder`].
     ///
-    /// For valid node label functions, see the field documentation.
-    pub fn node_label_fn<NewNodeLabelFn>(
+    /// For valid edge class functions, see the field documentation.
+    pub fn edge_class_fn<NewEdgeClassFn>(
         self,
-        node_label: NewNodeLabelFn,
-    ) -> SettingsBuilder<PositionMapFn, NewNodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
+        edge_class: NewEdgeClassFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        NewEdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
     where
-        NewNodeLabelFn: Fn(petgraph::prelude::NodeIndex) -> String,
+        NewEdgeClassFn: Fn(petgraph::prelude::EdgeIndex) -> String,
     {
         SettingsBuilder {
             width: self.width,
@@ -415,22 +3135,89 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             margin_x: self.margin_x,
             margin_y: self.margin_y,
             layout_or_pos_map: self.layout_or_pos_map,
-            node_label_fn: node_label,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
+            node_label_fn: self.node_label_fn,
             edge_label_fn: self.edge_label_fn,
             node_coloring_fn: self.node_coloring_fn,
             edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: edge_class,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
         }
     }
 
-    /// Sets the edge label function and returns the modified [`SettingsBuilder`].
+    /// Sets the node image function and returns the modified [`SettingsBuilder`].
     ///
-    /// For valid edge label functions, see the field documentation.
-    pub fn edge_label_fn<NewEdgeLabelFn>(
+    /// For valid node image functions, see the field documentation.
+    pub fn node_image_fn<NewNodeImageFn>(
         self,
-        edge_label: NewEdgeLabelFn,
-    ) -> SettingsBuilder<PositionMapFn, NodeLabelFn, NewEdgeLabelFn, NodeColoringFn, EdgeColoringFn>
+        node_image: NewNodeImageFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NewNodeImageFn,
+        EdgeCurvatureFn,
+        NodeZFn,
+    >
     where
-        NewEdgeLabelFn: Fn(petgraph::prelude::EdgeIndex) -> String,
+        NewNodeImageFn: Fn(NodeIndex) -> Option<String>,
     {
         SettingsBuilder {
             width: self.width,
@@ -441,22 +3228,89 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             margin_x: self.margin_x,
             margin_y: self.margin_y,
             layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
             node_label_fn: self.node_label_fn,
-            edge_label_fn: edge_label,
+            edge_label_fn: self.edge_label_fn,
             node_coloring_fn: self.node_coloring_fn,
             edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: node_image,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
         }
     }
 
-    /// Sets the node coloring function and returns the modified [`SettingsBuilder`].
+    /// Sets the edge curvature function and returns the modified [`SettingsBuilder`].
     ///
-    /// For valid node coloring functions, see the field documentation.
-    pub fn node_coloring_fn<NewNodeColoringFn>(
+    /// For valid edge curvature functions, see the field documentation.
+    pub fn edge_curvature_fn<NewEdgeCurvatureFn>(
         self,
-        node_coloring: NewNodeColoringFn,
-    ) -> SettingsBuilder<PositionMapFn, NodeLabelFn, EdgeLabelFn, NewNodeColoringFn, EdgeColoringFn>
+        edge_curvature: NewEdgeCurvatureFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        NewEdgeCurvatureFn,
+        NodeZFn,
+    >
     where
-        NewNodeColoringFn: Fn(petgraph::prelude::NodeIndex) -> String,
+        NewEdgeCurvatureFn: Fn(petgraph::prelude::EdgeIndex) -> f32,
     {
         SettingsBuilder {
             width: self.width,
@@ -467,22 +3321,89 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             margin_x: self.margin_x,
             margin_y: self.margin_y,
             layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
             node_label_fn: self.node_label_fn,
             edge_label_fn: self.edge_label_fn,
-            node_coloring_fn: node_coloring,
+            node_coloring_fn: self.node_coloring_fn,
             edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: edge_curvature,
+            node_z_fn: self.node_z_fn,
         }
     }
 
-    /// Sets the edge coloring function and returns the modified [`SettingsBuilder`].
+    /// Sets the node z-order function and returns the modified [`SettingsBuilder`].
     ///
-    /// For valid edge coloring functions, see the field documentation.
-    pub fn edge_coloring_fn<NewEdgeColoringFn>(
+    /// For valid node z-order functions, see the field documentation.
+    pub fn node_z_fn<NewNodeZFn>(
         self,
-        edge_coloring: NewEdgeColoringFn,
-    ) -> SettingsBuilder<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, NewEdgeColoringFn>
+        node_z: NewNodeZFn,
+    ) -> SettingsBuilder<
+        PositionMapFn,
+        NodeLabelFn,
+        EdgeLabelFn,
+        NodeColoringFn,
+        EdgeColoringFn,
+        EdgeWeightFn,
+        NodeOpacityFn,
+        NodeClassFn,
+        EdgeClassFn,
+        NodeImageFn,
+        EdgeCurvatureFn,
+        NewNodeZFn,
+    >
     where
-        NewEdgeColoringFn: Fn(petgraph::prelude::EdgeIndex) -> String,
+        NewNodeZFn: Fn(NodeIndex) -> i32,
     {
         SettingsBuilder {
             width: self.width,
@@ -493,10 +3414,64 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             margin_x: self.margin_x,
             margin_y: self.margin_y,
             layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
             node_label_fn: self.node_label_fn,
             edge_label_fn: self.edge_label_fn,
             node_coloring_fn: self.node_coloring_fn,
-            edge_coloring_fn: edge_coloring,
+            edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: node_z,
         }
     }
 
@@ -505,38 +3480,59 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
     /// Checks that all settings are within acceptable ranges. If not, returns a corresponding
     /// [`SettingsError`].
     fn validate(&self) -> Result<(), InvalidSettingsError> {
-        if self.width <= 0.0 || self.height <= 0.0 {
-            return Err(InvalidSettingsError::Dimensions(self.width, self.height));
-        } else if self.node_radius <= 0.0 {
-            return Err(InvalidSettingsError::Radius(self.node_radius));
-        } else if self.font_size <= 0.0 {
-            return Err(InvalidSettingsError::FontSize(self.font_size));
-        } else if self.stroke_width <= 0.0 {
-            return Err(InvalidSettingsError::StrokeWidth(self.stroke_width));
-        } else if self.margin_x < 0.0
-            || self.margin_x > 0.5
-            || self.margin_y < 0.0
-            || self.margin_y > 0.5
-        {
-            return Err(InvalidSettingsError::Margin(self.margin_x, self.margin_y));
-        }
+        validate_dimensions(self.width, self.height)?;
+        validate_radius(self.node_radius)?;
+        validate_font_size(self.font_size)?;
+        validate_stroke_width(self.stroke_width)?;
+        validate_stroke_width(self.highlight_stroke_width)?;
+        validate_margins(self.margin_x, self.margin_y)?;
+        validate_edge_label_position(self.edge_label_position)?;
+        validate_min_edge_length(self.min_edge_length)?;
+        validate_hierarchical_params(self.hierarchical_params)?;
+        validate_circular_params(self.circular_params)?;
+        validate_pinned_positions(&self.pinned_positions)?;
+        validate_snap_to_grid(self.snap_to_grid)?;
+        validate_label_max_chars(self.label_max_chars)?;
+        validate_title(self.title.as_deref(), self.font_size, self.height)?;
 
         Ok(())
     }
 
     /// Builds the [`Settings`] instance after validating the provided values.
-    pub fn build(
+    pub fn build<NodeColor, EdgeColor>(
         self,
     ) -> Result<
-        Settings<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>,
+        Settings<
+            PositionMapFn,
+            NodeLabelFn,
+            EdgeLabelFn,
+            NodeColoringFn,
+            EdgeColoringFn,
+            EdgeWeightFn,
+            NodeOpacityFn,
+            NodeClassFn,
+            EdgeClassFn,
+            NodeImageFn,
+            EdgeCurvatureFn,
+            NodeZFn,
+        >,
         InvalidSettingsError,
     >
     where
-        PositionMapFn: Fn(petgraph::prelude::NodeIndex) -> (f32, f32),
-        NodeLabelFn: Fn(petgraph::prelude::NodeIndex) -> String,
+        PositionMapFn: Fn(NodeIndex) -> (f32, f32),
+        NodeLabelFn: Fn(NodeIndex) -> String,
         EdgeLabelFn: Fn(petgraph::prelude::EdgeIndex) -> String,
-        NodeColoringFn: Fn(petgraph::prelude::NodeIndex) -> String,
-        EdgeColoringFn: Fn(petgraph::prelude::EdgeIndex) -> String,
+        NodeColoringFn: Fn(NodeIndex) -> NodeColor,
+        NodeColor: Into<NodeFill>,
+        EdgeColoringFn: Fn(petgraph::prelude::EdgeIndex) -> EdgeColor,
+        EdgeColor: Into<Color>,
+        EdgeWeightFn: Fn(petgraph::prelude::EdgeIndex) -> f32,
+        NodeOpacityFn: Fn(NodeIndex) -> f32,
+        NodeClassFn: Fn(NodeIndex) -> String,
+        EdgeClassFn: Fn(petgraph::prelude::EdgeIndex) -> String,
+        NodeImageFn: Fn(NodeIndex) -> Option<String>,
+        EdgeCurvatureFn: Fn(petgraph::prelude::EdgeIndex) -> f32,
+        NodeZFn: Fn(NodeIndex) -> i32,
     {
         self.validate()?;
         let settings = Settings {
@@ -548,11 +3544,499 @@ impl<PositionMapFn, NodeLabelFn, EdgeLabelFn, NodeColoringFn, EdgeColoringFn>
             margin_x: self.margin_x,
             margin_y: self.margin_y,
             layout_or_pos_map: self.layout_or_pos_map,
+            pinned_positions: self.pinned_positions,
+            circular_params: self.circular_params,
+            spiral_params: self.spiral_params,
+            hierarchical_params: self.hierarchical_params,
+            normalize_positions: self.normalize_positions,
+            y_axis: self.y_axis,
+            snap_to_grid: self.snap_to_grid,
+            avoid_overlaps: self.avoid_overlaps,
+            auto_node_radius: self.auto_node_radius,
+            legend: self.legend,
+            legend_corner: self.legend_corner,
+            title: self.title,
+            draw_node_labels: self.draw_node_labels,
+            rich_labels: self.rich_labels,
+            label_max_chars: self.label_max_chars,
+            show_index_badge: self.show_index_badge,
+            node_stroke_style: self.node_stroke_style,
+            node_shadow: self.node_shadow,
+            draw_edge_labels: self.draw_edge_labels,
+            edge_label_position: self.edge_label_position,
+            rotate_edge_labels: self.rotate_edge_labels,
+            bundle_parallel_edges: self.bundle_parallel_edges,
+            separate_reciprocal_edges: self.separate_reciprocal_edges,
+            arrow_style: self.arrow_style,
+            edge_arrow_mode: self.edge_arrow_mode,
+            min_edge_length: self.min_edge_length,
+            smooth_tree_edges: self.smooth_tree_edges,
+            edge_routing: self.edge_routing,
+            responsive: self.responsive,
+            units: self.units,
+            background_color: self.background_color,
+            draw_debug_grid: self.draw_debug_grid,
+            xml_declaration: self.xml_declaration,
+            extra_defs: self.extra_defs,
+            emit_element_ids: self.emit_element_ids,
+            emit_css_classes: self.emit_css_classes,
+            embed_metadata: self.embed_metadata,
+            highlighted_nodes: self.highlighted_nodes,
+            highlighted_edges: self.highlighted_edges,
+            node_draw_order: self.node_draw_order,
+            highlight_color: self.highlight_color,
+            highlight_stroke_width: self.highlight_stroke_width,
+            validate_colors: self.validate_colors,
             node_label_fn: self.node_label_fn,
             edge_label_fn: self.edge_label_fn,
             node_coloring_fn: self.node_coloring_fn,
             edge_coloring_fn: self.edge_coloring_fn,
+            edge_weight_fn: self.edge_weight_fn,
+            edge_weight_mode: self.edge_weight_mode,
+            seed: self.seed,
+            layout_time_budget: self.layout_time_budget,
+            layout_cache: self.layout_cache,
+            node_opacity_fn: self.node_opacity_fn,
+            node_class_fn: self.node_class_fn,
+            edge_class_fn: self.edge_class_fn,
+            node_image_fn: self.node_image_fn,
+            edge_curvature_fn: self.edge_curvature_fn,
+            node_z_fn: self.node_z_fn,
         };
         Ok(settings)
     }
 }
+
+/// Returns whether the value is strictly positive and finite. Note that `NaN` fails every
+/// comparison and thus has to be excluded explicitly by checking for finiteness.
+fn is_positive_and_finite(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+/// Checks that the width and height are strictly positive and finite.
+fn validate_dimensions(width: f32, height: f32) -> Result<(), InvalidSettingsError> {
+    if !is_positive_and_finite(width) || !is_positive_and_finite(height) {
+        return Err(InvalidSettingsError::Dimensions(width, height));
+    }
+    Ok(())
+}
+
+/// Checks that the node radius is strictly positive and finite.
+fn validate_radius(radius: f32) -> Result<(), InvalidSettingsError> {
+    if !is_positive_and_finite(radius) {
+        return Err(InvalidSettingsError::Radius(radius));
+    }
+    Ok(())
+}
+
+/// Checks that the level and sibling spacing of the hierarchical layout are strictly positive and
+/// finite, if given.
+fn validate_hierarchical_params(params: HierarchicalParams) -> Result<(), InvalidSettingsError> {
+    for spacing in [params.level_spacing, params.sibling_spacing]
+        .into_iter()
+        .flatten()
+    {
+        if !is_positive_and_finite(spacing) {
+            return Err(InvalidSettingsError::HierarchicalSpacing(spacing));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the radius of the circular layout is strictly positive and finite and that its
+/// center is finite.
+fn validate_circular_params(params: CircularParams) -> Result<(), InvalidSettingsError> {
+    let CircularParams {
+        radius,
+        center: (center_x, center_y),
+    } = params;
+    if !is_positive_and_finite(radius) || !center_x.is_finite() || !center_y.is_finite() {
+        return Err(InvalidSettingsError::CircularParams(
+            radius, center_x, center_y,
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that all pinned positions are normalized, i.e. lie in the range [0.0, 1.0].
+fn validate_pinned_positions(
+    pinned_positions: &HashMap<NodeIndex, (f32, f32)>,
+) -> Result<(), InvalidSettingsError> {
+    for (&node, &(x, y)) in pinned_positions {
+        if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+            return Err(InvalidSettingsError::PinnedPosition(node, x, y));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the font size is strictly positive and finite.
+fn validate_font_size(font_size: f32) -> Result<(), InvalidSettingsError> {
+    if !is_positive_and_finite(font_size) {
+        return Err(InvalidSettingsError::FontSize(font_size));
+    }
+    Ok(())
+}
+
+/// Checks that the stroke width is strictly positive and finite.
+fn validate_stroke_width(stroke_width: f32) -> Result<(), InvalidSettingsError> {
+    if !is_positive_and_finite(stroke_width) {
+        return Err(InvalidSettingsError::StrokeWidth(stroke_width));
+    }
+    Ok(())
+}
+
+/// Checks that both margins lie in the range [0.0, 0.5]. This rejects `NaN`, since it is not
+/// contained in any range.
+fn validate_margins(margin_x: f32, margin_y: f32) -> Result<(), InvalidSettingsError> {
+    if !(0.0..=0.5).contains(&margin_x) || !(0.0..=0.5).contains(&margin_y) {
+        return Err(InvalidSettingsError::Margin(margin_x, margin_y));
+    }
+    Ok(())
+}
+
+/// Checks that the edge label position lies in the range [0.0, 1.0].
+fn validate_edge_label_position(edge_label_position: f32) -> Result<(), InvalidSettingsError> {
+    if !(0.0..=1.0).contains(&edge_label_position) {
+        return Err(InvalidSettingsError::EdgeLabelPosition(edge_label_position));
+    }
+    Ok(())
+}
+
+/// Checks that the minimum edge length is strictly positive and finite.
+fn validate_min_edge_length(min_edge_length: f32) -> Result<(), InvalidSettingsError> {
+    if !is_positive_and_finite(min_edge_length) {
+        return Err(InvalidSettingsError::MinEdgeLength(min_edge_length));
+    }
+    Ok(())
+}
+
+/// Checks that the number of grid divisions is at least 1, if given.
+fn validate_snap_to_grid(snap_to_grid: Option<usize>) -> Result<(), InvalidSettingsError> {
+    if snap_to_grid == Some(0) {
+        return Err(InvalidSettingsError::GridDivisions(0));
+    }
+    Ok(())
+}
+
+/// Checks that the space reserved for the title, if any, is non-negative and smaller than the
+/// `height` of the canvas, such that there is space left for the graph.
+fn validate_title(
+    title: Option<&str>,
+    font_size: f32,
+    height: f32,
+) -> Result<(), InvalidSettingsError> {
+    let Some(_) = title else {
+        return Ok(());
+    };
+    let title_height = title_height(font_size);
+    if !(0.0..height).contains(&title_height) {
+        return Err(InvalidSettingsError::TitleHeight(title_height, height));
+    }
+    Ok(())
+}
+
+/// Checks that the maximum number of characters of labels is at least 1, if given.
+fn validate_label_max_chars(label_max_chars: Option<usize>) -> Result<(), InvalidSettingsError> {
+    if label_max_chars == Some(0) {
+        return Err(InvalidSettingsError::LabelMaxChars(0));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use petgraph::graph::{EdgeIndex, NodeIndex, UnGraph};
+
+    use crate::{
+        errors::{InvalidSettingsError, NotConnectedError},
+        graph_to_svg::graph_to_svg_string,
+        layout::{circular::CircularParams, hierarchical::HierarchicalParams},
+        settings::{Color, NodeFill, SettingsBuilder},
+        Layout,
+    };
+
+    #[test]
+    fn test_labels_from_weights() {
+        let mut graph = UnGraph::new_undirected();
+        let node_a = graph.add_node("Ljubljana".to_string());
+        let node_b = graph.add_node("Bielefeld".to_string());
+        graph.add_edge(node_a, node_b, 42);
+
+        // The label functions borrow the graph, which is used afterwards without being cloned
+        let settings = SettingsBuilder::new()
+            .labels_from_weights(&graph)
+            .build()
+            .expect("Values should be valid.");
+        assert_eq!((settings.node_label_fn)(node_b), "Bielefeld");
+        assert_eq!((settings.edge_label_fn)(EdgeIndex::new(0)), "42");
+
+        let svg = graph_to_svg_string(&graph, &settings);
+        assert!(svg.contains(">Ljubljana</text>"));
+        assert!(svg.contains(">42</text>"));
+
+        // Edge weights do not need to implement `Display` if only nodes are labeled by weight
+        let graph = graph.map(|_, weight| weight.clone(), |_, _| ());
+        let settings = SettingsBuilder::new()
+            .node_labels_from_weights(&graph)
+            .build()
+            .expect("Values should be valid.");
+        assert_eq!((settings.node_label_fn)(node_a), "Ljubljana");
+    }
+
+    #[test]
+    fn test_getters() {
+        let settings = SettingsBuilder::new()
+            .width(800.0)
+            .height(600.0)
+            .node_radius(20.0)
+            .font_size(12.0)
+            .stroke_width(2.0)
+            .margin_x(0.2)
+            .margin_y(0.3)
+            .title(Some("Graph".to_string()))
+            .seed(Some(7))
+            .responsive(true)
+            .build()
+            .expect("Values should be valid.");
+
+        assert_eq!(settings.width(), 800.0);
+        assert_eq!(settings.height(), 600.0);
+        assert_eq!(settings.radius(), 20.0);
+        assert_eq!(settings.font_size(), 12.0);
+        assert_eq!(settings.stroke_width(), 2.0);
+        assert_eq!(settings.margin_x(), 0.2);
+        assert_eq!(settings.margin_y(), 0.3);
+        assert_eq!(settings.title(), Some("Graph"));
+        assert_eq!(settings.seed(), Some(7));
+        assert!(settings.responsive());
+    }
+
+    #[test]
+    fn test_layout_getter() {
+        let settings = SettingsBuilder::new()
+            .layout(Layout::Circular)
+            .build()
+            .expect("Values should be valid.");
+        assert!(matches!(settings.layout(), Some(Layout::Circular)));
+
+        let settings = SettingsBuilder::new()
+            .position_map(|_: NodeIndex| (0.5, 0.5))
+            .build()
+            .expect("Values should be valid.");
+        assert!(settings.layout().is_none());
+    }
+
+    #[test]
+    fn test_color_to_svg() {
+        assert_eq!(Color::rgb(255, 0, 0).to_svg(), "#ff0000");
+        assert_eq!(
+            Color::rgba(0, 128, 255, 0).to_svg(),
+            "rgba(0, 128, 255, 0.0)"
+        );
+        assert_eq!(Color::from("red").to_svg(), "red");
+        assert_eq!(Color::from((1, 2, 3)), Color::rgb(1, 2, 3));
+        assert_eq!(
+            NodeFill::from(Color::rgb(0, 0, 0)),
+            NodeFill::Solid("#000000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_color_from_hex() {
+        assert_eq!(Color::from_hex("#ff8000"), Some(Color::rgb(255, 128, 0)));
+        assert_eq!(Color::from_hex("#F80"), Some(Color::rgb(255, 136, 0)));
+        assert_eq!(
+            Color::from_hex("#ff800080"),
+            Some(Color::rgba(255, 128, 0, 128))
+        );
+        for invalid in ["ff8000", "#ff80", "#gg8000", "#ff80000", "#é12"] {
+            assert_eq!(
+                Color::from_hex(invalid),
+                None,
+                "{invalid} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_reject_non_finite_values() {
+        for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let builders = [
+                SettingsBuilder::new().width(value),
+                SettingsBuilder::new().height(value),
+                SettingsBuilder::new().node_radius(value),
+                SettingsBuilder::new().font_size(value),
+                SettingsBuilder::new().stroke_width(value),
+                SettingsBuilder::new().highlight_stroke_width(value),
+                SettingsBuilder::new().margin_x(value),
+                SettingsBuilder::new().margin_y(value),
+                SettingsBuilder::new().edge_label_position(value),
+                SettingsBuilder::new().min_edge_length(value),
+                SettingsBuilder::new().hierarchical_params(HierarchicalParams {
+                    level_spacing: Some(value),
+                    ..HierarchicalParams::default()
+                }),
+                SettingsBuilder::new().circular_params(CircularParams {
+                    radius: value,
+                    ..CircularParams::default()
+                }),
+                SettingsBuilder::new().circular_params(CircularParams {
+                    center: (0.5, value),
+                    ..CircularParams::default()
+                }),
+            ];
+            for (i, builder) in builders.into_iter().enumerate() {
+                assert!(builder.build().is_err(), "Field {i} accepted {value}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_reject_invalid_circular_params() {
+        for radius in [-0.5, 0.0] {
+            let result = SettingsBuilder::new()
+                .circular_params(CircularParams {
+                    radius,
+                    ..CircularParams::default()
+                })
+                .build();
+            assert_eq!(
+                result.err(),
+                Some(InvalidSettingsError::CircularParams(radius, 0.5, 0.5))
+            );
+        }
+    }
+
+    #[test]
+    fn test_reject_invalid_pinned_positions() {
+        let node = NodeIndex::new(1);
+        for value in [-0.1, 1.5, f32::NAN, f32::INFINITY] {
+            let result = SettingsBuilder::new()
+                .pinned_positions(HashMap::from([(node, (0.5, value))]))
+                .build();
+            assert!(
+                matches!(
+                    result.err(),
+                    Some(InvalidSettingsError::PinnedPosition(n, x, _)) if n == node && x == 0.5
+                ),
+                "Accepted pinned position (0.5, {value})"
+            );
+        }
+
+        let result = SettingsBuilder::new()
+            .pinned_positions(HashMap::from([(node, (0.0, 1.0))]))
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_try_setters() {
+        let result = SettingsBuilder::new().try_width(-1.0);
+        assert_eq!(
+            result.err(),
+            Some(InvalidSettingsError::Dimensions(-1.0, 1000.0))
+        );
+
+        let result = SettingsBuilder::new().try_margin_y(0.7);
+        assert_eq!(result.err(), Some(InvalidSettingsError::Margin(0.05, 0.7)));
+
+        let settings_builder = SettingsBuilder::new()
+            .try_width(500.0)
+            .and_then(|builder| builder.try_node_radius(10.0))
+            .expect("Values should be valid.");
+        assert_eq!(settings_builder.width, 500.0);
+        assert_eq!(settings_builder.node_radius, 10.0);
+    }
+
+    #[test]
+    fn test_highlight_path() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let nodes: Vec<_> = (0..4).map(|_| graph.add_node(())).collect();
+        for pair in nodes.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+
+        let settings_builder = SettingsBuilder::new()
+            .highlight_path(&graph, &nodes)
+            .expect("Consecutive nodes should be connected.");
+        let expected: HashSet<_> = graph.edge_indices().collect();
+        assert_eq!(settings_builder.highlighted_edges, expected);
+
+        // The path may traverse the undirected edges in reverse
+        let reversed: Vec<_> = nodes.iter().rev().copied().collect();
+        let settings_builder = SettingsBuilder::new()
+            .highlight_path(&graph, &reversed[..2])
+            .expect("Consecutive nodes should be connected.");
+        assert_eq!(
+            settings_builder.highlighted_edges,
+            HashSet::from([EdgeIndex::new(2)])
+        );
+
+        let result = SettingsBuilder::new().highlight_path(&graph, &[nodes[0], nodes[2]]);
+        assert_eq!(
+            result.err(),
+            Some(NotConnectedError(NodeIndex::new(0), NodeIndex::new(2)))
+        );
+    }
+
+    #[test]
+    fn test_edge_context_fns() {
+        let mut graph = UnGraph::<(), u32>::new_undirected();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 20);
+
+        let settings =
+            SettingsBuilder::new()
+                .edge_coloring_fn_with_context(&graph, |edge| {
+                    if *edge.weight > 10 {
+                        "red"
+                    } else {
+                        "black"
+                    }
+                })
+                .edge_label_fn_with_context(&graph, |edge| {
+                    format!("{}-{}", edge.source.index(), edge.target.index())
+                })
+                .build()
+                .expect("Values should be valid.");
+        assert_eq!((settings.edge_coloring_fn)(EdgeIndex::new(1)), "red");
+        assert_eq!((settings.edge_coloring_fn)(EdgeIndex::new(0)), "black");
+        assert_eq!((settings.edge_label_fn)(EdgeIndex::new(1)), "1-2");
+
+        let svg = graph_to_svg_string(&graph, &settings);
+        assert_eq!(svg.matches("stroke=\"red\"").count(), 1);
+        assert!(svg.contains(">0-1<"));
+    }
+
+    #[test]
+    fn test_publication_preset() {
+        let settings_builder = SettingsBuilder::publication_preset();
+        assert_eq!(settings_builder.background_color.as_deref(), Some("white"));
+        assert_eq!(settings_builder.stroke_width, 1.0);
+        assert!(settings_builder.font_size < SettingsBuilder::new().font_size);
+
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+        let settings = settings_builder
+            .width(200.0)
+            .build()
+            .expect("Values should be valid.");
+        let svg = graph_to_svg_string(&graph, &settings);
+        assert!(svg.contains("<rect width=\"200.0\" height=\"1000.0\" fill=\"white\"/>"));
+        assert!(svg.contains("stroke=\"black\" stroke-width=\"1.0\""));
+        // No background by default
+        let settings = SettingsBuilder::new()
+            .build()
+            .expect("Values should be valid.");
+        let svg = graph_to_svg_string(&graph, &settings);
+        assert!(!svg.contains("<rect"));
+    }
+}
@@ -1,45 +1,247 @@
 //! SVG to image conversion utilities.
 //!
-//! This module provides functions to convert SVG data into image formats (currently PNG only)
-//! using the `resvg` crate.
+//! This module provides functions to convert SVG data into image formats (PNG and, with the `webp`
+//! feature, WebP) using the `resvg` crate.
 //!
 //! The main functions are [`svg_to_pixmap`], which converts SVG data to a [`tiny_skia::Pixmap`],
 //! and [`svg_to_img`], which saves the SVG data as a PNG image to a specified path.
+//! [`svg_to_pixmap_with_options`] allows trading render quality for speed and [`render_into`]
+//! reuses an existing pixmap. With the `webp` feature, `svg_to_webp` saves the SVG data as a WebP
+//! image instead. With the `png-options` feature, `svg_to_img_with_options` allows choosing the
+//! color type and compression of the PNG image. With the `gif` feature, `frames_to_gif` saves a
+//! sequence of SVG data as an animated GIF image.
 //!
 //! For more information on usage, see the function documentation.
 
+use std::sync::Arc;
+
 use resvg::{
     render,
     tiny_skia::{self, Pixmap},
+    usvg::{fontdb, ShapeRendering, TextRendering},
 };
 
 use crate::errors::SvgToImageError;
 
+/// Quality of images rendered by [`svg_to_pixmap_with_options`] and [`Renderer::render_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderQuality {
+    /// Shapes are anti-aliased and text is rendered for legibility. This is the default and what
+    /// [`svg_to_pixmap`] renders.
+    #[default]
+    High,
+    /// Shapes are not anti-aliased and text is rendered for speed. This is faster, especially for
+    /// large images, but edges look jagged, which is mostly acceptable for thumbnails.
+    Fast,
+}
+
+/// Options for rendering SVG data to pixmap images, see [`svg_to_pixmap_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Quality of the image.
+    pub quality: RenderQuality,
+}
+
+/// Renderer converting SVG data to pixmap images, which loads the bundled font once on creation.
+///
+/// Loading the font is a considerable part of the time needed to render small graphs. Thus, when
+/// rendering many graphs, e.g. in a loop, reusing a single renderer is faster than creating a new
+/// one for every graph. [`svg_to_pixmap`] and the functions building on it reuse a renderer per
+/// thread.
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    fontdb: Arc<fontdb::Database>,
+}
+
+impl Renderer {
+    /// Creates a new renderer and loads the bundled `DejaVu Sans` font, which is used as default
+    /// sans-serif font.
+    pub fn new() -> Self {
+        Renderer::with_font(include_bytes!("../assets/DejaVuSans.ttf"))
+    }
+
+    /// Creates a new renderer which only loads the font given by `font_bytes`, e.g. the contents
+    /// of a TrueType or OpenType file. The font is used as default sans-serif font, such that it
+    /// is used for all labels.
+    ///
+    /// Since no other fonts are loaded, text is rendered the same on every machine. If the data
+    /// is not a valid font, no text is rendered.
+    pub fn with_font(font_bytes: &[u8]) -> Self {
+        let mut fontdb = fontdb::Database::new();
+        fontdb.load_font_data(font_bytes.to_vec());
+        let family = fontdb
+            .faces()
+            .next()
+            .and_then(|face| face.families.first())
+            .map(|(family, _)| family.clone());
+        if let Some(family) = family {
+            fontdb.set_sans_serif_family(family);
+        }
+        Renderer {
+            fontdb: Arc::new(fontdb),
+        }
+    }
+
+    /// Convert SVG data to a pixmap image.
+    ///
+    /// The provided width and height should match those used to generate the SVG data and should
+    /// be strictly positive. Otherwise, an appropriate error will be returned. This includes
+    /// dimensions which are too large to allocate the image.
+    pub fn render(
+        &self,
+        svg_data: &str,
+        width: f32,
+        height: f32,
+    ) -> Result<Pixmap, SvgToImageError> {
+        self.render_with_options(svg_data, width, height, RenderOptions::default())
+    }
+
+    /// Same as [`Renderer::render`], but renders the image with the quality given by `options`.
+    pub fn render_with_options(
+        &self,
+        svg_data: &str,
+        width: f32,
+        height: f32,
+        options: RenderOptions,
+    ) -> Result<Pixmap, SvgToImageError> {
+        let invalid_dimensions = || SvgToImageError::InvalidDimensions(width, height);
+        let svg_tree = self.parse(svg_data, width, height, options)?;
+
+        // Render to pixmap. Fails if a dimension is zero after truncation or the pixmap is too
+        // large to allocate.
+        let mut pixmap = Pixmap::new(width as u32, height as u32).ok_or_else(invalid_dimensions)?;
+        render(
+            &svg_tree,
+            tiny_skia::Transform::identity(),
+            &mut pixmap.as_mut(),
+        );
+
+        Ok(pixmap)
+    }
+
+    /// Same as [`Renderer::render`], but renders into the provided `pixmap` instead of allocating
+    /// a new one, which is faster when rendering many images of the same size, e.g. the frames of
+    /// an animation.
+    ///
+    /// The pixmap is cleared before rendering. The dimensions of the SVG data have to match those
+    /// of the pixmap. Otherwise, [`SvgToImageError::DimensionMismatch`] is returned and the pixmap
+    /// is left unchanged.
+    pub fn render_into(&self, svg_data: &str, pixmap: &mut Pixmap) -> Result<(), SvgToImageError> {
+        let (width, height) = (pixmap.width(), pixmap.height());
+        let svg_tree = self.parse(
+            svg_data,
+            width as f32,
+            height as f32,
+            RenderOptions::default(),
+        )?;
+        let size = svg_tree.size();
+        if size.width() as u32 != width || size.height() as u32 != height {
+            return Err(SvgToImageError::DimensionMismatch(
+                size.width(),
+                size.height(),
+                width,
+                height,
+            ));
+        }
+
+        pixmap.fill(tiny_skia::Color::TRANSPARENT);
+        render(
+            &svg_tree,
+            tiny_skia::Transform::identity(),
+            &mut pixmap.as_mut(),
+        );
+
+        Ok(())
+    }
+
+    /// Parses the SVG data using the loaded font and the given `options`. The `width` and `height`
+    /// are used as size of the SVG data if it does not specify one.
+    fn parse(
+        &self,
+        svg_data: &str,
+        width: f32,
+        height: f32,
+        options: RenderOptions,
+    ) -> Result<resvg::usvg::Tree, SvgToImageError> {
+        let (shape_rendering, text_rendering) = match options.quality {
+            RenderQuality::High => (
+                ShapeRendering::GeometricPrecision,
+                TextRendering::OptimizeLegibility,
+            ),
+            RenderQuality::Fast => (ShapeRendering::OptimizeSpeed, TextRendering::OptimizeSpeed),
+        };
+        let opt = resvg::usvg::Options {
+            fontdb: Arc::clone(&self.fontdb),
+            default_size: resvg::usvg::Size::from_wh(width, height)
+                .ok_or(SvgToImageError::InvalidDimensions(width, height))?,
+            shape_rendering,
+            text_rendering,
+            ..Default::default()
+        };
+
+        Ok(resvg::usvg::Tree::from_data(svg_data.as_bytes(), &opt)?)
+    }
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Renderer::new()
+    }
+}
+
+thread_local! {
+    /// Renderer shared by all calls to [`svg_to_pixmap`] on the same thread, such that the font is
+    /// only loaded once per thread.
+    static RENDERER: Renderer = Renderer::new();
+}
+
 /// Convert SVG data to a pixmap image.
 ///
 /// The provided width and height should match those used to generate the SVG data and should be
 /// strictly positive. Otherwise, an appropriate error will be returned.
+///
+/// Uses a [`Renderer`] which is shared by all calls on the same thread.
 pub fn svg_to_pixmap(svg_data: &str, width: f32, height: f32) -> Result<Pixmap, SvgToImageError> {
-    let mut opt = resvg::usvg::Options::default();
-    opt.fontdb_mut()
-        .load_font_data(include_bytes!("../assets/DejaVuSans.ttf").to_vec());
-    opt.fontdb_mut().set_sans_serif_family("DejaVu Sans");
-    opt.default_size = resvg::usvg::Size::from_wh(width, height).expect(
-        "Provided dimensions should be strictly positive, as Settings struct is validated on \
-         creation.",
-    );
+    RENDERER.with(|renderer| renderer.render(svg_data, width, height))
+}
 
-    let svg_tree = resvg::usvg::Tree::from_data(svg_data.as_bytes(), &opt)?;
+/// Same as [`svg_to_pixmap`], but renders the image with the quality given by `options`.
+///
+/// [`RenderQuality::Fast`] trades anti-aliasing for speed, which suits thumbnails, while the
+/// default [`RenderQuality::High`] suits print.
+pub fn svg_to_pixmap_with_options(
+    svg_data: &str,
+    width: f32,
+    height: f32,
+    options: RenderOptions,
+) -> Result<Pixmap, SvgToImageError> {
+    RENDERER.with(|renderer| renderer.render_with_options(svg_data, width, height, options))
+}
 
-    // Render to pixmap
-    let mut pixmap = Pixmap::new(width as u32, height as u32).unwrap();
-    render(
-        &svg_tree,
-        tiny_skia::Transform::identity(),
-        &mut pixmap.as_mut(),
-    );
+/// Renders SVG data into the provided `pixmap`, reusing its buffer instead of allocating a new
+/// pixmap as [`svg_to_pixmap`] does, e.g. to render the frames of an animation.
+///
+/// The pixmap is cleared before rendering. If the dimensions of the SVG data do not match those of
+/// the pixmap, an appropriate error is returned.
+///
+/// Uses a [`Renderer`] which is shared by all calls on the same thread.
+pub fn render_into(svg_data: &str, pixmap: &mut Pixmap) -> Result<(), SvgToImageError> {
+    RENDERER.with(|renderer| renderer.render_into(svg_data, pixmap))
+}
 
-    Ok(pixmap)
+/// Same as [`svg_to_pixmap`], but renders text using only the font given by `font_bytes`, e.g. the
+/// contents of a TrueType or OpenType file.
+///
+/// This makes the rendered text independent of the fonts available on the machine. Note that the
+/// font is loaded on every call. To render several graphs, create a [`Renderer`] using
+/// [`Renderer::with_font`] once instead.
+pub fn svg_to_pixmap_with_font(
+    svg_data: &str,
+    width: f32,
+    height: f32,
+    font_bytes: &[u8],
+) -> Result<Pixmap, SvgToImageError> {
+    Renderer::with_font(font_bytes).render(svg_data, width, height)
 }
 
 /// Convert SVG data to a png image and save it to the specified path.
@@ -66,15 +268,278 @@ pub fn svg_to_img(
     Ok(())
 }
 
+/// Color type of PNG images written by [`svg_to_img_with_options`].
+#[cfg(feature = "png-options")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PngColorType {
+    /// Red, green, blue and alpha channel with 8 bits each. This is the default and what
+    /// [`svg_to_img`] writes.
+    #[default]
+    Rgba,
+    /// Red, green and blue channel with 8 bits each.
+    Rgb,
+    /// Gray and alpha channel with 8 bits each.
+    GrayscaleAlpha,
+    /// Gray channel with 8 bits.
+    Grayscale,
+}
+
+/// Compression of PNG images written by [`svg_to_img_with_options`].
+#[cfg(feature = "png-options")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PngCompression {
+    /// Balance between encoding speed and file size. This is the default.
+    #[default]
+    Default,
+    /// Fast encoding with larger files.
+    Fast,
+    /// Smallest files with slow encoding.
+    Best,
+}
+
+/// Options for PNG images written by [`svg_to_img_with_options`].
+#[cfg(feature = "png-options")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PngOptions {
+    /// Color type of the image.
+    pub color_type: PngColorType,
+    /// Compression of the image.
+    pub compression: PngCompression,
+}
+
+/// Same as [`svg_to_img`], but encodes the PNG image with the color type and compression given by
+/// `options`.
+///
+/// Grayscale images are considerably smaller for diagrams without colors. For color types without
+/// alpha channel, the image is drawn onto a white background.
+#[cfg(feature = "png-options")]
+pub fn svg_to_img_with_options(
+    svg_data: &str,
+    width: f32,
+    height: f32,
+    path: impl AsRef<std::path::Path>,
+    options: PngOptions,
+) -> Result<(), SvgToImageError> {
+    let pixmap = svg_to_pixmap(svg_data, width, height)?;
+
+    // Create target directory if it doesn't exist
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    encode_png(&pixmap, options, file)?;
+
+    Ok(())
+}
+
+/// Encodes the pixmap as a PNG image with the given `options` into the provided `writer`.
+#[cfg(feature = "png-options")]
+fn encode_png(
+    pixmap: &Pixmap,
+    options: PngOptions,
+    writer: impl std::io::Write,
+) -> Result<(), SvgToImageError> {
+    use image::{
+        codecs::png::{CompressionType, FilterType, PngEncoder},
+        DynamicImage, ImageEncoder, RgbaImage,
+    };
+
+    let has_alpha = matches!(
+        options.color_type,
+        PngColorType::Rgba | PngColorType::GrayscaleAlpha
+    );
+    let data: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            if has_alpha {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            } else {
+                // Pixels are premultiplied, so adding the missing coverage composites them onto
+                // a white background
+                let background = 255 - pixel.alpha();
+                [
+                    pixel.red() + background,
+                    pixel.green() + background,
+                    pixel.blue() + background,
+                    255,
+                ]
+            }
+        })
+        .collect();
+    let image = DynamicImage::ImageRgba8(
+        RgbaImage::from_raw(pixmap.width(), pixmap.height(), data)
+            .expect("The buffer should have the size of the pixmap."),
+    );
+    let image = match options.color_type {
+        PngColorType::Rgba => image,
+        PngColorType::Rgb => DynamicImage::ImageRgb8(image.to_rgb8()),
+        PngColorType::GrayscaleAlpha => DynamicImage::ImageLumaA8(image.to_luma_alpha8()),
+        PngColorType::Grayscale => DynamicImage::ImageLuma8(image.to_luma8()),
+    };
+
+    let compression = match options.compression {
+        PngCompression::Default => CompressionType::Default,
+        PngCompression::Fast => CompressionType::Fast,
+        PngCompression::Best => CompressionType::Best,
+    };
+    PngEncoder::new_with_quality(writer, compression, FilterType::Adaptive).write_image(
+        image.as_bytes(),
+        image.width(),
+        image.height(),
+        image.color().into(),
+    )?;
+
+    Ok(())
+}
+
+/// Convert SVG data to a lossless WebP image and save it to the specified path.
+///
+/// WebP images are usually considerably smaller than PNG images, especially for large graphs with
+/// many colors. The alpha channel is preserved.
+///
+/// The provided width and height should match those used to generate the SVG data and should be
+/// strictly positive. Otherwise, an appropriate error will be returned.
+///
+/// Calls [`svg_to_pixmap`] internally.
+#[cfg(feature = "webp")]
+pub fn svg_to_webp(
+    svg_data: &str,
+    width: f32,
+    height: f32,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(), SvgToImageError> {
+    let pixmap = svg_to_pixmap(svg_data, width, height)?;
+
+    // Create target directory if it doesn't exist
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    encode_webp(&pixmap, file)?;
+
+    Ok(())
+}
+
+/// Encodes the pixmap as a lossless WebP image into the provided `writer`.
+///
+/// The pixels of a pixmap are stored with premultiplied alpha, while WebP expects straight alpha.
+#[cfg(feature = "webp")]
+fn encode_webp(pixmap: &Pixmap, writer: impl std::io::Write) -> Result<(), SvgToImageError> {
+    let data: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+
+    image::codecs::webp::WebPEncoder::new_lossless(writer).encode(
+        &data,
+        pixmap.width(),
+        pixmap.height(),
+        image::ExtendedColorType::Rgba8,
+    )?;
+
+    Ok(())
+}
+
+/// Speed of the color quantization of GIF frames from 1 (best quality) to 30 (fastest). Graph
+/// drawings have few colors, so faster quantization hardly reduces the quality.
+#[cfg(feature = "gif")]
+const GIF_ENCODER_SPEED: i32 = 10;
+
+/// Convert a sequence of SVG data to an animated GIF image, which shows each frame for
+/// `frame_delay` and loops forever, and save it to the specified path.
+///
+/// This is useful to visualize graph algorithms step by step, with one frame per step. The
+/// provided width and height should match those used to generate the SVG data of all frames and
+/// should be strictly positive. Otherwise, an appropriate error will be returned.
+///
+/// The frames are rendered one after another into the same pixmap using [`render_into`], such
+/// that only a single frame is kept in memory at a time.
+#[cfg(feature = "gif")]
+pub fn frames_to_gif(
+    frames: &[String],
+    width: f32,
+    height: f32,
+    frame_delay: std::time::Duration,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(), SvgToImageError> {
+    // Create target directory if it doesn't exist
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    encode_gif(frames, width, height, frame_delay, file)?;
+
+    Ok(())
+}
+
+/// Renders the SVG data of the frames and encodes them as frames of an endlessly looping GIF image
+/// into the provided `writer`.
+///
+/// Each frame is encoded right after rendering it into a pixmap, which is reused for all frames.
+/// The pixels of a pixmap are stored with premultiplied alpha, while GIF expects straight alpha.
+#[cfg(feature = "gif")]
+fn encode_gif(
+    frames: &[String],
+    width: f32,
+    height: f32,
+    frame_delay: std::time::Duration,
+    writer: impl std::io::Write,
+) -> Result<(), SvgToImageError> {
+    use image::{
+        codecs::gif::{GifEncoder, Repeat},
+        Delay, Frame, RgbaImage,
+    };
+
+    let mut encoder = GifEncoder::new_with_speed(writer, GIF_ENCODER_SPEED);
+    encoder.set_repeat(Repeat::Infinite)?;
+    let delay = Delay::from_saturating_duration(frame_delay);
+    let mut pixmap: Option<Pixmap> = None;
+    for svg_data in frames {
+        let pixmap = match pixmap.as_mut() {
+            Some(pixmap) => {
+                render_into(svg_data, pixmap)?;
+                pixmap
+            }
+            None => pixmap.insert(svg_to_pixmap(svg_data, width, height)?),
+        };
+        let data: Vec<u8> = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect();
+        let buffer = RgbaImage::from_raw(pixmap.width(), pixmap.height(), data)
+            .expect("The buffer should have the size of the pixmap.");
+        encoder.encode_frame(Frame::from_parts(buffer, 0, 0, delay))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
 
     use image::{DynamicImage, GenericImageView, ImageReader};
+    use petgraph::graph::UnGraph;
     use resvg::tiny_skia::Pixmap;
 
+    use super::{RenderOptions, RenderQuality};
     use crate::{
+        errors::SvgToImageError,
         graph_to_svg::graph_to_svg_string,
+        settings::SettingsBuilder,
         tests::{custom_labels_test_case, position_map_test_case},
     };
 
@@ -153,4 +618,211 @@ mod tests {
 
         assert_images_equal(&pixmap, "examples/results/position_map.png".as_ref());
     }
+
+    #[test]
+    fn test_svg_to_pixmap_invalid_dimensions() {
+        let (graph, settings) = position_map_test_case();
+        let svg_data = graph_to_svg_string(&graph, &settings);
+
+        for (width, height) in [(0.0, 500.0), (500.0, -1.0), (0.5, 500.0), (f32::NAN, 500.0)] {
+            assert!(matches!(
+                super::svg_to_pixmap(&svg_data, width, height),
+                Err(SvgToImageError::InvalidDimensions(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_svg_to_pixmap_with_options() {
+        let (graph, settings) = position_map_test_case();
+        let svg_data = graph_to_svg_string(&graph, &settings);
+        let count_partially_transparent_pixels = |pixmap: &Pixmap| {
+            pixmap
+                .pixels()
+                .iter()
+                .filter(|p| p.alpha() > 0 && p.alpha() < 255)
+                .count()
+        };
+
+        for quality in [RenderQuality::High, RenderQuality::Fast] {
+            let pixmap = super::svg_to_pixmap_with_options(
+                &svg_data,
+                settings.width,
+                settings.height,
+                RenderOptions { quality },
+            )
+            .expect("SVG to pixmap conversion should succeed.");
+            assert_eq!(
+                (pixmap.width(), pixmap.height()),
+                (settings.width as u32, settings.height as u32)
+            );
+
+            // Only anti-aliasing produces partially transparent pixels
+            let partially_transparent_pixels = count_partially_transparent_pixels(&pixmap);
+            match quality {
+                RenderQuality::High => assert!(partially_transparent_pixels > 0),
+                RenderQuality::Fast => assert_eq!(partially_transparent_pixels, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_svg_to_pixmap_with_node_shadow() {
+        let mut graph = UnGraph::<(), ()>::new_undirected();
+        graph.add_node(());
+        // The node is centered at (50, 50) and its outline ends at a y of about 70.5
+        let alpha_below_node = |node_shadow| {
+            let settings = SettingsBuilder::new()
+                .width(100.0)
+                .height(100.0)
+                .margin_x(0.0)
+                .margin_y(0.0)
+                .node_radius(20.0)
+                .position_map(|_| (0.5, 0.5))
+                .node_shadow(node_shadow)
+                .build()
+                .expect("Values should be valid.");
+            let svg_data = graph_to_svg_string(&graph, &settings);
+            let pixmap = super::svg_to_pixmap(&svg_data, settings.width, settings.height)
+                .expect("SVG to pixmap conversion should succeed.");
+            pixmap
+                .pixel(52, 72)
+                .expect("Pixel should be within the pixmap")
+                .alpha()
+        };
+
+        assert!(alpha_below_node(true) > 0);
+        assert_eq!(alpha_below_node(false), 0);
+    }
+
+    #[test]
+    fn test_svg_to_pixmap_with_font() {
+        let svg_data = "<svg width=\"100\" height=\"50\" xmlns=\"http://www.w3.org/2000/svg\">
+    <text x=\"50\" y=\"25\" font-size=\"20px\" font-family=\"sans-serif\" fill=\"black\" \
+                        text-anchor=\"middle\" dominant-baseline=\"central\">Text</text>
+</svg>";
+        let count_text_pixels =
+            |pixmap: &Pixmap| pixmap.pixels().iter().filter(|p| p.alpha() > 0).count();
+
+        let font = include_bytes!("../assets/DejaVuSans.ttf");
+        let pixmap = super::svg_to_pixmap_with_font(svg_data, 100.0, 50.0, font).unwrap();
+        assert!(count_text_pixels(&pixmap) > 0);
+
+        // Without a valid font, no text is rendered
+        let pixmap = super::svg_to_pixmap_with_font(svg_data, 100.0, 50.0, &[]).unwrap();
+        assert_eq!(count_text_pixels(&pixmap), 0);
+    }
+
+    #[test]
+    fn test_render_into() {
+        let svg_for = |x| {
+            format!(
+                "<svg width=\"100\" height=\"50\" xmlns=\"http://www.w3.org/2000/svg\">
+    <rect x=\"{x}\" y=\"0\" width=\"50\" height=\"50\" fill=\"black\"/>
+</svg>"
+            )
+        };
+        let is_opaque = |pixmap: &Pixmap, x| {
+            pixmap
+                .pixel(x, 25)
+                .expect("Pixel should be within the pixmap")
+                .alpha()
+                == 255
+        };
+
+        let mut pixmap = Pixmap::new(100, 50).expect("Dimensions should be valid.");
+        super::render_into(&svg_for(0), &mut pixmap).expect("Rendering should succeed.");
+        assert!(is_opaque(&pixmap, 10) && !is_opaque(&pixmap, 90));
+
+        // The second image replaces the first one
+        super::render_into(&svg_for(50), &mut pixmap).expect("Rendering should succeed.");
+        assert!(!is_opaque(&pixmap, 10) && is_opaque(&pixmap, 90));
+
+        let mut smaller = Pixmap::new(50, 50).expect("Dimensions should be valid.");
+        assert!(matches!(
+            super::render_into(&svg_for(0), &mut smaller),
+            Err(SvgToImageError::DimensionMismatch(100.0, 50.0, 50, 50))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "png-options")]
+    fn test_encode_grayscale_png() {
+        use super::{encode_png, PngColorType, PngOptions};
+
+        let (graph, settings) = position_map_test_case();
+        let svg_data = graph_to_svg_string(&graph, &settings);
+        let pixmap = super::svg_to_pixmap(&svg_data, settings.width, settings.height).unwrap();
+
+        let encode = |color_type| {
+            let mut png = Vec::new();
+            let options = PngOptions {
+                color_type,
+                ..PngOptions::default()
+            };
+            encode_png(&pixmap, options, &mut png).unwrap();
+            png
+        };
+        let rgba = encode(PngColorType::Rgba);
+        let grayscale = encode(PngColorType::Grayscale);
+
+        assert!(grayscale.len() < rgba.len());
+        let image = image::load_from_memory(&grayscale).unwrap();
+        assert_eq!(image.color(), image::ColorType::L8);
+        assert_eq!(
+            (image.width(), image.height()),
+            (pixmap.width(), pixmap.height())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "webp")]
+    fn test_encode_webp() {
+        let (graph, settings) = position_map_test_case();
+
+        let svg_data = graph_to_svg_string(&graph, &settings);
+        let pixmap = super::svg_to_pixmap(&svg_data, settings.width, settings.height)
+            .expect("SVG to pixmap conversion should succeed.");
+        let mut webp = Vec::new();
+        super::encode_webp(&pixmap, &mut webp).expect("WebP encoding should succeed.");
+
+        assert_eq!(&webp[0..4], b"RIFF");
+        assert_eq!(&webp[8..12], b"WEBP");
+    }
+
+    #[test]
+    #[cfg(feature = "gif")]
+    fn test_encode_gif() {
+        use image::{codecs::gif::GifDecoder, AnimationDecoder};
+
+        let (graph, settings) = position_map_test_case();
+        // The second frame shows the graph in the default circular layout
+        let other_settings = SettingsBuilder::new()
+            .width(settings.width)
+            .height(settings.height)
+            .build()
+            .expect("Values should be valid.");
+        let frames = [
+            graph_to_svg_string(&graph, &settings),
+            graph_to_svg_string(&graph, &other_settings),
+        ];
+        let mut gif = Vec::new();
+        super::encode_gif(
+            &frames,
+            settings.width,
+            settings.height,
+            std::time::Duration::from_millis(500),
+            &mut gif,
+        )
+        .expect("GIF encoding should succeed.");
+
+        assert_eq!(&gif[0..6], b"GIF89a");
+        let frames = GifDecoder::new(std::io::Cursor::new(gif))
+            .expect("GIF should be decodable")
+            .into_frames()
+            .collect_frames()
+            .expect("Frames should be decodable");
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].delay().numer_denom_ms(), (500, 1));
+    }
 }
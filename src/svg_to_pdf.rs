@@ -0,0 +1,146 @@
+//! SVG to PDF conversion utilities.
+//!
+//! This module provides functions to convert SVG data into vector PDF documents using the
+//! `svg2pdf` crate. In contrast to the raster images of the `svg_to_img` module, the resulting
+//! documents are resolution-independent, which makes them well suited for print.
+//!
+//! The main functions are [`svg_to_pdf_bytes`], which converts SVG data to the bytes of a PDF
+//! document, and [`svg_to_pdf`], which saves the SVG data as a PDF document to a specified path.
+//!
+//! For more information on usage, see the function documentation.
+
+use svg2pdf::usvg::{fontdb, PostProcessingSteps, Size, Tree, TreeParsing, TreePostProc};
+
+use crate::errors::SvgToPdfError;
+
+/// Convert SVG data to the bytes of a PDF document.
+///
+/// The provided width and height should match those used to generate the SVG data and should be
+/// strictly positive. Otherwise, an appropriate error will be returned.
+///
+/// Text is converted into paths using the bundled `DejaVu Sans` font, such that the document
+/// renders the same regardless of the fonts installed on the viewing system.
+pub fn svg_to_pdf_bytes(svg_data: &str, width: f32, height: f32) -> Result<Vec<u8>, SvgToPdfError> {
+    let opt = svg2pdf::usvg::Options {
+        default_size: Size::from_wh(width, height)
+            .ok_or(SvgToPdfError::InvalidDimensions(width, height))?,
+        ..Default::default()
+    };
+
+    let mut svg_tree = Tree::from_str(svg_data, &opt)?;
+
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_font_data(include_bytes!("../assets/DejaVuSans.ttf").to_vec());
+    fontdb.set_sans_serif_family("DejaVu Sans");
+    svg_tree.postprocess(PostProcessingSteps::default(), &fontdb);
+
+    Ok(svg2pdf::convert_tree(
+        &svg_tree,
+        svg2pdf::Options::default(),
+    ))
+}
+
+/// Convert SVG data to a PDF document and save it to the specified path.
+///
+/// The provided width and height should match those used to generate the SVG data and should be
+/// strictly positive. Otherwise, an appropriate error will be returned.
+///
+/// Calls [`svg_to_pdf_bytes`] internally.
+pub fn svg_to_pdf(
+    svg_data: &str,
+    width: f32,
+    height: f32,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(), SvgToPdfError> {
+    let pdf = svg_to_pdf_bytes(svg_data, width, height)?;
+
+    // Create target directory if it doesn't exist
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, pdf)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::DiGraph;
+    use svg2pdf::usvg::{Color, Group, Node, Options, Paint, Tree, TreeParsing};
+
+    use crate::{
+        errors::SvgToPdfError,
+        graph_to_svg::graph_to_svg_string,
+        settings::{ArrowMode, SettingsBuilder},
+        tests::custom_labels_test_case,
+    };
+
+    /// Returns the number of paths in the group and its descendants which are filled with `color`.
+    fn count_paths_filled_with(group: &Group, color: Color) -> usize {
+        group
+            .children
+            .iter()
+            .map(|node| match node {
+                Node::Group(group) => count_paths_filled_with(group, color),
+                Node::Path(path) => usize::from(
+                    path.fill
+                        .as_ref()
+                        .map_or(false, |fill| fill.paint == Paint::Color(color)),
+                ),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_svg_to_pdf_on_custom_labels() {
+        let (graph, settings) = custom_labels_test_case();
+
+        let svg_data = graph_to_svg_string(&graph, &settings);
+        let pdf = super::svg_to_pdf_bytes(&svg_data, settings.width, settings.height)
+            .expect("SVG to PDF conversion should succeed.");
+
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_svg_to_pdf_with_arrows() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+        let settings = SettingsBuilder::new()
+            .edge_arrow_mode(ArrowMode::End)
+            .edge_coloring_fn(|_| "red")
+            .build()
+            .expect("Values should be valid.");
+        let svg_data = graph_to_svg_string(&graph, &settings);
+
+        // The SVG data is parsed like for the PDF document, where the arrowhead is the only path
+        // filled in the color of the edge
+        let svg_tree =
+            Tree::from_str(&svg_data, &Options::default()).expect("SVG data should be valid.");
+        assert_eq!(
+            count_paths_filled_with(&svg_tree.root, Color::new_rgb(255, 0, 0)),
+            1
+        );
+
+        let pdf = super::svg_to_pdf_bytes(&svg_data, settings.width, settings.height)
+            .expect("SVG to PDF conversion should succeed.");
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_svg_to_pdf_with_invalid_dimensions() {
+        let (graph, settings) = custom_labels_test_case();
+        let svg_data = graph_to_svg_string(&graph, &settings);
+
+        for (width, height) in [(0.0, 100.0), (100.0, -1.0), (f32::NAN, 100.0)] {
+            assert!(matches!(
+                super::svg_to_pdf_bytes(&svg_data, width, height),
+                Err(SvgToPdfError::InvalidDimensions(..))
+            ));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Ready-made width functions for common use cases.
+//!
+//! The functions of this module return closures which map edge weights to stroke widths in pixels,
+//! e.g. [`log_scale`], which keeps both light and heavy edges visible for weights spanning several
+//! orders of magnitude.
+
+/// Returns a function which maps a weight in [`min_weight`, `max_weight`] to a width in
+/// [`min_width`, `max_width`] pixels on a logarithmic scale.
+///
+/// That is, weights whose ratios are equal are mapped to equally spaced widths, e.g. for weights
+/// from 1 to 10000, the weight 100 is mapped to the width halfway between `min_width` and
+/// `max_width`. Weights outside of the range are clamped to it and NaN is treated as `min_weight`.
+/// If the range is empty, i.e. `min_weight >= max_weight`, or `min_weight` is not positive, all
+/// weights are mapped to `min_width`.
+pub fn log_scale(
+    min_weight: f32,
+    max_weight: f32,
+    min_width: f32,
+    max_width: f32,
+) -> impl Fn(f32) -> f32 {
+    let valid_range = min_weight > 0.0 && min_weight < max_weight;
+    let (log_min, log_max) = (min_weight.ln(), max_weight.ln());
+
+    move |weight| {
+        if !valid_range || weight.is_nan() {
+            return min_width;
+        }
+        let t = (weight.clamp(min_weight, max_weight).ln() - log_min) / (log_max - log_min);
+        min_width + (max_width - min_width) * t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::log_scale;
+
+    #[test]
+    fn test_log_scale() {
+        let width = log_scale(1.0, 10000.0, 1.0, 9.0);
+        assert_eq!(width(1.0), 1.0);
+        assert_eq!(width(10000.0), 9.0);
+        // Weights of equal ratios are mapped to equally spaced widths
+        assert!((width(100.0) - 5.0).abs() < 1e-4);
+        assert!((width(10.0) - 3.0).abs() < 1e-4);
+        assert!((width(1000.0) - 7.0).abs() < 1e-4);
+
+        assert_eq!(width(0.0), 1.0);
+        assert_eq!(width(f32::NAN), 1.0);
+        assert_eq!(width(1e9), 9.0);
+        assert_eq!(log_scale(0.0, 10.0, 1.0, 9.0)(5.0), 1.0);
+        assert_eq!(log_scale(10.0, 10.0, 1.0, 9.0)(10.0), 1.0);
+    }
+}